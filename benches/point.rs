@@ -0,0 +1,201 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, BenchmarkId, Criterion};
+use hypercube_optimizer::hypercube::Hypercube;
+use hypercube_optimizer::point;
+use hypercube_optimizer::point::Point;
+
+/// Dimensions benchmarked, on both sides of `INLINE_DIMENSION`
+const DIMENSIONS: [u32; 6] = [2, 4, 8, 16, 32, 128];
+
+/// Number of points in the populations whose memory is reported
+const POPULATION_SIZE: usize = 1000;
+
+/// Allocator that counts heap allocations and allocated bytes, so the benchmarks can report
+/// allocations per operation and memory per point
+struct CountingAllocator {
+    allocations: AtomicUsize,
+    bytes: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator {
+    allocations: AtomicUsize::new(0),
+    bytes: AtomicUsize::new(0),
+};
+
+/// Returns the average number of heap allocations of `operation` over `runs` runs
+fn allocations_per_run<F: FnMut()>(runs: usize, mut operation: F) -> f64 {
+    let before = ALLOCATOR.allocations.load(Ordering::Relaxed);
+
+    for _ in 0..runs {
+        operation();
+    }
+
+    (ALLOCATOR.allocations.load(Ordering::Relaxed) - before) as f64 / runs as f64
+}
+
+/// Prints the heap allocations of common point operations per dimension
+fn report_allocations() {
+    println!("heap allocations per operation");
+    println!(
+        "{:>9} {:>8} {:>8} {:>8} {:>10}",
+        "dimension", "fill", "add", "scale", "randomize"
+    );
+
+    for dimension in DIMENSIONS {
+        let a = point![1.5; dimension];
+        let b = point![0.5; dimension];
+
+        let mut hypercube = Hypercube::with_population_size(dimension, -5.0, 5.0, 100);
+        hypercube.set_seed(1);
+
+        let fill = allocations_per_run(1000, || {
+            black_box(Point::fill(black_box(1.0), dimension));
+        });
+        let add = allocations_per_run(1000, || {
+            black_box(black_box(&a) + black_box(&b));
+        });
+        let scale = allocations_per_run(1000, || {
+            black_box(black_box(&a).scale(2.0));
+        });
+        let randomize = allocations_per_run(10, || hypercube.randomize_pop()) / 100.0;
+
+        println!(
+            "{:>9} {:>8.2} {:>8.2} {:>8.2} {:>10.2}",
+            dimension, fill, add, scale, randomize
+        );
+    }
+}
+
+/// Prints the memory every point of a population takes per dimension, inline in the population
+/// and on the heap, next to what a boxed slice of coordinates would take
+fn report_memory() {
+    println!("bytes per point of a population");
+    println!(
+        "{:>9} {:>8} {:>8} {:>8} {:>12}",
+        "dimension", "inline", "heap", "total", "boxed slice"
+    );
+
+    for dimension in DIMENSIONS {
+        let before = ALLOCATOR.bytes.load(Ordering::Relaxed);
+        let population: Vec<Point> = (0..POPULATION_SIZE)
+            .map(|_| Point::fill(1.0, dimension))
+            .collect();
+        let total = (ALLOCATOR.bytes.load(Ordering::Relaxed) - before) / POPULATION_SIZE;
+        black_box(population);
+
+        let inline = size_of::<Point>();
+        let boxed_slice = size_of::<Box<[f64]>>() + dimension as usize * size_of::<f64>();
+
+        println!(
+            "{:>9} {:>8} {:>8} {:>8} {:>12}",
+            dimension,
+            inline,
+            total - inline,
+            total,
+            boxed_slice
+        );
+    }
+}
+
+fn point_arithmetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("point_arithmetic");
+
+    for dimension in DIMENSIONS {
+        let a = point![1.5; dimension];
+        let b = point![0.5; dimension];
+
+        group.bench_with_input(
+            BenchmarkId::new("add", dimension),
+            &dimension,
+            |bench, _| bench.iter(|| black_box(&a) + black_box(&b)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("mul", dimension),
+            &dimension,
+            |bench, _| bench.iter(|| black_box(&a) * black_box(&b)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("scale", dimension),
+            &dimension,
+            |bench, _| bench.iter(|| black_box(&a).scale(2.0)),
+        );
+    }
+
+    group.finish();
+}
+
+fn point_creation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("point_creation");
+
+    for dimension in DIMENSIONS {
+        let coords = vec![1.5; dimension as usize];
+
+        group.bench_with_input(
+            BenchmarkId::new("fill", dimension),
+            &dimension,
+            |bench, &n| bench.iter(|| Point::fill(black_box(1.0), n)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("from_slice", dimension),
+            &coords,
+            |bench, coords| bench.iter(|| Point::from_slice(black_box(coords))),
+        );
+    }
+
+    group.finish();
+}
+
+fn population_sampling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("population_sampling");
+
+    for dimension in DIMENSIONS {
+        let mut hypercube = Hypercube::with_population_size(dimension, -5.0, 5.0, 100);
+        hypercube.set_seed(1);
+
+        group.bench_with_input(
+            BenchmarkId::new("randomize_pop", dimension),
+            &dimension,
+            |bench, _| bench.iter(|| hypercube.randomize_pop()),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    point_arithmetic,
+    point_creation,
+    population_sampling
+);
+
+fn main() {
+    report_allocations();
+    report_memory();
+
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+}
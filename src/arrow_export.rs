@@ -0,0 +1,193 @@
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, UInt32Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+
+use crate::eval_log::EvalLogReader;
+use crate::evaluation::PointEval;
+use crate::result::{HypercubeOptimizerResult, IterationRecord};
+
+/// Number of evaluations converted to a record batch at a time when exporting an evaluation log
+const LOG_BATCH_SIZE: usize = 65536;
+
+impl HypercubeOptimizerResult {
+    /// Converts the per-loop optimization history to an Arrow record batch with the columns
+    /// `loop_index`, `fn_evals`, `best_f` and `diagonal_len`
+    pub fn history_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        history_record_batch(self.get_history())
+    }
+
+    /// Writes the per-loop optimization history to a Parquet file at `path`, with the columns of
+    /// `history_record_batch`
+    pub fn write_history_parquet<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        let batch = self.history_record_batch()?;
+
+        let mut writer = ArrowWriter::try_new(File::create(path)?, batch.schema(), None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+
+        Ok(())
+    }
+}
+
+/// Converts evaluations of `dimension`-dimensional points to an Arrow record batch with one
+/// column per coordinate (`x0`, `x1`, ...) and a final `f` column holding the images
+pub fn evaluations_record_batch(
+    evaluations: &[PointEval],
+    dimension: u32,
+) -> Result<RecordBatch, ArrowError> {
+    assert_ne!(dimension, 0, "dimension cannot be zero");
+    assert!(
+        evaluations.iter().all(|e| e.get_point().dim() == dimension),
+        "evaluation dimension does not match dimension"
+    );
+
+    let mut columns: Vec<ArrayRef> = (0..dimension as usize)
+        .map(|axis| {
+            let coords: Float64Array = evaluations
+                .iter()
+                .map(|e| *e.get_point().get(axis).unwrap())
+                .collect();
+            Arc::new(coords) as ArrayRef
+        })
+        .collect();
+
+    let images: Float64Array = evaluations.iter().map(|e| e.get_eval()).collect();
+    columns.push(Arc::new(images));
+
+    RecordBatch::try_new(evaluations_schema(dimension), columns)
+}
+
+/// Converts the evaluation log at `log_path` to a Parquet file at `parquet_path`, with the columns
+/// of `evaluations_record_batch`. The log is streamed in batches, so it doesn't have to fit in
+/// memory. Returns the number of evaluations written.
+pub fn eval_log_to_parquet<P, Q>(log_path: P, parquet_path: Q) -> Result<u64, Box<dyn Error>>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let mut reader = EvalLogReader::open(log_path)?;
+    let dimension = reader.get_dimension();
+
+    let file = File::create(parquet_path)?;
+    let mut writer = ArrowWriter::try_new(file, evaluations_schema(dimension), None)?;
+
+    let mut records = 0;
+    let mut evaluations = Vec::with_capacity(LOG_BATCH_SIZE);
+
+    loop {
+        evaluations.clear();
+
+        for evaluation in reader.by_ref().take(LOG_BATCH_SIZE) {
+            evaluations.push(evaluation?);
+        }
+
+        if evaluations.is_empty() {
+            break;
+        }
+
+        writer.write(&evaluations_record_batch(&evaluations, dimension)?)?;
+        records += evaluations.len() as u64;
+    }
+
+    writer.close()?;
+
+    Ok(records)
+}
+
+fn history_record_batch(history: &[IterationRecord]) -> Result<RecordBatch, ArrowError> {
+    let schema = Schema::new(vec![
+        Field::new("loop_index", DataType::UInt32, false),
+        Field::new("fn_evals", DataType::UInt32, false),
+        Field::new("best_f", DataType::Float64, false),
+        Field::new("diagonal_len", DataType::Float64, false),
+    ]);
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(
+            history
+                .iter()
+                .map(|r| r.loop_index)
+                .collect::<UInt32Array>(),
+        ),
+        Arc::new(history.iter().map(|r| r.fn_evals).collect::<UInt32Array>()),
+        Arc::new(history.iter().map(|r| r.best_f).collect::<Float64Array>()),
+        Arc::new(
+            history
+                .iter()
+                .map(|r| r.diagonal_len)
+                .collect::<Float64Array>(),
+        ),
+    ];
+
+    RecordBatch::try_new(Arc::new(schema), columns)
+}
+
+/// Schema of evaluations of `dimension`-dimensional points
+fn evaluations_schema(dimension: u32) -> SchemaRef {
+    let mut fields: Vec<Field> = (0..dimension)
+        .map(|axis| Field::new(format!("x{}", axis), DataType::Float64, false))
+        .collect();
+    fields.push(Field::new("f", DataType::Float64, false));
+
+    Arc::new(Schema::new(fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval_log::EvalLogWriter;
+    use crate::point;
+    use crate::point::Point;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Float64Type;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    #[test]
+    fn evaluations_to_record_batch() {
+        let evaluations = vec![
+            PointEval::from_image(point![1.0, -2.0], 3.5),
+            PointEval::from_image(point![0.5, 4.0], -1.0),
+        ];
+
+        let batch = evaluations_record_batch(&evaluations, 2).unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema().field(0).name(), "x0");
+        assert_eq!(batch.schema().field(2).name(), "f");
+
+        let x1 = batch.column(1).as_primitive::<Float64Type>();
+        let f = batch.column(2).as_primitive::<Float64Type>();
+
+        assert_eq!(x1.values(), &[-2.0, 4.0]);
+        assert_eq!(f.values(), &[3.5, -1.0]);
+    }
+
+    #[test]
+    fn eval_log_to_parquet_file() {
+        let dir = std::env::temp_dir();
+        let log_path = dir.join(format!("hypercube-arrow-{}.bin", std::process::id()));
+        let parquet_path = dir.join(format!("hypercube-arrow-{}.parquet", std::process::id()));
+
+        let mut log = EvalLogWriter::create(&log_path, 3).unwrap();
+        for i in 0..10 {
+            log.append(&point![i as f64; 3], -(i as f64)).unwrap();
+        }
+        log.flush().unwrap();
+
+        let records = eval_log_to_parquet(&log_path, &parquet_path).unwrap();
+        let reader = SerializedFileReader::new(File::open(&parquet_path).unwrap()).unwrap();
+        let metadata = reader.metadata().file_metadata().clone();
+
+        std::fs::remove_file(&log_path).unwrap();
+        std::fs::remove_file(&parquet_path).unwrap();
+
+        assert_eq!(records, 10);
+        assert_eq!(metadata.num_rows(), 10);
+        assert_eq!(metadata.schema_descr().num_columns(), 4);
+    }
+}
@@ -0,0 +1,190 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::evaluation::PointEval;
+use crate::point::Point;
+
+/// Bound on objective functions and evaluation backends. With the `parallel` feature, populations
+/// are evaluated on several threads and this requires `Sync`. Without it, every type implements
+/// `MaybeSync`, so objectives can capture `Cell`, `RefCell`, or `Rc`.
+#[cfg(feature = "parallel")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "parallel")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// Implemented by every type, since populations are evaluated on the current thread without the
+/// `parallel` feature
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "parallel"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// Objective function taken by `Optimizer::maximize`, `Sync` if the `parallel` feature is enabled
+#[cfg(feature = "parallel")]
+pub type Objective<'a> = dyn Fn(&Point) -> f64 + Sync + 'a;
+
+/// Objective function taken by `Optimizer::maximize`
+#[cfg(not(feature = "parallel"))]
+pub type Objective<'a> = dyn Fn(&Point) -> f64 + 'a;
+
+/// Evaluates batches of points for the optimizer. Implementors decide where and how the objective
+/// function is computed (e.g. locally, on a cluster, or behind a remote service), which keeps the
+/// optimization loop itself free of any transport specific code.
+pub trait EvaluationBackend {
+    /// Error returned when a single point fails to evaluate
+    type Error: fmt::Debug;
+
+    /// Evaluates every point in `points` and returns the images in the same order. A failed
+    /// evaluation only affects its own entry in the returned vector.
+    fn evaluate_batch(&self, points: &[Point]) -> Vec<Result<f64, Self::Error>>;
+}
+
+/// Default backend that evaluates a Rust closure on the local machine. Batches are evaluated in
+/// parallel using rayon if the `parallel` feature is enabled.
+pub struct LocalBackend<F> {
+    function: F,
+}
+
+impl<F> LocalBackend<F>
+where
+    F: Fn(&Point) -> f64 + MaybeSync,
+{
+    /// Creates a new `LocalBackend` that evaluates points with `function`
+    pub fn new(function: F) -> Self {
+        Self { function }
+    }
+}
+
+impl<F> EvaluationBackend for LocalBackend<F>
+where
+    F: Fn(&Point) -> f64 + MaybeSync,
+{
+    type Error = Infallible;
+
+    fn evaluate_batch(&self, points: &[Point]) -> Vec<Result<f64, Self::Error>> {
+        #[cfg(feature = "parallel")]
+        let points = points.par_iter();
+
+        #[cfg(not(feature = "parallel"))]
+        let points = points.iter();
+
+        points.map(|point| Ok((self.function)(point))).collect()
+    }
+}
+
+/// Backend that evaluates a closure returning an image together with auxiliary output, and keeps
+/// the auxiliary output of the best evaluation so far
+pub(crate) struct MetadataBackend<F> {
+    function: F,
+
+    /// best evaluation so far, with its auxiliary output attached
+    best: Mutex<Option<PointEval>>,
+}
+
+impl<F, T> MetadataBackend<F>
+where
+    F: Fn(&Point) -> (f64, T) + MaybeSync,
+    T: Send + Sync + 'static,
+{
+    pub(crate) fn new(function: F) -> Self {
+        Self {
+            function,
+            best: Mutex::new(None),
+        }
+    }
+
+    /// Returns the best evaluation, or `None` if every image was NaN
+    pub(crate) fn into_best(self) -> Option<PointEval> {
+        self.best.into_inner().unwrap()
+    }
+}
+
+impl<F, T> EvaluationBackend for MetadataBackend<F>
+where
+    F: Fn(&Point) -> (f64, T) + MaybeSync,
+    T: Send + Sync + 'static,
+{
+    type Error = Infallible;
+
+    fn evaluate_batch(&self, points: &[Point]) -> Vec<Result<f64, Self::Error>> {
+        #[cfg(feature = "parallel")]
+        let evaluations = points.par_iter();
+
+        #[cfg(not(feature = "parallel"))]
+        let evaluations = points.iter();
+
+        let evaluations: Vec<(f64, T)> = evaluations.map(|point| (self.function)(point)).collect();
+
+        let images: Vec<Result<f64, Self::Error>> =
+            evaluations.iter().map(|(image, _)| Ok(*image)).collect();
+
+        // only the auxiliary output of the best point of the batch can replace the best so far
+        let batch_best = evaluations
+            .into_iter()
+            .zip(points)
+            .filter(|((image, _), _)| !image.is_nan())
+            .max_by(|((a, _), _), ((b, _), _)| a.total_cmp(b));
+
+        if let Some(((image, metadata), point)) = batch_best {
+            let mut best = self.best.lock().unwrap();
+
+            if best.as_ref().is_none_or(|best| image > best.get_eval()) {
+                *best = Some(
+                    PointEval::from_image(point.clone(), image).with_metadata(Arc::new(metadata)),
+                );
+            }
+        }
+
+        images
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::summation;
+    use crate::point;
+
+    #[test]
+    fn local_backend_preserves_order() {
+        let backend = LocalBackend::new(summation);
+        let points: Vec<Point> = (0..100).map(|i| point![i as f64; 2]).collect();
+
+        let images = backend.evaluate_batch(&points);
+
+        assert_eq!(images.len(), points.len());
+
+        for (i, image) in images.into_iter().enumerate() {
+            assert_eq!(image.unwrap(), 2.0 * i as f64);
+        }
+    }
+
+    #[test]
+    fn metadata_backend_keeps_best() {
+        let backend = MetadataBackend::new(|p: &Point| (summation(p), format!("{:?}", p)));
+
+        backend.evaluate_batch(&[point![1.0; 2], point![3.0; 2]]);
+        backend.evaluate_batch(&[point![2.0; 2]]);
+
+        let best = backend.into_best().unwrap();
+
+        assert_eq!(best.get_eval(), 6.0);
+        assert_eq!(
+            best.get_metadata::<String>(),
+            Some(&format!("{:?}", point![3.0; 2]))
+        );
+    }
+
+    #[test]
+    fn local_backend_empty_batch() {
+        let backend = LocalBackend::new(summation);
+
+        assert!(backend.evaluate_batch(&[]).is_empty());
+    }
+}
@@ -0,0 +1,544 @@
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::backend::{EvaluationBackend, LocalBackend, Objective};
+use crate::bounds::HypercubeBounds;
+use crate::evaluation::PointEval;
+use crate::linalg::{backward_substitution, cholesky, forward_substitution};
+use crate::optimizer::{HypercubeOptimizer, HypercubeOptimizerConfig, Optimizer};
+use crate::point::Point;
+use crate::result::{HypercubeOptimizerResult, IterationRecord};
+use crate::stopping::{Budget, StoppingCriteria};
+
+/// Side length of the scaled search space the acquisition function is maximized in. Keeps the
+/// size of the inner hypercube population independent of the problem's bounds.
+const ACQUISITION_SIDE: f64 = 10.0;
+
+/// Candidate GP length scales, as fractions of the search space side length
+const LENGTH_SCALE_FRACTIONS: [f64; 5] = [0.05, 0.1, 0.2, 0.4, 0.8];
+
+/// Bayesian optimizer for expensive objectives that can only afford tens to hundreds of
+/// evaluations. A Gaussian process with a squared exponential kernel is fitted to every point
+/// evaluated so far and the next point is the one maximizing the expected improvement over the
+/// best value. The expected improvement is maximized with a `HypercubeOptimizer`.
+pub struct BayesianOptimizer {
+    /// dimension of the optimization problem
+    dimension: u32,
+
+    /// first point evaluated
+    init_point: Point,
+
+    /// bounds of the search space
+    bounds: HypercubeBounds,
+
+    /// number of points evaluated before the surrogate is used, including `init_point`
+    initial_samples: u32,
+
+    /// variance of the observation noise, relative to the variance of the observed images
+    noise: f64,
+
+    /// minimum improvement over the best value the expected improvement is computed against
+    exploration: f64,
+
+    /// maximum number of loops of the optimizer maximizing the expected improvement
+    acquisition_loops: u32,
+
+    /// conditions that terminate the optimization
+    criteria: StoppingCriteria,
+
+    /// random number generator used for the initial samples and the acquisition optimizer
+    rng: StdRng,
+}
+
+/// Gaussian process fitted to normalized images
+struct GaussianProcess<'a> {
+    points: &'a [Point],
+    length_scale: f64,
+
+    /// Cholesky factor of the kernel matrix
+    cholesky: Vec<f64>,
+
+    /// kernel matrix inverse applied to the normalized images
+    alpha: Vec<f64>,
+}
+
+impl BayesianOptimizer {
+    /// Returns a new `BayesianOptimizer`
+    ///
+    /// # Arguments
+    ///
+    /// * `init_point` - the first point evaluated
+    /// * `lower_bound` - the lower bound of the search space
+    /// * `upper_bound` - the upper bound of the search space
+    /// * `criteria` - conditions that terminate the optimization. The run converges once the next
+    ///   point lies within `tol_x` of a point that was already evaluated or its expected
+    ///   improvement falls below `tol_f`.
+    ///
+    pub fn new(
+        init_point: Point,
+        lower_bound: f64,
+        upper_bound: f64,
+        criteria: StoppingCriteria,
+    ) -> Self {
+        assert!(
+            upper_bound > lower_bound,
+            "upper bound not strictly larger than lower bound"
+        );
+        assert!(
+            init_point.max_val().unwrap() <= upper_bound,
+            "init_point not inside upper bound"
+        );
+        assert!(
+            init_point.min_val().unwrap() >= lower_bound,
+            "init_point not inside lower bound"
+        );
+
+        let dimension = init_point.dim();
+
+        Self {
+            dimension,
+            bounds: HypercubeBounds::new(dimension, lower_bound, upper_bound),
+            init_point,
+            initial_samples: (2 * dimension).max(5),
+            noise: 1e-6,
+            exploration: 0.01,
+            acquisition_loops: 30,
+            criteria,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Sets the number of points evaluated before the surrogate is used. The first one is the
+    /// initial point and the rest are sampled uniformly within the search space. Defaults to
+    /// `max(2n, 5)`.
+    pub fn set_initial_samples(&mut self, initial_samples: u32) {
+        assert_ne!(initial_samples, 0, "initial samples cannot be zero");
+        self.initial_samples = initial_samples;
+    }
+
+    /// Sets the variance of the observation noise relative to the variance of the observed
+    /// images. Defaults to 1e-6, i.e. a deterministic objective.
+    pub fn set_noise(&mut self, noise: f64) {
+        assert!(noise > 0.0, "noise must be positive");
+        self.noise = noise;
+    }
+
+    /// Sets the minimum improvement, relative to the standard deviation of the observed images,
+    /// the expected improvement is computed against. Larger values favour exploration. Defaults
+    /// to 0.01.
+    pub fn set_exploration(&mut self, exploration: f64) {
+        assert!(exploration >= 0.0, "exploration cannot be negative");
+        self.exploration = exploration;
+    }
+
+    /// Sets the maximum number of loops of the hypercube optimizer that maximizes the expected
+    /// improvement. Defaults to 30.
+    pub fn set_acquisition_loops(&mut self, acquisition_loops: u32) {
+        assert_ne!(acquisition_loops, 0, "acquisition loops cannot be zero");
+        self.acquisition_loops = acquisition_loops;
+    }
+
+    /// Seeds the random number generator used for the initial samples and the acquisition
+    /// optimizer
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Maximizes the objective function evaluated by `backend`
+    pub fn maximize_with<B>(&mut self, backend: &B) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + ?Sized,
+    {
+        let lower_bound = *self.bounds.get_lower().get(0).unwrap();
+        let upper_bound = *self.bounds.get_upper().get(0).unwrap();
+
+        let mut budget = Budget::start(&self.criteria);
+        let mut history: Vec<IterationRecord> = Vec::new();
+
+        let mut points: Vec<Point> = Vec::new();
+        let mut images: Vec<f64> = Vec::new();
+
+        // <----- initial design ----->
+
+        let initial_samples = self.initial_samples.min(budget.remaining_evals());
+
+        if let Some(exit_code) = budget.check(initial_samples.max(1)) {
+            return HypercubeOptimizerResult::new(exit_code, 0, 0, None, budget.elapsed());
+        }
+
+        let mut design = vec![self.init_point.clone()];
+
+        while design.len() < initial_samples as usize {
+            design.push(Point::random_with(
+                &mut self.rng,
+                self.dimension,
+                lower_bound,
+                upper_bound,
+            ));
+        }
+
+        self.evaluate(backend, &mut budget, design, &mut points, &mut images);
+
+        if points.is_empty() {
+            log::error!("no point of the initial design evaluated successfully");
+            return HypercubeOptimizerResult::new(1, 0, budget.fn_evals(), None, budget.elapsed());
+        }
+
+        // <----- surrogate loop ----->
+
+        let exit_code = loop {
+            if let Some(exit_code) = budget.check(1) {
+                break exit_code;
+            }
+
+            // normalize images so the kernel can have unit signal variance
+            let mean = images.iter().sum::<f64>() / images.len() as f64;
+            let variance =
+                images.iter().map(|y| (y - mean).powi(2)).sum::<f64>() / images.len() as f64;
+            let std_dev = if variance > 0.0 { variance.sqrt() } else { 1.0 };
+            let normalized: Vec<f64> = images.iter().map(|y| (y - mean) / std_dev).collect();
+
+            let gp = self.fit(&points, &normalized);
+            let length_scale = gp.length_scale;
+            let best_normalized = normalized.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let best_index = normalized
+                .iter()
+                .position(|&y| y == best_normalized)
+                .unwrap();
+
+            // <----- maximize expected improvement ----->
+
+            let acquisition_seed: u64 = self.rng.gen();
+
+            let acquisition = |scaled: &Point| {
+                let (mu, sigma) = gp.predict(&self.unscale(scaled));
+                expected_improvement(mu, sigma, best_normalized + self.exploration)
+            };
+
+            let config = HypercubeOptimizerConfig {
+                stopping: StoppingCriteria {
+                    tol_x: 1e-3,
+                    tol_f: 0.0,
+                    max_loop: self.acquisition_loops,
+                    max_eval: u32::MAX,
+                    max_timeout: self.criteria.max_timeout,
+                },
+                seed: Some(acquisition_seed),
+                ..HypercubeOptimizerConfig::default()
+            };
+
+            let mut acquisition_optimizer = HypercubeOptimizer::with_config(
+                self.scale(&points[best_index]),
+                0.0,
+                ACQUISITION_SIDE,
+                config,
+            );
+
+            let acquisition_result = acquisition_optimizer.maximize(acquisition);
+            let candidate = self
+                .unscale(acquisition_result.get_best_x().unwrap())
+                .clamp(&self.bounds);
+            let improvement = acquisition_result.get_best_f().unwrap() * std_dev;
+
+            // <----- convergence checks ----->
+
+            let nearest = points
+                .iter()
+                .map(|p| (p - &candidate).len())
+                .fold(f64::INFINITY, f64::min);
+
+            if nearest < self.criteria.tol_x {
+                log::info!("optimization process terminated due to input convergence");
+                break 0;
+            }
+
+            if improvement < self.criteria.tol_f {
+                log::info!("optimization process terminated due to image convergence");
+                break 0;
+            }
+
+            // <----- evaluate candidate ----->
+
+            self.evaluate(
+                backend,
+                &mut budget,
+                vec![candidate],
+                &mut points,
+                &mut images,
+            );
+            budget.record_loop();
+
+            history.push(IterationRecord {
+                loop_index: budget.loops() - 1,
+                fn_evals: budget.fn_evals(),
+                best_f: images.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                diagonal_len: length_scale * (self.dimension as f64).sqrt(),
+            });
+        };
+
+        let best = points
+            .into_iter()
+            .zip(images)
+            .map(|(point, image)| PointEval::from_image(point, image))
+            .max();
+
+        HypercubeOptimizerResult::new(
+            exit_code,
+            budget.loops(),
+            budget.fn_evals(),
+            best.as_ref(),
+            budget.elapsed(),
+        )
+        .with_history(history)
+    }
+
+    /// Evaluates `new_points` and appends the ones that evaluated successfully to the data set
+    fn evaluate<B>(
+        &self,
+        backend: &B,
+        budget: &mut Budget,
+        new_points: Vec<Point>,
+        points: &mut Vec<Point>,
+        images: &mut Vec<f64>,
+    ) where
+        B: EvaluationBackend + ?Sized,
+    {
+        let new_images = backend.evaluate_batch(&new_points);
+        budget.record_evals(new_points.len() as u32);
+
+        for (point, image) in new_points.into_iter().zip(new_images) {
+            match image {
+                Ok(image) if !image.is_nan() => {
+                    points.push(point);
+                    images.push(image);
+                }
+                Ok(_) => log::warn!("evaluation at {:?} returned NaN", point),
+                Err(e) => log::warn!("evaluation failed at {:?}: {:?}", point, e),
+            }
+        }
+    }
+
+    /// Fits a Gaussian process to the data, picking the length scale with the highest marginal
+    /// likelihood
+    fn fit<'a>(&self, points: &'a [Point], normalized: &[f64]) -> GaussianProcess<'a> {
+        let side_length = self.bounds.get_length();
+
+        LENGTH_SCALE_FRACTIONS
+            .iter()
+            .filter_map(|fraction| {
+                GaussianProcess::fit(points, normalized, fraction * side_length, self.noise)
+            })
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(gp, _)| gp)
+            .expect("kernel matrix is not positive definite for any length scale")
+    }
+
+    /// Maps a point of the search space to the acquisition search space
+    fn scale(&self, point: &Point) -> Point {
+        let lower = self.bounds.get_lower();
+        let side_length = self.bounds.get_length();
+
+        Point::from_vec(
+            point
+                .iter()
+                .zip(lower.iter())
+                .map(|(x, l)| {
+                    ((x - l) / side_length * ACQUISITION_SIDE).clamp(0.0, ACQUISITION_SIDE)
+                })
+                .collect(),
+        )
+    }
+
+    /// Maps a point of the acquisition search space back to the search space
+    fn unscale(&self, scaled: &Point) -> Point {
+        let lower = self.bounds.get_lower();
+        let side_length = self.bounds.get_length();
+
+        Point::from_vec(
+            scaled
+                .iter()
+                .zip(lower.iter())
+                .map(|(s, l)| l + s / ACQUISITION_SIDE * side_length)
+                .collect(),
+        )
+    }
+}
+
+impl Optimizer for BayesianOptimizer {
+    fn maximize(&mut self, obj_function: &Objective) -> HypercubeOptimizerResult {
+        self.maximize_with(&LocalBackend::new(obj_function))
+    }
+}
+
+impl<'a> GaussianProcess<'a> {
+    /// Fits a Gaussian process with the given length scale and returns it along with its log
+    /// marginal likelihood, or `None` if the kernel matrix is numerically singular
+    fn fit(
+        points: &'a [Point],
+        normalized: &[f64],
+        length_scale: f64,
+        noise: f64,
+    ) -> Option<(Self, f64)> {
+        let m = points.len();
+        let mut kernel = vec![0.0; m * m];
+
+        for i in 0..m {
+            for j in 0..m {
+                kernel[i * m + j] = squared_exponential(&points[i], &points[j], length_scale);
+            }
+            kernel[i * m + i] += noise;
+        }
+
+        let cholesky = cholesky(&kernel, m)?;
+        let alpha = backward_substitution(&cholesky, &forward_substitution(&cholesky, normalized));
+
+        let log_likelihood = -0.5
+            * normalized
+                .iter()
+                .zip(&alpha)
+                .map(|(y, a)| y * a)
+                .sum::<f64>()
+            - (0..m).map(|i| cholesky[i * m + i].ln()).sum::<f64>()
+            - 0.5 * m as f64 * (2.0 * PI).ln();
+
+        Some((
+            Self {
+                points,
+                length_scale,
+                cholesky,
+                alpha,
+            },
+            log_likelihood,
+        ))
+    }
+
+    /// Returns the posterior mean and standard deviation of the normalized image at `point`
+    fn predict(&self, point: &Point) -> (f64, f64) {
+        let k: Vec<f64> = self
+            .points
+            .iter()
+            .map(|p| squared_exponential(p, point, self.length_scale))
+            .collect();
+
+        let mean = k.iter().zip(&self.alpha).map(|(k, a)| k * a).sum();
+
+        let v = forward_substitution(&self.cholesky, &k);
+        let variance = (1.0 - v.iter().map(|v| v * v).sum::<f64>()).max(1e-12);
+
+        (mean, variance.sqrt())
+    }
+}
+
+/// Squared exponential kernel with unit signal variance
+fn squared_exponential(a: &Point, b: &Point, length_scale: f64) -> f64 {
+    let squared_distance: f64 = a.iter().zip(b.iter()).map(|(a, b)| (a - b).powi(2)).sum();
+    (-squared_distance / (2.0 * length_scale * length_scale)).exp()
+}
+
+/// Expected improvement over `target` of a normally distributed image
+fn expected_improvement(mean: f64, std_dev: f64, target: f64) -> f64 {
+    let improvement = mean - target;
+    let z = improvement / std_dev;
+
+    (improvement * normal_cdf(z) + std_dev * normal_pdf(z)).max(0.0)
+}
+
+fn normal_pdf(z: f64) -> f64 {
+    (-0.5 * z * z).exp() / (2.0 * PI).sqrt()
+}
+
+fn normal_cdf(z: f64) -> f64 {
+    0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2))
+}
+
+/// Error function, using the approximation 7.1.26 of Abramowitz and Stegun (maximum error 1.5e-7)
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let polynomial = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let value = 1.0 - polynomial * (-x * x).exp();
+
+    if x >= 0.0 {
+        value
+    } else {
+        -value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::neg_sphere;
+    use crate::point;
+
+    fn criteria(max_eval: u32) -> StoppingCriteria {
+        StoppingCriteria {
+            tol_x: 1e-6,
+            tol_f: 1e-9,
+            max_eval,
+            ..StoppingCriteria::default()
+        }
+    }
+
+    #[test]
+    fn normal_distribution_functions() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((normal_cdf(-1.96) - 0.025).abs() < 1e-4);
+        assert!((normal_pdf(0.0) - 0.398_942_28).abs() < 1e-7);
+    }
+
+    #[test]
+    fn expected_improvement_properties() {
+        // certain improvement equals the improvement itself
+        assert!((expected_improvement(2.0, 1e-9, 1.0) - 1.0).abs() < 1e-6);
+
+        // uncertainty adds value
+        assert!(expected_improvement(0.0, 1.0, 0.0) > expected_improvement(0.0, 0.1, 0.0));
+    }
+
+    #[test]
+    fn gaussian_process_interpolates_data() {
+        let points = vec![point![0.0], point![1.0], point![2.0]];
+        let images = vec![0.5, -1.0, 0.25];
+
+        let (gp, _) = GaussianProcess::fit(&points, &images, 1.0, 1e-10).unwrap();
+
+        for (point, image) in points.iter().zip(&images) {
+            let (mean, std_dev) = gp.predict(point);
+
+            assert!((mean - image).abs() < 1e-4);
+            assert!(std_dev < 1e-3);
+        }
+
+        // far away from the data the prior takes over
+        let (mean, std_dev) = gp.predict(&point![50.0]);
+        assert!(mean.abs() < 1e-6);
+        assert!((std_dev - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn optimizes_sphere_with_few_evaluations() {
+        let mut bayesian = BayesianOptimizer::new(point![3.0; 2], -5.0, 5.0, criteria(40));
+        bayesian.set_seed(1);
+
+        let result = Optimizer::maximize(&mut bayesian, &neg_sphere);
+
+        assert!(result.get_fn_evals() <= 40);
+        assert!(result.get_best_f().unwrap() > -0.5);
+    }
+
+    #[test]
+    fn scale_round_trip() {
+        let bayesian = BayesianOptimizer::new(point![3.0; 2], -5.0, 15.0, criteria(10));
+        let point = point![2.5, -4.0];
+
+        let scaled = bayesian.scale(&point);
+
+        assert_eq!(scaled, point![3.75, 0.5]);
+        assert_eq!(bayesian.unscale(&scaled), point);
+    }
+}
@@ -0,0 +1,333 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::objective_functions::{
+    neg_ackley, neg_griewank, neg_rastrigin, neg_rosenbrock, neg_sphere,
+};
+use crate::optimizer::{HypercubeOptimizer, HypercubeOptimizerConfig};
+use crate::point::Point;
+use crate::text_format::{csv_field, json_number, json_string};
+
+/// A test problem to benchmark the optimizer against. Problems are stated as maximization
+/// problems, matching `HypercubeOptimizer::maximize`.
+#[derive(Debug, Clone)]
+pub struct BenchmarkProblem {
+    /// name of the problem used in reports
+    pub name: String,
+
+    /// objective function to maximize
+    pub function: fn(&Point) -> f64,
+
+    /// dimension of the search space
+    pub dimension: u32,
+
+    /// lower bound of the search space
+    pub lower_bound: f64,
+
+    /// upper bound of the search space
+    pub upper_bound: f64,
+
+    /// known global maximum of the objective function
+    pub optimum: f64,
+}
+
+/// A named optimizer configuration to run every benchmark problem with
+#[derive(Debug, Clone)]
+pub struct BenchmarkConfig {
+    /// name of the configuration used in reports
+    pub name: String,
+
+    /// tuning options passed to the optimizer
+    pub config: HypercubeOptimizerConfig,
+}
+
+/// Outcome of a single optimization run of the benchmark matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkRecord {
+    pub problem: String,
+    pub config: String,
+    pub seed: u64,
+    pub exit_code: u32,
+    pub best_f: Option<f64>,
+
+    /// absolute distance between the best value found and the known optimum
+    pub error: Option<f64>,
+    pub fn_evals: u32,
+    pub loops: u32,
+    pub wall_time: Duration,
+}
+
+/// Collection of benchmark records that can be written out as CSV or JSON
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BenchmarkReport {
+    pub records: Vec<BenchmarkRecord>,
+}
+
+impl BenchmarkProblem {
+    /// Creates a new `BenchmarkProblem`
+    pub fn new(
+        name: &str,
+        function: fn(&Point) -> f64,
+        dimension: u32,
+        lower_bound: f64,
+        upper_bound: f64,
+        optimum: f64,
+    ) -> Self {
+        assert_ne!(dimension, 0, "dimension cannot be zero");
+        assert!(
+            upper_bound > lower_bound,
+            "upper bound not strictly larger than lower bound"
+        );
+
+        Self {
+            name: name.to_string(),
+            function,
+            dimension,
+            lower_bound,
+            upper_bound,
+            optimum,
+        }
+    }
+
+    /// Returns the standard suite of test functions with `dimension` dimensions: sphere,
+    /// rastrigin, rosenbrock, ackley, and griewank.
+    pub fn standard_suite(dimension: u32) -> Vec<Self> {
+        vec![
+            Self::new("sphere", neg_sphere, dimension, -5.12, 5.12, 0.0),
+            Self::new("rastrigin", neg_rastrigin, dimension, -5.12, 5.12, 0.0),
+            Self::new("rosenbrock", neg_rosenbrock, dimension, -5.0, 10.0, 0.0),
+            Self::new("ackley", neg_ackley, dimension, -32.768, 32.768, 0.0),
+            Self::new("griewank", neg_griewank, dimension, -50.0, 50.0, 0.0),
+        ]
+    }
+
+    /// Initial point used for every run of the problem. It sits away from the center of the
+    /// search space because most test functions have their optimum there.
+    pub fn init_point(&self) -> Point {
+        let init_value = self.lower_bound + 0.8 * (self.upper_bound - self.lower_bound);
+        Point::fill(init_value, self.dimension)
+    }
+}
+
+impl BenchmarkConfig {
+    /// Creates a new `BenchmarkConfig`
+    pub fn new(name: &str, config: HypercubeOptimizerConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+        }
+    }
+}
+
+/// Runs every combination of `problems`, `configs`, and `seeds` and collects the outcome of each
+/// run into a `BenchmarkReport`. Runs are executed one after another so that wall times aren't
+/// distorted by runs competing for the same threads.
+pub fn run_benchmark(
+    problems: &[BenchmarkProblem],
+    configs: &[BenchmarkConfig],
+    seeds: &[u64],
+) -> BenchmarkReport {
+    let mut records = Vec::with_capacity(problems.len() * configs.len() * seeds.len());
+
+    for problem in problems {
+        for config in configs {
+            for &seed in seeds {
+                records.push(run_single(problem, config, seed));
+            }
+        }
+    }
+
+    BenchmarkReport { records }
+}
+
+/// Runs a single cell of the benchmark matrix
+fn run_single(problem: &BenchmarkProblem, config: &BenchmarkConfig, seed: u64) -> BenchmarkRecord {
+    let mut optimizer_config = config.config.clone();
+    optimizer_config.seed = Some(seed);
+
+    let mut optimizer = HypercubeOptimizer::with_config(
+        problem.init_point(),
+        problem.lower_bound,
+        problem.upper_bound,
+        optimizer_config,
+    );
+
+    let start_time = Instant::now();
+    let result = optimizer.maximize(problem.function);
+    let wall_time = start_time.elapsed();
+
+    BenchmarkRecord {
+        problem: problem.name.clone(),
+        config: config.name.clone(),
+        seed,
+        exit_code: result.get_exit_code(),
+        best_f: result.get_best_f(),
+        error: result.get_best_f().map(|f| (f - problem.optimum).abs()),
+        fn_evals: result.get_fn_evals(),
+        loops: result.get_loops(),
+        wall_time,
+    }
+}
+
+impl BenchmarkReport {
+    /// Formats the report as CSV with a header row
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            String::from("problem,config,seed,exit_code,best_f,error,fn_evals,loops,wall_time_s\n");
+
+        for r in &self.records {
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{}",
+                csv_field(&r.problem),
+                csv_field(&r.config),
+                r.seed,
+                r.exit_code,
+                r.best_f.map(|f| f.to_string()).unwrap_or_default(),
+                r.error.map(|e| e.to_string()).unwrap_or_default(),
+                r.fn_evals,
+                r.loops,
+                r.wall_time.as_secs_f64()
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+
+    /// Formats the report as a JSON array with one object per record
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+
+        for (index, r) in self.records.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+
+            write!(
+                json,
+                "\n  {{\"problem\": {}, \"config\": {}, \"seed\": {}, \"exit_code\": {}, \
+                \"best_f\": {}, \"error\": {}, \"fn_evals\": {}, \"loops\": {}, \
+                \"wall_time_s\": {}}}",
+                json_string(&r.problem),
+                json_string(&r.config),
+                r.seed,
+                r.exit_code,
+                json_number(r.best_f),
+                json_number(r.error),
+                r.fn_evals,
+                r.loops,
+                r.wall_time.as_secs_f64()
+            )
+            .unwrap();
+        }
+
+        json.push_str("\n]\n");
+        json
+    }
+
+    /// Writes the report to `path` as CSV
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+
+    /// Writes the report to `path` as JSON
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stopping::StoppingCriteria;
+
+    fn small_config() -> BenchmarkConfig {
+        BenchmarkConfig::new(
+            "small",
+            HypercubeOptimizerConfig {
+                stopping: StoppingCriteria {
+                    max_loop: 5,
+                    ..StoppingCriteria::default()
+                },
+                ..HypercubeOptimizerConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn run_benchmark_matrix() {
+        let problems = BenchmarkProblem::standard_suite(2);
+        let configs = vec![small_config()];
+        let seeds = vec![1, 2];
+
+        let report = run_benchmark(&problems[..2], &configs, &seeds);
+
+        assert_eq!(report.records.len(), 4);
+        assert_eq!(report.records[0].problem, "sphere");
+        assert_eq!(report.records[3].problem, "rastrigin");
+        assert_eq!(report.records[3].seed, 2);
+        assert!(report.records.iter().all(|r| r.error.unwrap() >= 0.0));
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let problems = vec![BenchmarkProblem::standard_suite(2).remove(1)];
+        let configs = vec![small_config()];
+
+        let report_a = run_benchmark(&problems, &configs, &[42]);
+        let report_b = run_benchmark(&problems, &configs, &[42]);
+
+        assert_eq!(report_a.records[0].best_f, report_b.records[0].best_f);
+    }
+
+    #[test]
+    fn csv_format() {
+        let report = BenchmarkReport {
+            records: vec![BenchmarkRecord {
+                problem: "sphere".to_string(),
+                config: "a,b".to_string(),
+                seed: 3,
+                exit_code: 0,
+                best_f: Some(-0.5),
+                error: Some(0.5),
+                fn_evals: 100,
+                loops: 4,
+                wall_time: Duration::from_millis(1500),
+            }],
+        };
+
+        assert_eq!(
+            report.to_csv(),
+            "problem,config,seed,exit_code,best_f,error,fn_evals,loops,wall_time_s\n\
+            sphere,\"a,b\",3,0,-0.5,0.5,100,4,1.5\n"
+        );
+    }
+
+    #[test]
+    fn json_format() {
+        let report = BenchmarkReport {
+            records: vec![BenchmarkRecord {
+                problem: "sph\"ere".to_string(),
+                config: "default".to_string(),
+                seed: 3,
+                exit_code: 0,
+                best_f: None,
+                error: None,
+                fn_evals: 100,
+                loops: 4,
+                wall_time: Duration::from_millis(1500),
+            }],
+        };
+
+        assert_eq!(
+            report.to_json(),
+            "[\n  {\"problem\": \"sph\\\"ere\", \"config\": \"default\", \"seed\": 3, \
+            \"exit_code\": 0, \"best_f\": null, \"error\": null, \"fn_evals\": 100, \
+            \"loops\": 4, \"wall_time_s\": 1.5}\n]\n"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::env;
+use std::process;
+
+use hypercube_optimizer::benchmark::{run_benchmark, BenchmarkConfig, BenchmarkProblem};
+use hypercube_optimizer::optimizer::HypercubeOptimizerConfig;
+use hypercube_optimizer::stopping::StoppingCriteria;
+
+const USAGE: &str = "usage: hypercube-bench [--dimension N] [--seeds N] [--max-loop N] \
+                     [--format csv|json] [--output PATH]";
+
+/// Runs the standard benchmark suite over a small set of optimizer configurations and seeds, and
+/// prints (or writes) the report as CSV or JSON.
+fn main() {
+    let mut dimension: u32 = 2;
+    let mut num_seeds: u64 = 5;
+    let mut max_loop: u32 = 200;
+    let mut format = String::from("csv");
+    let mut output: Option<String> = None;
+
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| exit_with_usage(&arg));
+
+        match arg.as_str() {
+            "--dimension" => dimension = parse_or_exit(&arg, &value()),
+            "--seeds" => num_seeds = parse_or_exit(&arg, &value()),
+            "--max-loop" => max_loop = parse_or_exit(&arg, &value()),
+            "--format" => format = value(),
+            "--output" => output = Some(value()),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => exit_with_usage(&arg),
+        }
+    }
+
+    let default_config = HypercubeOptimizerConfig {
+        stopping: StoppingCriteria {
+            max_loop,
+            ..StoppingCriteria::default()
+        },
+        ..HypercubeOptimizerConfig::default()
+    };
+
+    let loose_config = HypercubeOptimizerConfig {
+        stopping: StoppingCriteria {
+            tol_f: 1.0,
+            ..default_config.stopping.clone()
+        },
+        ..default_config.clone()
+    };
+
+    let problems = BenchmarkProblem::standard_suite(dimension);
+    let configs = vec![
+        BenchmarkConfig::new("default", default_config),
+        BenchmarkConfig::new("loose", loose_config),
+    ];
+    let seeds: Vec<u64> = (0..num_seeds).collect();
+
+    let report = run_benchmark(&problems, &configs, &seeds);
+
+    let formatted = match format.as_str() {
+        "csv" => report.to_csv(),
+        "json" => report.to_json(),
+        _ => exit_with_usage("--format"),
+    };
+
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(&path, formatted) {
+                eprintln!("failed to write report to {}: {}", path, e);
+                process::exit(1);
+            }
+        }
+        None => print!("{}", formatted),
+    }
+}
+
+fn parse_or_exit<T: std::str::FromStr>(arg: &str, value: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with_usage(arg))
+}
+
+fn exit_with_usage(arg: &str) -> ! {
+    eprintln!("invalid argument: {}\n{}", arg, USAGE);
+    process::exit(2);
+}
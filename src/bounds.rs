@@ -1,4 +1,3 @@
-use crate::hypercube::Hypercube;
 use crate::point;
 use crate::point::Point;
 
@@ -90,7 +89,7 @@ impl HypercubeBounds {
         }
 
         // both upper and lower bounds should not ever be outside the `rhs` bounds
-        return if lower_outside_range && upper_outside_range {
+        if lower_outside_range && upper_outside_range {
             BoundsOverlap::BothOutOfBounds
         } else if lower_outside_range {
             BoundsOverlap::LowerOutOfBounds
@@ -98,7 +97,7 @@ impl HypercubeBounds {
             BoundsOverlap::UpperOutOfBounds
         } else {
             BoundsOverlap::NoneOutOfBounds
-        };
+        }
     }
 
     /// Displaces hypercube bounds by `vector`
@@ -138,8 +137,8 @@ impl HypercubeBounds {
         let mut new_lower = self.lower.clone();
         let mut new_upper = self.upper.clone();
 
-        new_lower.shrink_towards_center_in_place(&center, scale_factor);
-        new_upper.shrink_towards_center_in_place(&center, scale_factor);
+        new_lower.shrink_towards_center_in_place(center, scale_factor);
+        new_upper.shrink_towards_center_in_place(center, scale_factor);
 
         Self {
             lower: new_lower,
@@ -147,6 +146,23 @@ impl HypercubeBounds {
         }
     }
 
+    /// Scale bounds away from `center` by a scale factor of at least 1
+    pub fn expand_from_center(&self, center: &Point, scale_factor: f64) -> Self {
+        assert!(scale_factor >= 1.0, "scale factor below 1 is invalid");
+        assert_eq!(
+            self.lower.dim(),
+            center.dim(),
+            "center point dimension and bounds point dimension do not match. expected {}, got {}",
+            self.lower.dim(),
+            center.dim()
+        );
+
+        Self {
+            lower: center + &(&self.lower - center).scale(scale_factor),
+            upper: center + &(&self.upper - center).scale(scale_factor),
+        }
+    }
+
     pub fn get_diagonal(&self) -> Point {
         &self.upper - &self.lower
     }
@@ -159,7 +175,7 @@ impl HypercubeBounds {
         // the value of one of the elements since all elements in the diagonal should be the
         // same due to the bounds invariant where whatever happens to the lower bound also
         // happens to the upper bound
-        diagonal.sum() as f64 / diagonal.dim() as f64
+        diagonal.sum() / diagonal.dim() as f64
     }
 
     pub fn get_lower(&self) -> &Point {
@@ -181,6 +197,20 @@ impl HypercubeBounds {
         }
     }
 
+    /// Checks whether `point` lies inside the bounds, faces included
+    pub fn contains(&self, point: &Point) -> bool {
+        assert_eq!(
+            point.dim(),
+            self.dim(),
+            "point dimension and bounds dimension do not match"
+        );
+
+        point
+            .iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .all(|(x, (lower, upper))| lower <= x && x <= upper)
+    }
+
     pub fn compute_center(&self) -> Point {
         (&self.upper + &self.lower).scale(1.0 / 2.0)
     }
@@ -231,7 +261,7 @@ impl HypercubeBounds {
             // if both bounds are out of bounds, clamp them
             BoundsOverlap::BothOutOfBounds => {
                 let lower_clamp_result = self.clamp_lower(limit);
-                self.clamp_upper(&lower_clamp_result)
+                lower_clamp_result.clamp_upper(limit)
             }
         }
     }
@@ -331,6 +361,15 @@ mod tests {
         assert_eq!(expected_result, b);
     }
 
+    #[test]
+    fn contains_1() {
+        let a = HypercubeBounds::new(2, -1.0, 1.0);
+
+        assert!(a.contains(&point![0.0, 0.5]));
+        assert!(a.contains(&point![1.0, -1.0]));
+        assert!(!a.contains(&point![1.0 + f64::EPSILON, 0.0]));
+    }
+
     // <----- .within() tests ----->
 
     #[test]
@@ -415,6 +454,21 @@ mod tests {
         )
     }
 
+    #[test]
+    fn clamp_opposite_corners_out_of_bounds() {
+        let mut new_bounds = HypercubeBounds::new(2, 0.0, 120.0);
+        let init_bounds = HypercubeBounds::new(2, 0.0, 120.0);
+
+        new_bounds.scale_in_place(0.5);
+        new_bounds.displace_by_in_place(&point![90.0, -30.0]);
+
+        let calculated_result = new_bounds.clamp(&init_bounds);
+
+        let expected_result = HypercubeBounds::from_points(point![60.0, 0.0], point![120.0, 60.0]);
+
+        assert_eq!(calculated_result, expected_result);
+    }
+
     #[test]
     fn clamp_upper_out_of_bounds() {
         let mut new_bounds = HypercubeBounds::new(3, 0.0, 120.0);
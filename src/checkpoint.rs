@@ -0,0 +1,219 @@
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::Path;
+
+use crate::evaluation::PointEval;
+use crate::point::Point;
+
+/// Bytes at the start of every checkpoint file
+const MAGIC: &[u8; 4] = b"HCCP";
+
+/// Snapshot of an optimization that lets it continue where it left off, e.g. after the process
+/// was killed. Holds the progress counters, the best evaluation, and the position and size of the
+/// hypercube. The random number generator and the convergence checks start over on resume.
+///
+/// The file is binary: the magic bytes `HCCP`, the fingerprint as a little-endian `u64`, the
+/// loops, evaluations and dimension as little-endian `u32`, then the best point, its image, the
+/// hypercube center and its side length, all as little-endian `f64`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    /// identifies the problem and configuration the checkpoint was taken for
+    fingerprint: u64,
+
+    /// number of loops finished when the checkpoint was taken
+    loops: u32,
+
+    /// number of function evaluations made when the checkpoint was taken
+    fn_evals: u32,
+
+    /// best evaluation so far, in the search space of the optimizer
+    best: PointEval,
+
+    /// center of the hypercube
+    center: Point,
+
+    /// side length of the hypercube
+    side_length: f64,
+}
+
+impl Checkpoint {
+    pub(crate) fn new(
+        fingerprint: u64,
+        loops: u32,
+        fn_evals: u32,
+        best: PointEval,
+        center: Point,
+        side_length: f64,
+    ) -> Self {
+        assert_eq!(
+            best.get_point().dim(),
+            center.dim(),
+            "best point and hypercube center dimensions do not match"
+        );
+
+        Self {
+            fingerprint,
+            loops,
+            fn_evals,
+            best,
+            center,
+            side_length,
+        }
+    }
+
+    /// Reads the checkpoint file at `path`
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut reader = File::open(path)?;
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+
+        if &magic != MAGIC {
+            return Err(io::Error::new(ErrorKind::InvalidData, "not a checkpoint"));
+        }
+
+        let mut header = [0; 20];
+        reader.read_exact(&mut header)?;
+
+        let fingerprint = u64::from_le_bytes(header[0..8].try_into().unwrap());
+        let loops = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        let fn_evals = u32::from_le_bytes(header[12..16].try_into().unwrap());
+        let dimension = u32::from_le_bytes(header[16..20].try_into().unwrap()) as usize;
+
+        if dimension == 0 {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "checkpoint dimension is zero",
+            ));
+        }
+
+        let mut buffer = vec![0; (2 * dimension + 2) * 8];
+        reader.read_exact(&mut buffer)?;
+
+        let values: Vec<f64> = buffer
+            .chunks_exact(8)
+            .map(|bytes| f64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+
+        let best = PointEval::from_image(
+            Point::from_vec(values[..dimension].to_vec()),
+            values[dimension],
+        );
+        let center = Point::from_vec(values[dimension + 1..2 * dimension + 1].to_vec());
+
+        Ok(Self::new(
+            fingerprint,
+            loops,
+            fn_evals,
+            best,
+            center,
+            values[2 * dimension + 1],
+        ))
+    }
+
+    /// Writes the checkpoint to `path`. The checkpoint is written to a temporary file next to
+    /// `path` first and then renamed, so `path` never holds a partially written checkpoint.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&self.fingerprint.to_le_bytes());
+        bytes.extend_from_slice(&self.loops.to_le_bytes());
+        bytes.extend_from_slice(&self.fn_evals.to_le_bytes());
+        bytes.extend_from_slice(&self.center.dim().to_le_bytes());
+
+        let best_point = self.best.get_point();
+        let values = best_point
+            .iter()
+            .copied()
+            .chain([self.best.get_eval()])
+            .chain(self.center.iter().copied())
+            .chain([self.side_length]);
+
+        for value in values {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let mut file = File::create(&temp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+
+        fs::rename(&temp_path, path)
+    }
+
+    pub fn get_fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    pub fn get_loops(&self) -> u32 {
+        self.loops
+    }
+
+    pub fn get_fn_evals(&self) -> u32 {
+        self.fn_evals
+    }
+
+    pub fn get_best(&self) -> &PointEval {
+        &self.best
+    }
+
+    pub fn get_center(&self) -> &Point {
+        &self.center
+    }
+
+    pub fn get_side_length(&self) -> f64 {
+        self.side_length
+    }
+
+    pub fn get_dimension(&self) -> u32 {
+        self.center.dim()
+    }
+}
+
+/// 64-bit FNV-1a hash of `text`. Unlike the standard library hashers, it is stable across
+/// processes and compiler versions, so fingerprints stored in checkpoint files stay comparable.
+pub(crate) fn fingerprint(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point;
+
+    #[test]
+    fn write_then_read() {
+        let path = std::env::temp_dir().join(format!(
+            "hypercube-checkpoint-test-{}.bin",
+            std::process::id()
+        ));
+
+        let checkpoint = Checkpoint::new(
+            7,
+            12,
+            3400,
+            PointEval::from_image(point![0.5, -1.5], -2.5),
+            point![0.25, -1.0],
+            0.75,
+        );
+
+        checkpoint.write(&path).unwrap();
+        let read = Checkpoint::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read, checkpoint);
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        assert_eq!(fingerprint(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fingerprint("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(fingerprint("config a"), fingerprint("config b"));
+    }
+}
@@ -0,0 +1,468 @@
+use std::collections::VecDeque;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::backend::{EvaluationBackend, LocalBackend, Objective};
+use crate::bounds::HypercubeBounds;
+use crate::evaluation::PointEval;
+use crate::linalg::{identity, mat_vec, symmetric_eigen};
+use crate::optimizer::Optimizer;
+use crate::point;
+use crate::point::Point;
+use crate::result::{HypercubeOptimizerResult, IterationRecord};
+use crate::stopping::{Budget, StoppingCriteria};
+
+/// Number of consecutive generations with too few successful evaluations after which the
+/// optimization gives up
+const MAX_FAILED_GENERATIONS: u32 = 10;
+
+/// Covariance matrix adaptation evolution strategy (CMA-ES). Samples each generation from a
+/// multivariate normal distribution whose mean, step size, and covariance matrix are adapted
+/// towards the best offspring of the previous generation. Sampled points are clamped to the
+/// search space bounds before they are evaluated. Generations with too few successful evaluations
+/// are resampled, and the optimization stops with exit code 1 after 10 of them in a row.
+///
+/// This implementation follows "The CMA Evolution Strategy: A Tutorial" by Nikolaus Hansen
+/// (https://arxiv.org/abs/1604.00772).
+pub struct CmaEs {
+    /// dimension of the optimization problem
+    dimension: u32,
+
+    /// initial mean of the search distribution
+    init_point: Point,
+
+    /// bounds of the search space
+    bounds: HypercubeBounds,
+
+    /// initial step size of the search distribution
+    init_sigma: f64,
+
+    /// number of offspring sampled every generation
+    population_size: usize,
+
+    /// conditions that terminate the optimization
+    criteria: StoppingCriteria,
+
+    /// random number generator used to sample offspring
+    rng: StdRng,
+}
+
+/// Strategy parameters that only depend on the dimension and population size
+struct StrategyParameters {
+    /// number of offspring used to recombine the new mean
+    mu: usize,
+    weights: Vec<f64>,
+    mu_eff: f64,
+    c_sigma: f64,
+    d_sigma: f64,
+    c_c: f64,
+    c_1: f64,
+    c_mu: f64,
+
+    /// expected length of a standard normally distributed vector
+    chi_n: f64,
+}
+
+impl CmaEs {
+    /// Returns a new `CmaEs` optimizer
+    ///
+    /// # Arguments
+    ///
+    /// * `init_point` - the initial mean of the search distribution
+    /// * `lower_bound` - the lower bound of the search space
+    /// * `upper_bound` - the upper bound of the search space
+    /// * `criteria` - conditions that terminate the optimization. `tol_x` is compared against the
+    ///   largest standard deviation of the search distribution and `tol_f` against the range of
+    ///   recent best values.
+    ///
+    pub fn new(
+        init_point: Point,
+        lower_bound: f64,
+        upper_bound: f64,
+        criteria: StoppingCriteria,
+    ) -> Self {
+        assert!(
+            upper_bound > lower_bound,
+            "upper bound not strictly larger than lower bound"
+        );
+        assert!(
+            init_point.max_val().unwrap() <= upper_bound,
+            "init_point not inside upper bound"
+        );
+        assert!(
+            init_point.min_val().unwrap() >= lower_bound,
+            "init_point not inside lower bound"
+        );
+
+        let dimension = init_point.dim();
+
+        Self {
+            dimension,
+            bounds: HypercubeBounds::new(dimension, lower_bound, upper_bound),
+            init_point,
+            init_sigma: 0.3 * (upper_bound - lower_bound),
+            population_size: 4 + (3.0 * (dimension as f64).ln()).floor() as usize,
+            criteria,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Sets the initial step size of the search distribution. Defaults to 30% of the search space
+    /// side length.
+    pub fn set_sigma(&mut self, sigma: f64) {
+        assert!(sigma > 0.0, "sigma must be positive");
+        self.init_sigma = sigma;
+    }
+
+    /// Sets the number of offspring sampled every generation. Defaults to `4 + 3 ln(n)`.
+    pub fn set_population_size(&mut self, population_size: usize) {
+        assert!(population_size >= 2, "population size must be at least 2");
+        self.population_size = population_size;
+    }
+
+    /// Seeds the random number generator that samples offspring
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Maximizes the objective function evaluated by `backend`
+    pub fn maximize_with<B>(&mut self, backend: &B) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + ?Sized,
+    {
+        let n = self.dimension as usize;
+        let lambda = self.population_size;
+        let params = StrategyParameters::new(n, lambda);
+
+        let mut budget = Budget::start(&self.criteria);
+        let mut history: Vec<IterationRecord> = Vec::new();
+
+        // state of the search distribution
+        let mut mean: Vec<f64> = self.init_point.iter().copied().collect();
+        let mut sigma = self.init_sigma;
+        let mut covariance = identity(n);
+        let mut p_sigma = vec![0.0; n];
+        let mut p_c = vec![0.0; n];
+
+        let mut best: Option<PointEval> = None;
+
+        // best values of recent generations, used for the tol_f check
+        let history_len = 10 + (30.0 * n as f64 / lambda as f64).ceil() as usize;
+        let mut recent_best: VecDeque<f64> = VecDeque::with_capacity(history_len);
+
+        // consecutive generations that could not update the search distribution
+        let mut failed_generations = 0;
+
+        let exit_code = loop {
+            if let Some(exit_code) = budget.check(lambda as u32) {
+                break exit_code;
+            }
+
+            // <----- sample offspring ----->
+
+            // C = B diag(eigenvalues) B^T
+            let (eigenvalues, eigenvectors) = symmetric_eigen(&covariance, n);
+            let std_devs: Vec<f64> = eigenvalues.iter().map(|e| e.max(1e-20).sqrt()).collect();
+
+            let mut offspring: Vec<Point> = Vec::with_capacity(lambda);
+            let mut steps: Vec<Vec<f64>> = Vec::with_capacity(lambda);
+
+            for _ in 0..lambda {
+                let z = Point::random_gaussian(&mut self.rng, &point![0.0; n as u32], 1.0);
+                let scaled: Vec<f64> = z.iter().zip(&std_devs).map(|(z, d)| z * d).collect();
+                let y = mat_vec(&eigenvectors, &scaled);
+
+                let x = Point::from_vec(mean.iter().zip(&y).map(|(m, y)| m + sigma * y).collect())
+                    .clamp(&self.bounds);
+
+                // recompute the step from the clamped point so the update matches what was
+                // actually evaluated
+                steps.push(x.iter().zip(&mean).map(|(x, m)| (x - m) / sigma).collect());
+                offspring.push(x);
+            }
+
+            // <----- evaluate offspring ----->
+
+            let images = backend.evaluate_batch(&offspring);
+            budget.record_evals(lambda as u32);
+            budget.record_loop();
+
+            let mut ranked: Vec<(usize, f64)> = images
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, image)| match image {
+                    Ok(image) if !image.is_nan() => Some((i, image)),
+                    Ok(_) => None,
+                    Err(e) => {
+                        log::warn!("evaluation failed at {:?}: {:?}", offspring[i], e);
+                        None
+                    }
+                })
+                .collect();
+
+            if ranked.len() < params.mu {
+                failed_generations += 1;
+
+                if failed_generations == MAX_FAILED_GENERATIONS {
+                    log::error!(
+                        "optimization process terminated after {} generations with too few \
+                        successful evaluations",
+                        failed_generations
+                    );
+                    break 1;
+                }
+
+                log::warn!(
+                    "only {} of {} offspring evaluated successfully, resampling generation",
+                    ranked.len(),
+                    lambda
+                );
+                continue;
+            }
+
+            failed_generations = 0;
+
+            // best offspring first
+            ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+            let (best_index, best_image) = ranked[0];
+            let generation_best = PointEval::from_image(offspring[best_index].clone(), best_image);
+
+            if best.as_ref().is_none_or(|b| generation_best > *b) {
+                best = Some(generation_best);
+            }
+
+            history.push(IterationRecord {
+                loop_index: budget.loops() - 1,
+                fn_evals: budget.fn_evals(),
+                best_f: best.as_ref().unwrap().get_eval(),
+                diagonal_len: sigma * (0..n).map(|i| covariance[i * n + i]).sum::<f64>().sqrt(),
+            });
+
+            // <----- update mean ----->
+
+            let mut mean_step = vec![0.0; n];
+
+            for (weight, (index, _)) in params.weights.iter().zip(&ranked) {
+                for (step, y) in mean_step.iter_mut().zip(&steps[*index]) {
+                    *step += weight * y;
+                }
+            }
+
+            for (m, step) in mean.iter_mut().zip(&mean_step) {
+                *m += sigma * step;
+            }
+
+            // <----- update evolution paths ----->
+
+            // C^(-1/2) = B diag(1 / std_devs) B^T
+            let inv_sqrt_step: Vec<f64> = {
+                let projected: Vec<f64> = (0..n)
+                    .map(|j| (0..n).map(|i| eigenvectors[i * n + j] * mean_step[i]).sum())
+                    .collect();
+                let scaled: Vec<f64> = projected
+                    .iter()
+                    .zip(&std_devs)
+                    .map(|(p, d)| p / d)
+                    .collect();
+                mat_vec(&eigenvectors, &scaled)
+            };
+
+            let sigma_path_factor =
+                (params.c_sigma * (2.0 - params.c_sigma) * params.mu_eff).sqrt();
+
+            for (p, step) in p_sigma.iter_mut().zip(&inv_sqrt_step) {
+                *p = (1.0 - params.c_sigma) * *p + sigma_path_factor * step;
+            }
+
+            let p_sigma_len = p_sigma.iter().map(|p| p * p).sum::<f64>().sqrt();
+            let generation = budget.loops() as i32;
+            let h_sigma = p_sigma_len
+                / (1.0 - (1.0 - params.c_sigma).powi(2 * generation)).sqrt()
+                / params.chi_n
+                < 1.4 + 2.0 / (n as f64 + 1.0);
+            let h_sigma = if h_sigma { 1.0 } else { 0.0 };
+
+            let c_path_factor = (params.c_c * (2.0 - params.c_c) * params.mu_eff).sqrt();
+
+            for (p, step) in p_c.iter_mut().zip(&mean_step) {
+                *p = (1.0 - params.c_c) * *p + h_sigma * c_path_factor * step;
+            }
+
+            // <----- update covariance matrix ----->
+
+            let old_weight = 1.0 - params.c_1 - params.c_mu
+                + (1.0 - h_sigma) * params.c_1 * params.c_c * (2.0 - params.c_c);
+
+            for i in 0..n {
+                for j in 0..n {
+                    let rank_mu: f64 = params
+                        .weights
+                        .iter()
+                        .zip(&ranked)
+                        .map(|(w, (index, _))| w * steps[*index][i] * steps[*index][j])
+                        .sum();
+
+                    covariance[i * n + j] = old_weight * covariance[i * n + j]
+                        + params.c_1 * p_c[i] * p_c[j]
+                        + params.c_mu * rank_mu;
+                }
+            }
+
+            // <----- update step size ----->
+
+            sigma *= ((params.c_sigma / params.d_sigma) * (p_sigma_len / params.chi_n - 1.0)).exp();
+
+            // <----- convergence checks ----->
+
+            let max_std_dev = (0..n)
+                .map(|i| covariance[i * n + i].sqrt())
+                .fold(0.0, f64::max);
+
+            if sigma * max_std_dev < self.criteria.tol_x {
+                log::info!("optimization process terminated due to input convergence");
+                break 0;
+            }
+
+            if recent_best.len() == history_len {
+                recent_best.pop_front();
+            }
+            recent_best.push_back(best_image);
+
+            let recent_range = recent_best.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b))
+                - recent_best.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+
+            if recent_best.len() == history_len && recent_range <= self.criteria.tol_f {
+                log::info!("optimization process terminated due to image convergence");
+                break 0;
+            }
+        };
+
+        HypercubeOptimizerResult::new(
+            exit_code,
+            budget.loops(),
+            budget.fn_evals(),
+            best.as_ref(),
+            budget.elapsed(),
+        )
+        .with_history(history)
+    }
+}
+
+impl Optimizer for CmaEs {
+    fn maximize(&mut self, obj_function: &Objective) -> HypercubeOptimizerResult {
+        self.maximize_with(&LocalBackend::new(obj_function))
+    }
+}
+
+impl StrategyParameters {
+    /// Computes the default strategy parameters for dimension `n` and `lambda` offspring
+    fn new(n: usize, lambda: usize) -> Self {
+        let n = n as f64;
+        let mu = lambda / 2;
+
+        // log-linearly decreasing recombination weights
+        let raw_weights: Vec<f64> = (0..mu)
+            .map(|i| (mu as f64 + 0.5).ln() - ((i + 1) as f64).ln())
+            .collect();
+        let weight_sum: f64 = raw_weights.iter().sum();
+        let weights: Vec<f64> = raw_weights.iter().map(|w| w / weight_sum).collect();
+
+        let mu_eff = 1.0 / weights.iter().map(|w| w * w).sum::<f64>();
+
+        let c_sigma = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let d_sigma = 1.0 + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + c_sigma;
+        let c_c = (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n);
+        let c_1 = 2.0 / ((n + 1.3).powi(2) + mu_eff);
+        let c_mu =
+            (1.0 - c_1).min(2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0).powi(2) + mu_eff));
+
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        Self {
+            mu,
+            weights,
+            mu_eff,
+            c_sigma,
+            d_sigma,
+            c_c,
+            c_1,
+            c_mu,
+            chi_n,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::{neg_rosenbrock, neg_sphere};
+
+    fn criteria(max_eval: u32) -> StoppingCriteria {
+        StoppingCriteria {
+            tol_x: 1e-8,
+            tol_f: 1e-12,
+            max_loop: 10_000,
+            max_eval,
+            max_timeout: 60,
+        }
+    }
+
+    #[test]
+    fn converges_on_sphere() {
+        let mut cmaes = CmaEs::new(point![3.0; 4], -5.0, 5.0, criteria(20_000));
+        cmaes.set_seed(1);
+
+        let result = Optimizer::maximize(&mut cmaes, &neg_sphere);
+
+        assert!(result.get_best_f().unwrap() > -1e-8);
+        assert!(result.get_fn_evals() <= 20_000);
+    }
+
+    #[test]
+    fn converges_on_rosenbrock() {
+        let mut cmaes = CmaEs::new(point![-2.0; 3], -5.0, 10.0, criteria(50_000));
+        cmaes.set_seed(2);
+
+        let result = Optimizer::maximize(&mut cmaes, &neg_rosenbrock);
+
+        for coord in result.get_best_x().unwrap().iter() {
+            assert!((coord - 1.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn respects_eval_budget() {
+        let mut cmaes = CmaEs::new(point![3.0; 4], -5.0, 5.0, criteria(100));
+
+        let result = Optimizer::maximize(&mut cmaes, &neg_sphere);
+
+        assert_eq!(result.get_exit_code(), 2);
+        assert!(result.get_fn_evals() <= 100);
+    }
+
+    #[test]
+    fn gives_up_on_failing_backend() {
+        let mut cmaes = CmaEs::new(point![3.0; 4], -5.0, 5.0, criteria(20_000));
+        let lambda = cmaes.population_size as u32;
+
+        let result = Optimizer::maximize(&mut cmaes, &|_: &Point| f64::NAN);
+
+        assert_eq!(result.get_exit_code(), 1);
+        assert_eq!(result.get_fn_evals(), MAX_FAILED_GENERATIONS * lambda);
+        assert!(result.get_best_x().is_none());
+    }
+
+    #[test]
+    fn best_point_within_bounds() {
+        // optimum of the sphere function lies outside the search space
+        let mut cmaes = CmaEs::new(point![3.0; 2], 1.0, 5.0, criteria(2_000));
+
+        let result = Optimizer::maximize(&mut cmaes, &neg_sphere);
+        let best_x = result.get_best_x().unwrap();
+
+        assert!(best_x.iter().all(|x| (1.0..=5.0).contains(x)));
+        assert!((result.get_best_f().unwrap() + 2.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,229 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+
+use crate::evaluation::PointEval;
+use crate::point::Point;
+
+/// Bytes at the start of every evaluation log
+const MAGIC: &[u8; 4] = b"HCEL";
+
+/// Append-only log of objective function evaluations, written to disk as they happen so that runs
+/// with millions of evaluations don't have to keep them in memory. Writes are buffered.
+///
+/// The log is binary: the magic bytes `HCEL`, the problem dimension as a little-endian `u32`, then
+/// one record per evaluation made of the point coordinates followed by the image, all as
+/// little-endian `f64`. Use `EvalLogReader` to read it back.
+pub struct EvalLogWriter {
+    writer: Box<dyn Write + Send>,
+    dimension: u32,
+    records: u64,
+}
+
+impl EvalLogWriter {
+    /// Creates a log file at `path` for points of the given `dimension`, truncating any existing
+    /// file
+    pub fn create(path: impl AsRef<Path>, dimension: u32) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Self::new(BufWriter::new(file), dimension)
+    }
+
+    /// Writes the log to `writer`, e.g. a socket or an in-memory buffer
+    pub fn new(writer: impl Write + Send + 'static, dimension: u32) -> io::Result<Self> {
+        assert_ne!(dimension, 0, "dimension cannot be zero");
+
+        let mut writer: Box<dyn Write + Send> = Box::new(writer);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&dimension.to_le_bytes())?;
+
+        Ok(Self {
+            writer,
+            dimension,
+            records: 0,
+        })
+    }
+
+    /// Appends the evaluation of `point`
+    pub fn append(&mut self, point: &Point, image: f64) -> io::Result<()> {
+        assert_eq!(
+            point.dim(),
+            self.dimension,
+            "point dimension does not match log dimension"
+        );
+
+        for coord in point.iter() {
+            self.writer.write_all(&coord.to_le_bytes())?;
+        }
+        self.writer.write_all(&image.to_le_bytes())?;
+
+        self.records += 1;
+
+        Ok(())
+    }
+
+    /// Writes buffered records to the underlying writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn get_dimension(&self) -> u32 {
+        self.dimension
+    }
+
+    /// Number of evaluations appended so far
+    pub fn get_records(&self) -> u64 {
+        self.records
+    }
+}
+
+/// Reads an evaluation log written by `EvalLogWriter`, one evaluation at a time
+pub struct EvalLogReader<R> {
+    reader: R,
+    dimension: u32,
+}
+
+impl EvalLogReader<BufReader<File>> {
+    /// Opens the log file at `path`
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read> EvalLogReader<R> {
+    /// Reads a log from `reader`, checking its header
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+
+        if &magic != MAGIC {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "not an evaluation log",
+            ));
+        }
+
+        let mut dimension = [0; 4];
+        reader.read_exact(&mut dimension)?;
+
+        Ok(Self {
+            reader,
+            dimension: u32::from_le_bytes(dimension),
+        })
+    }
+
+    pub fn get_dimension(&self) -> u32 {
+        self.dimension
+    }
+
+    /// Reads the next record, or returns `None` at the end of the log
+    fn read_record(&mut self) -> io::Result<Option<PointEval>> {
+        let mut buffer = vec![0; (self.dimension as usize + 1) * 8];
+
+        // an empty read means the log ended cleanly after the previous record
+        let first_read = self.reader.read(&mut buffer)?;
+
+        if first_read == 0 {
+            return Ok(None);
+        }
+
+        self.reader
+            .read_exact(&mut buffer[first_read..])
+            .map_err(|e| match e.kind() {
+                ErrorKind::UnexpectedEof => {
+                    io::Error::new(ErrorKind::InvalidData, "truncated evaluation record")
+                }
+                _ => e,
+            })?;
+
+        let mut values: Vec<f64> = buffer
+            .chunks_exact(8)
+            .map(|bytes| f64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+
+        let image = values.pop().unwrap();
+
+        Ok(Some(PointEval::from_image(Point::from_vec(values), image)))
+    }
+}
+
+impl<R: Read> Iterator for EvalLogReader<R> {
+    type Item = io::Result<PointEval>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point;
+    use std::sync::{Arc, Mutex};
+
+    /// In-memory writer whose contents can be inspected after the log writer takes ownership
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_then_read() {
+        let buffer = SharedBuffer::default();
+        let mut writer = EvalLogWriter::new(buffer.clone(), 2).unwrap();
+
+        writer.append(&point![1.0, 2.0], -5.0).unwrap();
+        writer.append(&point![0.5, -0.25], 3.5).unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(writer.get_records(), 2);
+
+        let bytes = buffer.0.lock().unwrap().clone();
+        let reader = EvalLogReader::new(bytes.as_slice()).unwrap();
+
+        assert_eq!(reader.get_dimension(), 2);
+
+        let records: Vec<PointEval> = reader.map(Result::unwrap).collect();
+
+        assert_eq!(
+            records,
+            vec![
+                PointEval::from_image(point![1.0, 2.0], -5.0),
+                PointEval::from_image(point![0.5, -0.25], 3.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated_record() {
+        let buffer = SharedBuffer::default();
+        let mut writer = EvalLogWriter::new(buffer.clone(), 3).unwrap();
+
+        writer.append(&point![1.0; 3], 1.0).unwrap();
+
+        let mut bytes = buffer.0.lock().unwrap().clone();
+        bytes.truncate(bytes.len() - 4);
+
+        let mut reader = EvalLogReader::new(bytes.as_slice()).unwrap();
+
+        assert_eq!(
+            reader.next().unwrap().unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn wrong_magic() {
+        let bytes = b"JUNK\x02\x00\x00\x00".to_vec();
+
+        assert!(EvalLogReader::new(bytes.as_slice()).is_err());
+    }
+}
@@ -1,7 +1,13 @@
 use crate::point::Point;
 use ordered_float::NotNan;
+use std::any::Any;
 use std::cmp::Ordering;
 use std::fmt;
+use std::sync::Arc;
+
+/// Auxiliary output of an objective function evaluation, e.g. a per-metric breakdown of the image
+/// or the path of a model artifact
+pub type Metadata = Arc<dyn Any + Send + Sync>;
 
 /// Used to store the input and output to a specific vector function. Can be placed inside a binary
 /// heap and will be ordered by the image. This means PointEval instances with higher image values
@@ -10,26 +16,45 @@ use std::fmt;
 pub struct PointEval {
     argument: Point,
     image: NotNan<f64>,
+    metadata: Option<Metadata>,
 }
 
 impl PointEval {
     pub fn new(argument: Point, image: NotNan<f64>) -> Self {
-        Self { argument, image }
+        Self {
+            argument,
+            image,
+            metadata: None,
+        }
     }
 
     pub fn with_eval(argument: Point, function: impl Fn(&Point) -> f64) -> Self {
         let image = function(&argument);
+        Self::from_image(argument, image)
+    }
+
+    /// Creates a `PointEval` from an image that was computed elsewhere (e.g. by an evaluation
+    /// backend). Panics if the image is NaN.
+    pub fn from_image(argument: Point, image: f64) -> Self {
         let nn_image = NotNan::new(image);
 
         match nn_image {
             Ok(nn) => Self {
                 argument,
                 image: nn,
+                metadata: None,
             },
             Err(_) => panic!("function evaluated at {:?} returned {}", argument, image),
         }
     }
 
+    /// Same as `from_image`, but returns `None` if the image is NaN
+    pub fn try_from_image(argument: Point, image: f64) -> Option<Self> {
+        NotNan::new(image)
+            .ok()
+            .map(|image| Self::new(argument, image))
+    }
+
     pub fn eval(&mut self, func: fn(&Point) -> f64) {
         // evaluate the function at point and insert image into struct
         let image = func(&self.argument);
@@ -48,8 +73,29 @@ impl PointEval {
         self.image.into_inner()
     }
 
-    pub fn get_point(&self) -> Point {
-        self.argument.clone()
+    pub fn get_point(&self) -> &Point {
+        &self.argument
+    }
+
+    /// Takes the point out of the evaluation without copying it
+    pub fn into_point(self) -> Point {
+        self.argument
+    }
+
+    /// Attaches auxiliary output of the objective function to the evaluation
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Returns the auxiliary output of the evaluation if there is one of type `T`
+    pub fn get_metadata<T: Any>(&self) -> Option<&T> {
+        self.metadata.as_ref()?.downcast_ref()
+    }
+
+    /// Takes the auxiliary output out of the evaluation
+    pub(crate) fn into_metadata(self) -> Option<Metadata> {
+        self.metadata
     }
 }
 
@@ -63,7 +109,7 @@ impl Eq for PointEval {}
 
 impl PartialOrd for PointEval {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.image.cmp(&other.image))
+        Some(self.cmp(other))
     }
 }
 
@@ -76,7 +122,7 @@ impl Ord for PointEval {
 
 impl fmt::Display for PointEval {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:#?} => {}\n", self.argument, self.get_eval())
+        writeln!(f, "{:#?} => {}", self.argument, self.get_eval())
     }
 }
 
@@ -87,6 +133,7 @@ mod tests {
     use crate::point;
     use crate::point::Point;
     use ordered_float::NotNan;
+    use std::sync::Arc;
 
     #[test]
     fn new_1() {
@@ -97,6 +144,7 @@ mod tests {
         let expected_eval = PointEval {
             argument: test_argument,
             image: test_image,
+            metadata: None,
         };
 
         assert_eq!(test_eval, expected_eval)
@@ -110,14 +158,17 @@ mod tests {
         let expected_eval = PointEval {
             argument: test_point.clone(),
             image: NotNan::new(0.0).unwrap(),
+            metadata: None,
         };
+
+        assert_eq!(test_eval, expected_eval);
     }
 
     #[test]
     #[should_panic]
     fn new_with_eval_2() {
         let test_point = point![0.0; 3];
-        let test_eval = PointEval::with_eval(test_point, nan_function);
+        let _test_eval = PointEval::with_eval(test_point, nan_function);
     }
 
     #[test]
@@ -128,8 +179,8 @@ mod tests {
         let test_eval_a = PointEval::with_eval(test_point_a, summation);
         let test_eval_b = PointEval::with_eval(test_point_b, summation);
 
-        assert_eq!(test_eval_a < test_eval_b, true);
-        assert_eq!(test_eval_a <= test_eval_a, true);
+        assert!(test_eval_a < test_eval_b);
+        assert!(test_eval_a <= test_eval_a);
     }
 
     #[test]
@@ -140,7 +191,7 @@ mod tests {
         let test_eval_a = PointEval::with_eval(test_point_a, summation);
         let test_eval_b = PointEval::with_eval(test_point_b, summation);
 
-        assert_eq!(test_eval_a > test_eval_b, false);
+        assert!(test_eval_a <= test_eval_b);
     }
 
     #[test]
@@ -162,6 +213,15 @@ mod tests {
         assert_eq!(test_eval_b.clone().max(test_eval_a), test_eval_b);
     }
 
+    #[test]
+    fn metadata_1() {
+        let test_eval =
+            PointEval::from_image(point![1.0; 3], 2.0).with_metadata(Arc::new("model.bin"));
+
+        assert_eq!(test_eval.get_metadata::<&str>(), Some(&"model.bin"));
+        assert_eq!(test_eval.get_metadata::<String>(), None);
+    }
+
     #[test]
     fn eval_1() {
         let test_point = point![1.0; 3];
@@ -0,0 +1,57 @@
+use crate::point::Point;
+
+/// Something that happened during an optimization. Events are reported to the observer set with
+/// `HypercubeOptimizer::set_observer` so tools can react to the optimization programmatically
+/// instead of parsing log messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptimizerEvent {
+    /// an optimization loop started
+    LoopStarted { loop_index: u32 },
+
+    /// the population of a loop was evaluated. `loop_best_f` is the best image of the population,
+    /// or `None` if no point evaluated successfully.
+    Evaluated {
+        loop_index: u32,
+        fn_evals: u32,
+        loop_best_f: Option<f64>,
+    },
+
+    /// the hypercube shrank, diagonal lengths before and after
+    Shrunk { from: f64, to: f64 },
+
+    /// the hypercube grew, diagonal lengths before and after
+    Expanded { from: f64, to: f64 },
+
+    /// the hypercube moved, new center
+    Displaced { to: Point },
+
+    /// the optimization was restarted with a fresh hypercube
+    Restarted { restart: u32, population_size: u64 },
+
+    /// the best evaluation reached the target value
+    TargetReached { best_f: f64 },
+
+    /// the best evaluation or the best point stopped changing for a whole stagnation window
+    Converged { loop_index: u32 },
+
+    /// the optimization stopped because the loop would not have finished before the deadline
+    DeadlineReached { loop_index: u32 },
+
+    /// the optimization stopped because it was cancelled through a `CancelHandle`
+    Cancelled { loop_index: u32 },
+}
+
+/// Receives the events of an optimization as they happen. Implemented for closures taking an
+/// `&OptimizerEvent`.
+pub trait OptimizerObserver {
+    fn notify(&mut self, event: &OptimizerEvent);
+}
+
+impl<F> OptimizerObserver for F
+where
+    F: FnMut(&OptimizerEvent),
+{
+    fn notify(&mut self, event: &OptimizerEvent) {
+        self(event)
+    }
+}
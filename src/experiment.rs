@@ -0,0 +1,179 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::backend::MaybeSync;
+use crate::optimizer::{HypercubeOptimizer, HypercubeOptimizerConfig};
+use crate::point::Point;
+use crate::result::HypercubeOptimizerResult;
+
+/// Outcome of one seeded run of an experiment
+#[derive(Debug)]
+pub struct SeedRun {
+    pub seed: u64,
+    pub result: HypercubeOptimizerResult,
+}
+
+/// Results of running the optimizer on the same problem under several seeds, with statistics
+/// aggregated over the runs. Runs whose every evaluation failed have no best value and are left
+/// out of the best value statistics.
+#[derive(Debug)]
+pub struct ExperimentResult {
+    runs: Vec<SeedRun>,
+
+    /// best values of the runs in increasing order
+    sorted_best_f: Vec<f64>,
+}
+
+/// Runs the optimizer on `obj_function` under `n_seeds` seeds, in parallel if the `parallel`
+/// feature is enabled. Seeds count up from
+/// the seed of `config`, or from zero if it has none, so experiments are reproducible.
+///
+/// # Arguments
+///
+/// * `obj_function` - the objective function to maximize
+/// * `init_point` - the initial point of every run
+/// * `lower_bound` - the lower bound of the search space
+/// * `upper_bound` - the upper bound of the search space
+/// * `config` - tuning options shared by every run
+/// * `n_seeds` - the number of runs
+///
+pub fn run_experiment<F>(
+    obj_function: F,
+    init_point: &Point,
+    lower_bound: f64,
+    upper_bound: f64,
+    config: &HypercubeOptimizerConfig,
+    n_seeds: u32,
+) -> ExperimentResult
+where
+    F: Fn(&Point) -> f64 + MaybeSync,
+{
+    assert_ne!(n_seeds, 0, "experiment needs at least one seed");
+
+    let first_seed = config.seed.unwrap_or(0);
+
+    #[cfg(feature = "parallel")]
+    let seed_offsets = (0..n_seeds as u64).into_par_iter();
+
+    #[cfg(not(feature = "parallel"))]
+    let seed_offsets = 0..n_seeds as u64;
+
+    let runs: Vec<SeedRun> = seed_offsets
+        .map(|i| {
+            let seed = first_seed.wrapping_add(i);
+
+            let mut run_config = config.clone();
+            run_config.seed = Some(seed);
+
+            let mut optimizer = HypercubeOptimizer::with_config(
+                init_point.clone(),
+                lower_bound,
+                upper_bound,
+                run_config,
+            );
+
+            SeedRun {
+                seed,
+                result: optimizer.maximize(&obj_function),
+            }
+        })
+        .collect();
+
+    ExperimentResult::new(runs)
+}
+
+impl ExperimentResult {
+    fn new(runs: Vec<SeedRun>) -> Self {
+        let mut sorted_best_f: Vec<f64> =
+            runs.iter().filter_map(|r| r.result.get_best_f()).collect();
+        sorted_best_f.sort_by(f64::total_cmp);
+
+        Self {
+            runs,
+            sorted_best_f,
+        }
+    }
+
+    /// Runs in order of their seeds
+    pub fn get_runs(&self) -> &[SeedRun] {
+        &self.runs
+    }
+
+    /// Median of the best values of the runs
+    pub fn get_median_best_f(&self) -> Option<f64> {
+        self.best_f_quantile(0.5)
+    }
+
+    /// Interquartile range of the best values of the runs, i.e. the spread of the middle half
+    pub fn get_best_f_iqr(&self) -> Option<f64> {
+        Some(self.best_f_quantile(0.75)? - self.best_f_quantile(0.25)?)
+    }
+
+    /// Fraction of the runs whose best value reached `target`
+    pub fn get_success_rate(&self, target: f64) -> f64 {
+        let successes = self.sorted_best_f.iter().filter(|f| **f >= target).count();
+
+        successes as f64 / self.runs.len() as f64
+    }
+
+    /// Quantile `q` of the best values, interpolating linearly between runs
+    fn best_f_quantile(&self, q: f64) -> Option<f64> {
+        let last = self.sorted_best_f.len().checked_sub(1)?;
+
+        let position = q * last as f64;
+        let below = position.floor() as usize;
+        let above = position.ceil() as usize;
+        let fraction = position - below as f64;
+
+        Some(
+            self.sorted_best_f[below]
+                + fraction * (self.sorted_best_f[above] - self.sorted_best_f[below]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluation::PointEval;
+    use crate::point;
+    use std::time::Duration;
+
+    fn run_with_best(seed: u64, best_f: f64) -> SeedRun {
+        let best = PointEval::from_image(point![0.0], best_f);
+
+        SeedRun {
+            seed,
+            result: HypercubeOptimizerResult::new(0, 1, 1, Some(&best), Duration::ZERO),
+        }
+    }
+
+    #[test]
+    fn aggregate_statistics() {
+        let runs = [4.0, 1.0, 3.0, 2.0, 5.0]
+            .into_iter()
+            .enumerate()
+            .map(|(seed, best_f)| run_with_best(seed as u64, best_f))
+            .collect();
+
+        let experiment = ExperimentResult::new(runs);
+
+        assert_eq!(experiment.get_median_best_f(), Some(3.0));
+        assert_eq!(experiment.get_best_f_iqr(), Some(2.0));
+        assert_eq!(experiment.get_success_rate(4.0), 0.4);
+    }
+
+    #[test]
+    fn failed_runs_count_as_unsuccessful() {
+        let failed = SeedRun {
+            seed: 1,
+            result: HypercubeOptimizerResult::new(1, 0, 1, None, Duration::ZERO),
+        };
+
+        let experiment = ExperimentResult::new(vec![run_with_best(0, 1.0), failed]);
+
+        assert_eq!(experiment.get_median_best_f(), Some(1.0));
+        assert_eq!(experiment.get_best_f_iqr(), Some(0.0));
+        assert_eq!(experiment.get_success_rate(0.0), 0.5);
+    }
+}
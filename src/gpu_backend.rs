@@ -0,0 +1,121 @@
+use std::fmt;
+
+use crate::backend::EvaluationBackend;
+use crate::point::Point;
+
+/// Evaluates a whole batch of points at once, typically by wrapping a GPU compute kernel (e.g. a
+/// `wgpu` compute shader or a CUDA launch). Points are handed over as a flat row-major matrix
+/// with one row of `dimension` coordinates per point, which can be uploaded to device memory
+/// as-is.
+pub trait GpuKernel {
+    /// Error returned when the kernel fails to run
+    type Error: fmt::Debug;
+
+    /// Evaluates the `points.len() / dimension` points stored in `points` and returns one image
+    /// per point, in the same order
+    fn evaluate(&self, points: &[f64], dimension: usize) -> Result<Vec<f64>, Self::Error>;
+}
+
+impl<F, E> GpuKernel for F
+where
+    F: Fn(&[f64], usize) -> Result<Vec<f64>, E>,
+    E: fmt::Debug,
+{
+    type Error = E;
+
+    fn evaluate(&self, points: &[f64], dimension: usize) -> Result<Vec<f64>, Self::Error> {
+        self(points, dimension)
+    }
+}
+
+/// Evaluation backend that hands the population to a `GpuKernel` as a flat matrix and reads back
+/// the images. Large populations are split into launches of at most `max_launch_size` points so
+/// they fit in device memory.
+pub struct GpuBackend<K> {
+    kernel: K,
+    max_launch_size: usize,
+}
+
+/// Error returned when a point could not be evaluated by the kernel
+#[derive(Debug, Clone, PartialEq)]
+pub enum GpuBackendError {
+    /// the kernel failed to run the launch containing the point
+    Kernel(String),
+
+    /// the kernel returned a different number of images than points in the launch
+    ImageCount { expected: usize, actual: usize },
+}
+
+impl<K> GpuBackend<K>
+where
+    K: GpuKernel,
+{
+    /// Creates a new `GpuBackend` that evaluates points with `kernel`
+    pub fn new(kernel: K) -> Self {
+        Self {
+            kernel,
+            max_launch_size: usize::MAX,
+        }
+    }
+
+    /// Sets the maximum number of points evaluated by a single kernel launch. Unlimited by
+    /// default.
+    pub fn with_max_launch_size(mut self, max_launch_size: usize) -> Self {
+        assert_ne!(max_launch_size, 0, "max launch size cannot be zero");
+        self.max_launch_size = max_launch_size;
+        self
+    }
+
+    /// Runs a single kernel launch over `points`
+    fn launch(&self, points: &[Point]) -> Result<Vec<f64>, GpuBackendError> {
+        let dimension = points[0].dim() as usize;
+
+        let mut matrix = Vec::with_capacity(points.len() * dimension);
+
+        for point in points {
+            assert_eq!(
+                point.dim() as usize,
+                dimension,
+                "all points in a batch must have the same dimension"
+            );
+            matrix.extend(point.iter());
+        }
+
+        let images = self
+            .kernel
+            .evaluate(&matrix, dimension)
+            .map_err(|e| GpuBackendError::Kernel(format!("{:?}", e)))?;
+
+        if images.len() != points.len() {
+            return Err(GpuBackendError::ImageCount {
+                expected: points.len(),
+                actual: images.len(),
+            });
+        }
+
+        Ok(images)
+    }
+}
+
+impl<K> EvaluationBackend for GpuBackend<K>
+where
+    K: GpuKernel,
+{
+    type Error = GpuBackendError;
+
+    fn evaluate_batch(&self, points: &[Point]) -> Vec<Result<f64, Self::Error>> {
+        let mut images = Vec::with_capacity(points.len());
+
+        for chunk in points.chunks(self.max_launch_size) {
+            match self.launch(chunk) {
+                Ok(chunk_images) => images.extend(chunk_images.into_iter().map(Ok)),
+                Err(e) => {
+                    log::warn!("kernel launch of {} points failed: {:?}", chunk.len(), e);
+                    images.extend(chunk.iter().map(|_| Err(e.clone())));
+                }
+            }
+        }
+
+        images
+    }
+}
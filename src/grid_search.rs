@@ -0,0 +1,156 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::backend::MaybeSync;
+use crate::bounds::HypercubeBounds;
+use crate::evaluation::PointEval;
+use crate::point::Point;
+
+/// Evaluates `f` on a regular grid spanning `bounds` and returns the best point found. Every
+/// dimension is split into `points_per_dim` evenly spaced coordinates including both bounds, so
+/// `points_per_dim^n` points are evaluated, in parallel if the `parallel` feature is enabled.
+/// With a single point per dimension, only the center of the bounds is evaluated.
+///
+/// The best point is a good initial point for the hypercube optimizer on low-dimensional
+/// problems. Points where `f` returns NaN count as failed evaluations and rank as `-inf`.
+pub fn grid_search<F>(bounds: &HypercubeBounds, points_per_dim: u32, f: F) -> PointEval
+where
+    F: Fn(&Point) -> f64 + MaybeSync,
+{
+    grid_indices(bounds, points_per_dim)
+        .map(|index| evaluate(grid_point(bounds, points_per_dim, index), &f))
+        .max()
+        .unwrap()
+}
+
+/// Same as `grid_search`, but returns the evaluation of every grid point. Points are ordered
+/// with the last coordinate changing fastest.
+pub fn grid_search_full<F>(bounds: &HypercubeBounds, points_per_dim: u32, f: F) -> Vec<PointEval>
+where
+    F: Fn(&Point) -> f64 + MaybeSync,
+{
+    grid_indices(bounds, points_per_dim)
+        .map(|index| evaluate(grid_point(bounds, points_per_dim, index), &f))
+        .collect()
+}
+
+/// Evaluates `f` at `point`, ranking a NaN image as `-inf`
+fn evaluate<F>(point: Point, f: &F) -> PointEval
+where
+    F: Fn(&Point) -> f64,
+{
+    let image = match f(&point) {
+        image if image.is_nan() => f64::NEG_INFINITY,
+        image => image,
+    };
+
+    PointEval::from_image(point, image)
+}
+
+/// Indices of the grid points, iterated in parallel
+#[cfg(feature = "parallel")]
+fn grid_indices(bounds: &HypercubeBounds, points_per_dim: u32) -> rayon::range::Iter<u64> {
+    (0..grid_size(bounds, points_per_dim)).into_par_iter()
+}
+
+/// Indices of the grid points
+#[cfg(not(feature = "parallel"))]
+fn grid_indices(bounds: &HypercubeBounds, points_per_dim: u32) -> std::ops::Range<u64> {
+    0..grid_size(bounds, points_per_dim)
+}
+
+/// Number of points in the grid
+fn grid_size(bounds: &HypercubeBounds, points_per_dim: u32) -> u64 {
+    assert_ne!(points_per_dim, 0, "points per dimension cannot be zero");
+
+    (points_per_dim as u64)
+        .checked_pow(bounds.dim())
+        .expect("grid has too many points")
+}
+
+/// Returns the grid point at `index`, decoding the index as a base `points_per_dim` number
+fn grid_point(bounds: &HypercubeBounds, points_per_dim: u32, index: u64) -> Point {
+    let dimension = bounds.dim() as usize;
+    let mut coords = vec![0.0; dimension];
+    let mut remainder = index;
+
+    for axis in (0..dimension).rev() {
+        let step = remainder % points_per_dim as u64;
+        remainder /= points_per_dim as u64;
+
+        let lower = bounds.get_lower().get(axis).unwrap();
+        let upper = bounds.get_upper().get(axis).unwrap();
+
+        coords[axis] = if points_per_dim == 1 {
+            (lower + upper) / 2.0
+        } else {
+            lower + (upper - lower) * step as f64 / (points_per_dim - 1) as f64
+        };
+    }
+
+    Point::from_vec(coords)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::{neg_rastrigin, neg_sphere};
+    use crate::point;
+
+    #[test]
+    fn grid_search_finds_optimum_on_grid() {
+        let bounds = HypercubeBounds::new(2, -5.0, 5.0);
+
+        let best = grid_search(&bounds, 11, neg_rastrigin);
+
+        assert_eq!(best.get_point(), &point![0.0; 2]);
+        assert_eq!(best.get_eval(), 0.0);
+    }
+
+    #[test]
+    fn grid_search_full_order() {
+        let bounds = HypercubeBounds::new(2, 0.0, 1.0);
+
+        let grid = grid_search_full(&bounds, 3, neg_sphere);
+        let points: Vec<&Point> = grid.iter().map(|e| e.get_point()).collect();
+
+        assert_eq!(grid.len(), 9);
+        assert_eq!(points[0], &point![0.0, 0.0]);
+        assert_eq!(points[1], &point![0.0, 0.5]);
+        assert_eq!(points[3], &point![0.5, 0.0]);
+        assert_eq!(points[8], &point![1.0, 1.0]);
+        assert_eq!(grid[8].get_eval(), -2.0);
+    }
+
+    #[test]
+    fn grid_search_single_point() {
+        let bounds = HypercubeBounds::new(3, 1.0, 3.0);
+
+        let grid = grid_search_full(&bounds, 1, neg_sphere);
+
+        assert_eq!(grid.len(), 1);
+        assert_eq!(grid[0].get_point(), &point![2.0; 3]);
+    }
+
+    #[test]
+    fn grid_search_nan_images() {
+        let bounds = HypercubeBounds::new(1, -1.0, 1.0);
+        let f = |p: &Point| match *p.get(0).unwrap() {
+            x if x > 0.0 => f64::NAN,
+            x => x,
+        };
+
+        let grid = grid_search_full(&bounds, 3, f);
+        let best = grid_search(&bounds, 3, f);
+
+        assert_eq!(grid[2].get_eval(), f64::NEG_INFINITY);
+        assert_eq!(best.get_point(), &point![0.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_search_zero_points() {
+        let bounds = HypercubeBounds::new(2, -1.0, 1.0);
+        grid_search(&bounds, 0, neg_sphere);
+    }
+}
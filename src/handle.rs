@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::evaluation::PointEval;
+
+/// Thread-safe view of the best value found so far by a running optimization. Handles are cheap to
+/// clone and can be polled from other threads while `maximize()` runs, e.g. to update a dashboard
+/// or to take a good enough point early.
+#[derive(Debug, Clone, Default)]
+pub struct BestHandle {
+    best: Arc<RwLock<Option<PointEval>>>,
+}
+
+impl BestHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the best value found so far, or `None` if nothing was evaluated successfully yet
+    pub fn get(&self) -> Option<PointEval> {
+        self.best.read().unwrap().clone()
+    }
+
+    /// Replaces the best value with `candidate` if it is better
+    pub(crate) fn offer(&self, candidate: &PointEval) {
+        let mut best = self.best.write().unwrap();
+
+        if best.as_ref().is_none_or(|best| candidate > best) {
+            *best = Some(candidate.clone());
+        }
+    }
+
+    /// Forgets the best value, e.g. when a new optimization starts
+    pub(crate) fn clear(&self) {
+        *self.best.write().unwrap() = None;
+    }
+}
+
+/// Thread-safe switch that stops a running optimization. Handles are cheap to clone, so one can
+/// be moved into e.g. a signal handler while `maximize()` runs. The optimization stops before its
+/// next loop and reports the best value found so far.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks the optimization to stop
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Withdraws the cancellation, e.g. when a new optimization starts
+    pub(crate) fn reset(&self) {
+        self.cancelled.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point;
+    use crate::point::Point;
+
+    #[test]
+    fn offer_keeps_best() {
+        let handle = BestHandle::new();
+        let reader = handle.clone();
+
+        assert!(reader.get().is_none());
+
+        handle.offer(&PointEval::from_image(point![1.0], -3.0));
+        handle.offer(&PointEval::from_image(point![0.5], -1.0));
+        handle.offer(&PointEval::from_image(point![2.0], -5.0));
+
+        assert_eq!(reader.get(), Some(PointEval::from_image(point![0.5], -1.0)));
+
+        handle.clear();
+
+        assert!(reader.get().is_none());
+    }
+
+    #[test]
+    fn cancel_is_shared() {
+        let handle = CancelHandle::new();
+        let canceller = handle.clone();
+
+        assert!(!handle.is_cancelled());
+
+        canceller.cancel();
+        assert!(handle.is_cancelled());
+
+        handle.reset();
+        assert!(!canceller.is_cancelled());
+    }
+}
@@ -0,0 +1,222 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::backend::EvaluationBackend;
+use crate::point::Point;
+
+/// Evaluation backend that sends points to a REST service. Points are POSTed in batches to the
+/// configured endpoint as JSON of the form
+///
+/// ```json
+/// { "points": [[1.0, 2.0], [3.0, 4.0]] }
+/// ```
+///
+/// and the service must reply with the images of the points, in the same order:
+///
+/// ```json
+/// { "values": [5.0, 6.0] }
+/// ```
+///
+/// Requests that fail because of transport errors or server errors (5xx) are retried. A request
+/// that still fails after all retries marks every point in its batch as failed.
+pub struct HttpBackend {
+    endpoint: String,
+    agent: ureq::Agent,
+    batch_size: usize,
+    max_concurrency: usize,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+/// Images of a single batch of points, or the reason the whole batch failed
+type BatchResult = Result<Vec<f64>, HttpBackendError>;
+
+/// Error returned when a point could not be evaluated by the remote service
+#[derive(Debug, Clone, PartialEq)]
+pub enum HttpBackendError {
+    /// the request could not be sent or the connection failed
+    Transport(String),
+
+    /// the service replied with a non-success status code
+    Status(u16),
+
+    /// the response body could not be interpreted as a list of images
+    InvalidResponse(String),
+}
+
+impl HttpBackend {
+    /// Creates a new `HttpBackend` that sends points to `endpoint`
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            agent: Self::build_agent(Duration::from_secs(30)),
+            batch_size: 64,
+            max_concurrency: 4,
+            max_retries: 3,
+            retry_delay: Duration::from_millis(200),
+        }
+    }
+
+    /// Sets the timeout for every individual request, including connecting and reading the
+    /// response. Defaults to 30 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.agent = Self::build_agent(timeout);
+        self
+    }
+
+    /// Sets the maximum number of points sent in a single request. Defaults to 64.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert_ne!(batch_size, 0, "batch size cannot be zero");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the maximum number of requests in flight at the same time. Defaults to 4.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        assert_ne!(max_concurrency, 0, "max concurrency cannot be zero");
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Sets how many times a failed request is retried and the base delay between retries. The
+    /// delay grows linearly with every attempt. Defaults to 3 retries with a 200ms base delay.
+    pub fn with_retries(mut self, max_retries: u32, retry_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    fn build_agent(timeout: Duration) -> ureq::Agent {
+        ureq::AgentBuilder::new().timeout(timeout).build()
+    }
+
+    /// Sends a single batch of points, retrying transient failures
+    fn send_batch(&self, points: &[Point]) -> BatchResult {
+        let body = json!({
+            "points": points
+                .iter()
+                .map(|p| p.iter().copied().collect::<Vec<f64>>())
+                .collect::<Vec<_>>()
+        });
+
+        let mut attempt = 0;
+
+        loop {
+            let result = self.post(&body, points.len());
+
+            match result {
+                Err(HttpBackendError::Transport(_)) | Err(HttpBackendError::Status(500..=599))
+                    if attempt < self.max_retries =>
+                {
+                    attempt += 1;
+                    log::warn!(
+                        "request to {} failed ({:?}), retrying ({}/{})",
+                        self.endpoint,
+                        result.unwrap_err(),
+                        attempt,
+                        self.max_retries
+                    );
+                    thread::sleep(self.retry_delay * attempt);
+                }
+                _ => return result,
+            }
+        }
+    }
+
+    /// POSTs `body` to the endpoint and parses the returned images
+    fn post(&self, body: &Value, expected_len: usize) -> BatchResult {
+        let response = match self.agent.post(&self.endpoint).send_json(body) {
+            Ok(response) => response,
+            Err(ureq::Error::Status(code, _)) => return Err(HttpBackendError::Status(code)),
+            Err(e) => return Err(HttpBackendError::Transport(e.to_string())),
+        };
+
+        let reply: Value = response
+            .into_json()
+            .map_err(|e| HttpBackendError::InvalidResponse(e.to_string()))?;
+
+        let values = reply
+            .get("values")
+            .and_then(Value::as_array)
+            .ok_or_else(|| {
+                HttpBackendError::InvalidResponse("response has no \"values\" array".to_string())
+            })?;
+
+        if values.len() != expected_len {
+            return Err(HttpBackendError::InvalidResponse(format!(
+                "expected {} values, got {}",
+                expected_len,
+                values.len()
+            )));
+        }
+
+        values
+            .iter()
+            .map(|v| {
+                v.as_f64().ok_or_else(|| {
+                    HttpBackendError::InvalidResponse(format!("{} is not a number", v))
+                })
+            })
+            .collect()
+    }
+}
+
+impl EvaluationBackend for HttpBackend {
+    type Error = HttpBackendError;
+
+    fn evaluate_batch(&self, points: &[Point]) -> Vec<Result<f64, Self::Error>> {
+        let batches: Vec<&[Point]> = points.chunks(self.batch_size).collect();
+        let batch_results: Mutex<Vec<Option<BatchResult>>> = Mutex::new(vec![None; batches.len()]);
+
+        // workers pull batches off a shared counter until there are none left
+        let next_batch = AtomicUsize::new(0);
+        let num_workers = self.max_concurrency.min(batches.len());
+
+        thread::scope(|scope| {
+            for _ in 0..num_workers {
+                scope.spawn(|| loop {
+                    let index = next_batch.fetch_add(1, Ordering::Relaxed);
+
+                    if index >= batches.len() {
+                        break;
+                    }
+
+                    let result = self.send_batch(batches[index]);
+                    batch_results.lock().unwrap()[index] = Some(result);
+                });
+            }
+        });
+
+        // flatten batch results back into one result per point
+        let mut images = Vec::with_capacity(points.len());
+
+        for (batch, result) in batches.iter().zip(batch_results.into_inner().unwrap()) {
+            match result.unwrap() {
+                Ok(values) => images.extend(values.into_iter().map(Ok)),
+                Err(e) => images.extend(batch.iter().map(|_| Err(e.clone()))),
+            }
+        }
+
+        images
+    }
+}
+
+impl fmt::Display for HttpBackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpBackendError::Transport(message) => write!(f, "transport error: {}", message),
+            HttpBackendError::Status(code) => write!(f, "server replied with status {}", code),
+            HttpBackendError::InvalidResponse(message) => {
+                write!(f, "invalid response: {}", message)
+            }
+        }
+    }
+}
+
+impl Error for HttpBackendError {}
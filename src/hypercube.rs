@@ -1,13 +1,46 @@
 use std::collections::BinaryHeap;
 use std::fmt;
-
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use ordered_float::NotNan;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "parallel")]
+use rayon::ThreadPool;
+
+use crate::backend::{EvaluationBackend, LocalBackend, MaybeSync};
 use crate::bounds::HypercubeBounds;
+use crate::eval_log::EvalLogWriter;
 use crate::evaluation::PointEval;
 use crate::point;
 use crate::point::Point;
+use crate::population_file::read_population;
+use crate::sampler::Sampler;
 
 use crate::bounds::BoundsOverlap;
 
+/// Number of population points generated from a single random number generator stream. The
+/// population is generated in chunks of this size so that parallel sampling stays reproducible
+/// regardless of the number of threads. Even, so mirrored pairs never straddle two chunks.
+const SAMPLING_CHUNK_SIZE: u64 = 256;
+
+/// Largest population the default population size formula may produce. Bounds that call for more
+/// points make a `HypercubeOptimizer` stop right away with exit code 4 unless it is given a
+/// population size.
+pub const MAX_POPULATION_SIZE: u64 = 1 << 20;
+
+/// Image of the population point at `index`. Evaluations refer to their points by index so that
+/// storing and ranking them never copies a point. Ordered by image, then index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct IndexedImage {
+    image: NotNan<f64>,
+    index: usize,
+}
+
 #[derive(Clone)]
 pub struct Hypercube {
     dimension: u32,
@@ -17,12 +50,20 @@ pub struct Hypercube {
     center: Point,
     population_size: u64,
     population: Vec<Point>,
-    values: Vec<PointEval>,
-    ordered_values: BinaryHeap<PointEval>,
+    values: Vec<IndexedImage>,
+    ordered_values: BinaryHeap<IndexedImage>,
+    axis_scales: Point,
+    sampler: Sampler,
+    sampling_focus: Option<Point>,
+    #[cfg(feature = "parallel")]
+    thread_pool: Option<Arc<ThreadPool>>,
+    eval_log: Option<Arc<Mutex<EvalLogWriter>>>,
+    rng: StdRng,
 }
 
 impl Hypercube {
-    /// Creates a new hypercube with a given `dimension` and bounds.
+    /// Creates a new hypercube with a given `dimension` and bounds. Bounds too large for the
+    /// default population size get a population of `MAX_POPULATION_SIZE` points, with a warning.
     pub fn new(dimension: u32, lower_bound: f64, upper_bound: f64) -> Self {
         assert_ne!(dimension, 0, "dimension cannot be zero");
         assert!(
@@ -30,30 +71,68 @@ impl Hypercube {
             "upper bound is not strictly larger than lower bound"
         );
 
+        // bounds too large for the formula get the largest population it may produce
+        let num_points = Hypercube::default_population_size(dimension, lower_bound, upper_bound)
+            .unwrap_or_else(|| {
+                log::warn!(
+                    "default population size too large for the bounds, using {} points",
+                    MAX_POPULATION_SIZE
+                );
+                MAX_POPULATION_SIZE
+            });
+
+        Hypercube::with_population_size(dimension, lower_bound, upper_bound, num_points)
+    }
+
+    /// Returns the default population size of a hypercube, `n^2 (upper - lower)` for an
+    /// `n`-dimensional hypercube, or `None` if that exceeds `MAX_POPULATION_SIZE`
+    pub fn default_population_size(
+        dimension: u32,
+        lower_bound: f64,
+        upper_bound: f64,
+    ) -> Option<u64> {
+        let num_points = (dimension as f64).powi(2) * (upper_bound - lower_bound).trunc();
+
+        (num_points <= MAX_POPULATION_SIZE as f64).then_some(num_points as u64)
+    }
+
+    /// Creates a new hypercube with a given `dimension`, bounds, and number of points in its
+    /// population.
+    pub fn with_population_size(
+        dimension: u32,
+        lower_bound: f64,
+        upper_bound: f64,
+        population_size: u64,
+    ) -> Self {
+        assert_ne!(dimension, 0, "dimension cannot be zero");
+        assert!(
+            upper_bound > lower_bound,
+            "upper bound is not strictly larger than lower bound"
+        );
+
         // generate initial bounds struct
         let init_bounds: HypercubeBounds =
             HypercubeBounds::new(dimension, lower_bound, upper_bound);
 
-        // TODO: replace with function that takes dimension and bounds and returns number of hypercube points
-        let num_points = dimension.pow(2) * ((upper_bound - lower_bound) as u32);
-
         // calculate the hypercube's diagonal
         let hypercube_diagonal: Point =
             &point![upper_bound; dimension] - &point![lower_bound; dimension];
 
-        let random_points = Hypercube::generate_random_points(
-            dimension,
-            num_points as u64,
-            lower_bound,
-            upper_bound,
-        );
-
-        let population_size = random_points.len() as u64;
-
         // generate center vector
         let central_value: f64 = (upper_bound + lower_bound) / 2.0;
         let center: Point = point![central_value; dimension];
 
+        let mut rng = StdRng::from_entropy();
+
+        let random_points = Hypercube::generate_random_points(
+            &mut rng,
+            &Sampler::Uniform,
+            population_size,
+            &center,
+            &hypercube_diagonal.scale(0.5),
+            &center,
+        );
+
         // return Hypercube struct
         Self {
             dimension,
@@ -65,38 +144,165 @@ impl Hypercube {
             population: random_points,
             values: Vec::with_capacity(population_size as usize),
             ordered_values: BinaryHeap::with_capacity(population_size as usize),
+            axis_scales: point![1.0; dimension],
+            sampler: Sampler::Uniform,
+            sampling_focus: None,
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
+            eval_log: None,
+            rng,
+        }
+    }
+
+    /// Seeds the random number generator used to sample the population. Hypercubes with the same
+    /// seed generate the same sequence of populations.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Draws a seed for another random number generator from the one of the hypercube
+    pub(crate) fn draw_seed(&mut self) -> u64 {
+        self.rng.gen()
+    }
+
+    /// Sets the thread pool used to sample and evaluate the population. When no pool is set, the
+    /// global rayon thread pool is used.
+    #[cfg(feature = "parallel")]
+    pub fn set_thread_pool(&mut self, thread_pool: Option<Arc<ThreadPool>>) {
+        self.thread_pool = thread_pool;
+    }
+
+    /// Sets the log that every successful population evaluation is appended to
+    pub fn set_eval_log(&mut self, eval_log: Option<Arc<Mutex<EvalLogWriter>>>) {
+        self.eval_log = eval_log;
+    }
+
+    /// Runs `op` inside the hypercube's thread pool, or on the global pool if none was set
+    #[cfg(feature = "parallel")]
+    fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        match &self.thread_pool {
+            Some(pool) => pool.install(op),
+            None => op(),
         }
     }
 
+    /// Runs `op` on the current thread
+    #[cfg(not(feature = "parallel"))]
+    fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R,
+    {
+        op()
+    }
+
     /// Applies the vector function to all points in the population and stores it in the hypercube
-    /// struct.
-    pub fn evaluate(&mut self, point_function: impl Fn(&Point) -> f64) {
-        // iterate over population points, apply vector function, and store result in values and
-        // ordered_values
-        for point in &self.population {
-            // TODO: improve this so unnecessary cloning is removed
-            let new_eval = PointEval::with_eval(point.clone(), &point_function);
-            self.values.push(new_eval.clone());
-            self.ordered_values.push(new_eval);
+    /// struct. Points are evaluated in parallel.
+    pub fn evaluate(&mut self, point_function: impl Fn(&Point) -> f64 + MaybeSync) {
+        self.evaluate_with(&LocalBackend::new(point_function));
+    }
+
+    /// Evaluates all points in the population using `backend` and stores the results in the
+    /// hypercube struct. Points that fail to evaluate or evaluate to NaN are logged and left out of
+    /// the results.
+    pub fn evaluate_with<B>(&mut self, backend: &B)
+    where
+        B: EvaluationBackend + MaybeSync + ?Sized,
+        B::Error: Send,
+    {
+        self.evaluate_images_with(backend);
+    }
+
+    /// Same as `evaluate_with`, but also returns the image of every point in the population, in
+    /// population order. Points that failed to evaluate have no image.
+    pub(crate) fn evaluate_images_with<B>(&mut self, backend: &B) -> Vec<Option<f64>>
+    where
+        B: EvaluationBackend + MaybeSync + ?Sized,
+        B::Error: Send,
+    {
+        let population = &self.population;
+
+        let images = self.install(|| backend.evaluate_batch(population));
+
+        assert_eq!(
+            images.len(),
+            population.len(),
+            "evaluation backend returned the wrong number of images. expected {}, got {}.",
+            population.len(),
+            images.len()
+        );
+
+        let mut indexed_images = Vec::with_capacity(images.len());
+
+        // store results in values and ordered_values
+        for (index, (point, image)) in population.iter().zip(images).enumerate() {
+            match image.map(NotNan::new) {
+                Ok(Ok(image)) => {
+                    let indexed_image = IndexedImage { image, index };
+
+                    self.values.push(indexed_image);
+                    self.ordered_values.push(indexed_image);
+                    indexed_images.push(Some(image.into_inner()));
+                }
+                // a NaN image counts as a failed evaluation
+                Ok(Err(_)) => {
+                    log::warn!("evaluation at {:?} returned NaN", point);
+                    indexed_images.push(None);
+                }
+                Err(e) => {
+                    log::warn!("evaluation failed at {:?}: {:?}", point, e);
+                    indexed_images.push(None);
+                }
+            }
+        }
+
+        if let Some(eval_log) = &self.eval_log {
+            let mut eval_log = eval_log.lock().unwrap();
+
+            for (point, image) in population.iter().zip(&indexed_images) {
+                if let Some(image) = image {
+                    if let Err(e) = eval_log.append(point, *image) {
+                        log::warn!("failed to append to evaluation log: {}", e);
+                        break;
+                    }
+                }
+            }
         }
+
+        self.check_invariants("evaluate");
+
+        indexed_images
     }
 
     /// Peek at the maximum value evaluated by the hypercube
     pub fn peek_best_value(&self) -> Option<PointEval> {
-        let best_value = self.ordered_values.peek();
-        best_value.map(|v| v.to_owned())
+        self.peek_best()
+            .map(|(point, image)| PointEval::from_image(point.clone(), image))
+    }
+
+    /// Peek at the point with the maximum value evaluated by the hypercube and its value, without
+    /// copying the point
+    pub fn peek_best(&self) -> Option<(&Point, f64)> {
+        self.ordered_values
+            .peek()
+            .map(|best| (&self.population[best.index], best.image.into_inner()))
     }
 
     /// Pop the maximum value evaluated by the hypercube
     pub fn pop_best_value(&mut self) -> Option<PointEval> {
-        self.ordered_values.pop()
+        self.ordered_values
+            .pop()
+            .map(|best| PointEval::from_image(self.population[best.index].clone(), *best.image))
     }
 
     /// Displaces the hypercube by adding the `vector` argument to the hypercube's center.
     pub fn try_displace_by(&mut self, vector: &Point) -> Result<(), &'static str> {
         // ensures the destination vector is the correct dimension
         assert_eq!(
-            vector.dim() as u32,
+            vector.dim(),
             self.dimension,
             "vector is not the correct size. \
             expected {}, got {}.",
@@ -112,7 +318,7 @@ impl Hypercube {
             BoundsOverlap::NoneOutOfBounds => {
                 // add vector to all points in population
                 for point in self.population.iter_mut() {
-                    *point += vector.clone();
+                    *point += vector;
                 }
 
                 // current bounds should now be new_bounds
@@ -128,6 +334,8 @@ impl Hypercube {
                 // calculate new diagonal
                 self.diagonal = self.current_bounds.get_upper() - self.current_bounds.get_lower();
 
+                self.check_invariants("displace");
+
                 Ok(())
             }
             _ => Err("cannot displace, displacement results in hypercube out of bounds"),
@@ -140,7 +348,7 @@ impl Hypercube {
 
         // ensures the destination vector is the correct dimension
         assert_eq!(
-            destination.dim() as u32,
+            destination.dim(),
             self.dimension,
             "destination is not the correct dimension. \
             expected {}, got {}.",
@@ -160,7 +368,7 @@ impl Hypercube {
 
         // ensures the destination vector is the correct dimension
         assert_eq!(
-            destination.dim() as u32,
+            destination.dim(),
             self.dimension,
             "vector is not the correct size. \
             expected {}, got {}.",
@@ -213,7 +421,7 @@ impl Hypercube {
 
         // add destination to population
         for point in self.population.iter_mut() {
-            *point += center_to_destination.clone();
+            *point += &center_to_destination;
         }
 
         // wipe out previous evaluation results
@@ -222,6 +430,8 @@ impl Hypercube {
 
         // calculate new diagonal
         self.diagonal = self.current_bounds.get_upper() - self.current_bounds.get_lower();
+
+        self.check_invariants("displace");
     }
 
     /// Shrinks the hypercube by the given `factor`. This eliminates the previously computed
@@ -246,47 +456,217 @@ impl Hypercube {
         // clear previous evaluation values
         self.values.clear();
         self.ordered_values.clear();
+
+        self.check_invariants("shrink");
+    }
+
+    /// Expands the hypercube by the given `factor`, up to the size it was initialized with. If the
+    /// expanded hypercube sticks out of its initial bounds, it is moved back inside them. This
+    /// eliminates the previously computed hypercube values.
+    pub fn expand(&mut self, factor: f64) {
+        assert!(factor >= 1.0, "factor cannot be less than one");
+
+        // never grow past the initial hypercube
+        let factor = factor.min(self.init_bounds.get_length() / self.current_bounds.get_length());
+
+        // resize current bounds and move them back inside the initial bounds
+        self.current_bounds = self
+            .current_bounds
+            .expand_from_center(&self.center, factor)
+            .clamp(&self.init_bounds);
+
+        let new_center = self.current_bounds.compute_center();
+
+        // resize population points around the new center
+        for point in self.population.iter_mut() {
+            *point = &new_center + &(&*point - &self.center).scale(factor);
+        }
+
+        self.center = new_center;
+
+        // recalculate diagonal
+        self.diagonal = self.current_bounds.get_diagonal();
+
+        // clear previous evaluation values
+        self.values.clear();
+        self.ordered_values.clear();
+
+        self.check_invariants("expand");
+    }
+
+    /// Resets the hypercube to its initial bounds with a new population of `population_size`
+    /// random points. The random number generator and thread pool are kept.
+    pub fn restart(&mut self, population_size: u64) {
+        self.current_bounds = self.init_bounds.clone();
+        self.center = self.init_bounds.compute_center();
+        self.diagonal = self.init_bounds.get_diagonal();
+        self.population_size = population_size;
+        self.axis_scales = point![1.0; self.dimension];
+        self.sampling_focus = None;
+
+        self.randomize_pop();
     }
 
     /// Re-generate points inside hypercube and erase previous evaluations
     pub fn randomize_pop(&mut self) {
+        let population_size = self.population_size;
+        let half_extents = &self.diagonal.scale(0.5) * &self.axis_scales;
+        let center = self.center.clone();
+        let sampler = &self.sampler;
+        let focus = self.sampling_focus.as_ref().unwrap_or(&center);
+        // sample with a copy of the generator so `self` isn't borrowed mutably inside the pool
+        let mut rng = self.rng.clone();
+
         // randomize the hypercube's population
-        let new_random_points = Hypercube::generate_random_points(
-            self.dimension,
-            self.population_size,
-            self.current_bounds.get_lower().min_val().unwrap(),
-            self.current_bounds.get_upper().max_val().unwrap(),
-        );
+        let mut new_random_points = self.install(|| {
+            Hypercube::generate_random_points(
+                &mut rng,
+                sampler,
+                population_size,
+                &center,
+                &half_extents,
+                focus,
+            )
+        });
+
+        self.rng = rng;
+
+        // rounding in repeated shrinks and displacements can leave sampled points a hair outside
+        // the current or initial bounds, so pull them back onto the faces of both
+        for point in new_random_points.iter_mut() {
+            point.clamp_in_place(&self.current_bounds);
+            point.clamp_in_place(&self.init_bounds);
+        }
 
         self.population = new_random_points;
 
         // clear previous evaluations
         self.values.clear();
         self.ordered_values.clear();
+
+        self.check_invariants("randomize");
+        self.check_population_invariant("randomize");
+    }
+
+    /// Sets the fraction of the hypercube side that the population is sampled over along each
+    /// dimension. Defaults to sampling the whole hypercube.
+    pub fn set_axis_scales(&mut self, axis_scales: Point) {
+        assert_eq!(
+            axis_scales.dim(),
+            self.dimension,
+            "axis scales dimension does not match hypercube dimension"
+        );
+        assert!(
+            axis_scales.iter().all(|s| *s > 0.0 && *s <= 1.0),
+            "axis scales must be between zero and one"
+        );
+
+        self.axis_scales = axis_scales;
+    }
+
+    /// Sets the distribution the population is sampled from. Defaults to `Sampler::Uniform`.
+    pub fn set_sampler(&mut self, sampler: Sampler) {
+        sampler.validate();
+        self.sampler = sampler;
+    }
+
+    /// Sets the point that `Sampler::Gaussian` draws around, usually the best point so far.
+    /// Defaults to the center of the hypercube, and is forgotten on restart.
+    pub fn set_sampling_focus(&mut self, focus: Point) {
+        assert_eq!(
+            focus.dim(),
+            self.dimension,
+            "focus dimension does not match hypercube dimension"
+        );
+        self.sampling_focus = Some(focus);
     }
 
-    /// Generate a vector of random points with a given dimension and within given bounds
+    /// Generate a vector of random points distributed according to `sampler` within
+    /// `half_extents` of `center` along each dimension, drawing Gaussian points around `focus`.
+    /// Points are generated in parallel if the
+    /// `parallel` feature is enabled, in chunks that are each seeded from `rng`.
     fn generate_random_points(
-        dimension: u32,
+        rng: &mut StdRng,
+        sampler: &Sampler,
         num_points: u64,
-        lower_bound: f64,
-        upper_bound: f64,
+        center: &Point,
+        half_extents: &Point,
+        focus: &Point,
     ) -> Vec<Point> {
         assert!(
-            upper_bound > lower_bound,
-            "upper bound not strictly larger than lower bound"
+            half_extents.iter().all(|h| *h > 0.0),
+            "half extents must be strictly positive"
         );
 
-        // random point Vector to store random generated points
-        let mut random_points: Vec<Point> = Vec::with_capacity(num_points as usize);
+        // draw one seed per chunk up front so results don't depend on thread scheduling
+        let num_chunks = num_points.div_ceil(SAMPLING_CHUNK_SIZE);
+        let chunk_seeds: Vec<u64> = (0..num_chunks).map(|_| rng.gen()).collect();
+
+        let sample_chunk = |(chunk_index, chunk_seed): (usize, u64)| {
+            let chunk_start = chunk_index as u64 * SAMPLING_CHUNK_SIZE;
+            let chunk_len = SAMPLING_CHUNK_SIZE.min(num_points - chunk_start);
+
+            sampler.sample(
+                StdRng::seed_from_u64(chunk_seed),
+                chunk_len,
+                center,
+                half_extents,
+                focus,
+            )
+        };
+
+        #[cfg(feature = "parallel")]
+        let points = chunk_seeds
+            .into_par_iter()
+            .enumerate()
+            .flat_map_iter(sample_chunk)
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let points = chunk_seeds
+            .into_iter()
+            .enumerate()
+            .flat_map(sample_chunk)
+            .collect();
+
+        points
+    }
 
-        for _ in 0..num_points {
-            // insert point into random_points vector
-            let point = Point::random(dimension, lower_bound, upper_bound);
-            random_points.push(point);
-        }
+    /// Replaces the population with `population` and erases previous evaluations
+    pub fn set_population(&mut self, population: Vec<Point>) {
+        assert_eq!(
+            population.len() as u64,
+            self.population_size,
+            "population size does not match hypercube population size"
+        );
+        assert!(
+            population.iter().all(|p| p.dim() == self.dimension),
+            "population dimension does not match hypercube dimension"
+        );
+
+        self.population = population;
 
-        random_points
+        // clear previous evaluations
+        self.values.clear();
+        self.ordered_values.clear();
+    }
+
+    /// Replaces the population with the points in the file at `path` and erases previous
+    /// evaluations. The number of points in the file becomes the population size. Files with a
+    /// `.json` extension hold an array of coordinate arrays, any other file is read as CSV with one
+    /// point per line and an optional header line. Points must have the dimension of the hypercube
+    /// and lie inside its current bounds.
+    pub fn population_from_file(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let population = read_population(path, &self.current_bounds)?;
+
+        self.population_size = population.len() as u64;
+        self.set_population(population);
+
+        Ok(())
+    }
+
+    pub fn get_population(&self) -> &[Point] {
+        &self.population
     }
 
     pub fn has_shrunk(&self) -> bool {
@@ -310,6 +690,88 @@ impl Hypercube {
     }
 }
 
+/// Consistency checks run after every update of the hypercube with the `debug-invariants`
+/// feature. They panic with the name of the operation that broke an invariant.
+#[cfg(feature = "debug-invariants")]
+impl Hypercube {
+    /// Checks that the current bounds lie within the initial bounds, that the diagonal and center
+    /// match the current bounds, and that every evaluation is ordered
+    fn check_invariants(&self, operation: &str) {
+        let tolerance = 1e-9 * self.init_bounds.get_length();
+
+        let within_init = self
+            .current_bounds
+            .get_lower()
+            .iter()
+            .zip(self.init_bounds.get_lower().iter())
+            .all(|(current, init)| *current >= init - tolerance)
+            && self
+                .current_bounds
+                .get_upper()
+                .iter()
+                .zip(self.init_bounds.get_upper().iter())
+                .all(|(current, init)| *current <= init + tolerance);
+        assert!(
+            within_init,
+            "{}: current bounds {:?} left initial bounds {:?}",
+            operation, self.current_bounds, self.init_bounds
+        );
+
+        let diagonal = self.current_bounds.get_diagonal();
+        assert!(
+            (&self.diagonal - &diagonal)
+                .iter()
+                .all(|d| d.abs() <= tolerance),
+            "{}: diagonal {:?} does not match current bounds diagonal {:?}",
+            operation,
+            self.diagonal,
+            diagonal
+        );
+
+        let center = self.current_bounds.compute_center();
+        assert!(
+            (&self.center - &center)
+                .iter()
+                .all(|d| d.abs() <= tolerance),
+            "{}: center {:?} does not match current bounds center {:?}",
+            operation,
+            self.center,
+            center
+        );
+
+        assert_eq!(
+            self.ordered_values.len(),
+            self.values.len(),
+            "{}: {} ordered evaluations but {} evaluations",
+            operation,
+            self.ordered_values.len(),
+            self.values.len()
+        );
+    }
+
+    /// Checks that every population point lies within the current and initial bounds. Populations
+    /// replaced with `set_population`, e.g. repaired points, are allowed to lie outside them.
+    fn check_population_invariant(&self, operation: &str) {
+        for bounds in [&self.current_bounds, &self.init_bounds] {
+            if let Some(point) = self.population.iter().find(|p| !bounds.contains(p)) {
+                panic!(
+                    "{}: population point {:?} left bounds {:?}",
+                    operation, point, bounds
+                );
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "debug-invariants"))]
+impl Hypercube {
+    #[inline(always)]
+    fn check_invariants(&self, _operation: &str) {}
+
+    #[inline(always)]
+    fn check_population_invariant(&self, _operation: &str) {}
+}
+
 impl PartialEq for Hypercube {
     fn eq(&self, other: &Self) -> bool {
         let bool_vec = vec![
@@ -319,7 +781,7 @@ impl PartialEq for Hypercube {
             self.diagonal == other.diagonal,
             self.center == other.center,
             self.population_size == other.population_size,
-            self.population == other.population
+            self.population == other.population,
         ];
 
         bool_vec.into_iter().fold(true, |acc, x| acc & x)
@@ -347,8 +809,7 @@ impl fmt::Display for Hypercube {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::objective_functions::rastrigin;
-    use ordered_float::NotNan;
+    use crate::objective_functions::{rastrigin, summation};
 
     #[test]
     fn new_hypercube_1() {
@@ -411,10 +872,197 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
+    fn expand_1() {
+        let mut test_hypercube = Hypercube::new(2, 0.0, 120.0);
+        test_hypercube.shrink(0.25);
+        test_hypercube.displace_to(&point![20.0; 2]);
+
+        // cube spans [5, 35] before expanding, which would reach below the initial bounds
+        test_hypercube.expand(2.0);
+
+        assert_eq!(
+            test_hypercube.current_bounds,
+            HypercubeBounds::new(2, 0.0, 60.0)
+        );
+        assert_eq!(test_hypercube.center, point![30.0; 2]);
+        assert_eq!(test_hypercube.diagonal, point![60.0; 2]);
+    }
+
+    #[test]
+    fn expand_past_initial_size() {
+        let mut test_hypercube = Hypercube::new(2, 0.0, 120.0);
+        test_hypercube.shrink(0.5);
+
+        test_hypercube.expand(10.0);
+
+        assert!(!test_hypercube.has_shrunk());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn evaluate_in_thread_pool() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+
+        let mut test_hypercube = Hypercube::new(3, -5.0, 5.0);
+        test_hypercube.set_thread_pool(Some(Arc::new(pool)));
+
+        // every evaluation should happen on one of the pool's worker threads
+        test_hypercube.evaluate(|p: &Point| {
+            assert_eq!(rayon::current_num_threads(), 2);
+            assert!(rayon::current_thread_index().is_some());
+            rastrigin(p)
+        });
+
+        assert_eq!(
+            test_hypercube.values.len() as u64,
+            test_hypercube.population_size
+        );
+    }
+
+    /// Backend that fails for every point with a positive first coordinate
+    struct PositiveFailingBackend;
+
+    impl EvaluationBackend for PositiveFailingBackend {
+        type Error = &'static str;
+
+        fn evaluate_batch(&self, points: &[Point]) -> Vec<Result<f64, Self::Error>> {
+            points
+                .iter()
+                .map(|p| {
+                    if *p.get(0).unwrap() > 0.0 {
+                        Err("positive coordinate")
+                    } else {
+                        Ok(rastrigin(p))
+                    }
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn evaluate_with_failing_backend() {
+        let mut test_hypercube = Hypercube::new(3, -5.0, 5.0);
+        test_hypercube.evaluate_with(&PositiveFailingBackend);
+
+        // only the successfully evaluated points are stored
+        assert!(test_hypercube.values.len() < test_hypercube.population.len());
+        assert!(test_hypercube
+            .values
+            .iter()
+            .all(|v| *test_hypercube.population[v.index].get(0).unwrap() <= 0.0));
+    }
+
+    #[test]
+    fn best_value_refers_to_population() {
+        let mut hypercube = Hypercube::with_population_size(2, -5.0, 5.0, 3);
+        hypercube.set_population(vec![point![1.0; 2], point![3.0; 2], point![2.0; 2]]);
+        hypercube.evaluate(summation);
+
+        let (best_point, best_image) = hypercube.peek_best().unwrap();
+
+        // the best point is borrowed from the population instead of copied
+        assert!(std::ptr::eq(best_point, &hypercube.get_population()[1]));
+        assert_eq!(best_image, 6.0);
+
+        assert_eq!(hypercube.pop_best_value().unwrap().get_eval(), 6.0);
+        assert_eq!(
+            hypercube.pop_best_value().unwrap().get_point(),
+            &point![2.0; 2]
+        );
+    }
+
+    #[test]
+    fn seeded_populations_match() {
+        let mut hypercube_a = Hypercube::new(4, -5.0, 5.0);
+        let mut hypercube_b = Hypercube::new(4, -5.0, 5.0);
+
+        hypercube_a.set_seed(7);
+        hypercube_b.set_seed(7);
+
+        for _ in 0..3 {
+            hypercube_a.randomize_pop();
+            hypercube_b.randomize_pop();
+
+            assert_eq!(hypercube_a.population, hypercube_b.population);
+        }
+
+        // a different seed should give a different population
+        hypercube_b.set_seed(8);
+        hypercube_a.randomize_pop();
+        hypercube_b.randomize_pop();
+
+        assert_ne!(hypercube_a.population, hypercube_b.population);
+    }
+
+    #[test]
+    fn set_population_clears_values() {
+        let mut hypercube = Hypercube::with_population_size(2, -5.0, 5.0, 3);
+        hypercube.evaluate(summation);
+
+        let population = vec![point![1.0; 2], point![2.0; 2], point![3.0; 2]];
+        hypercube.set_population(population.clone());
+
+        assert_eq!(hypercube.get_population(), &population[..]);
+        assert!(hypercube.peek_best_value().is_none());
+
+        let images = hypercube.evaluate_images_with(&LocalBackend::new(summation));
+
+        assert_eq!(images, vec![Some(2.0), Some(4.0), Some(6.0)]);
+        assert_eq!(hypercube.peek_best_value().unwrap().get_eval(), 6.0);
+    }
+
+    #[test]
+    fn evaluate_nan_images() {
+        let mut hypercube = Hypercube::with_population_size(1, -5.0, 5.0, 3);
+        hypercube.set_population(vec![point![1.0], point![-2.0], point![3.0]]);
+
+        let images = hypercube
+            .evaluate_images_with(&LocalBackend::new(|p: &Point| p.get(0).unwrap().sqrt()));
+
+        assert_eq!(images, vec![Some(1.0), None, Some(3.0f64.sqrt())]);
+        assert_eq!(hypercube.values.len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_population_wrong_size() {
+        let mut hypercube = Hypercube::with_population_size(2, -5.0, 5.0, 3);
+        hypercube.set_population(vec![point![1.0; 2]]);
+    }
+
+    #[test]
     fn leakage_1() {
         // check whether the hypercube points stay within the hypercube bounds at all times
-        todo!()
+        let mut hypercube = Hypercube::with_population_size(3, -5.0, 5.0, 200);
+        hypercube.set_seed(7);
+
+        for i in 0..200 {
+            hypercube.shrink(0.9);
+
+            // push the hypercube against alternating corners of the initial bounds
+            let corner = if i % 2 == 0 { 5.0 } else { -5.0 };
+            hypercube.displace_to(&point![corner, -corner, 0.1 * i as f64]);
+            hypercube.randomize_pop();
+
+            for point in hypercube.get_population() {
+                assert!(hypercube.current_bounds.contains(point));
+                assert!(hypercube.init_bounds.contains(point));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "debug-invariants")]
+    #[should_panic(expected = "shrink: center")]
+    fn broken_invariant_panics() {
+        let mut hypercube = Hypercube::with_population_size(2, -5.0, 5.0, 10);
+
+        // move the center behind the back of the bounds
+        hypercube.center = point![4.0; 2];
+        hypercube.shrink(0.5);
     }
 
     #[test]
@@ -429,7 +1077,7 @@ mod tests {
 
         loop {
             let best_value = hut.pop_best_value();
-            if best_value == None {
+            if best_value.is_none() {
                 break;
             }
 
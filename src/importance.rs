@@ -0,0 +1,120 @@
+use crate::point::Point;
+
+/// Weight given to the newest displacement when updating the variance estimates
+const DECAY: f64 = 0.2;
+
+/// Smallest fraction of the hypercube side that a dimension is sampled over, so unimportant
+/// dimensions are never frozen completely
+const MIN_SAMPLING_SCALE: f64 = 0.25;
+
+/// Learns which dimensions matter from how much each coordinate of successive best points varies.
+/// Dimensions along which the best point keeps moving are considered important.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DimensionImportance {
+    /// exponential moving average of the squared displacement of the best point along each
+    /// dimension, relative to the hypercube side length
+    variances: Vec<f64>,
+
+    /// number of displacements recorded
+    updates: u32,
+}
+
+impl DimensionImportance {
+    pub fn new(dimension: u32) -> Self {
+        assert_ne!(dimension, 0, "dimension cannot be zero");
+
+        Self {
+            variances: vec![0.0; dimension as usize],
+            updates: 0,
+        }
+    }
+
+    /// Records the move of the best point from `previous` to `current` within a hypercube with
+    /// the given `side_length`
+    pub fn record(&mut self, previous: &Point, current: &Point, side_length: f64) {
+        assert_eq!(
+            previous.dim() as usize,
+            self.variances.len(),
+            "point dimension does not match importance dimension"
+        );
+        assert!(side_length > 0.0, "side length must be positive");
+
+        let displacement = current - previous;
+
+        for (variance, delta) in self.variances.iter_mut().zip(displacement.iter()) {
+            let relative_sqr_delta = (delta / side_length).powi(2);
+
+            *variance = if self.updates == 0 {
+                relative_sqr_delta
+            } else {
+                (1.0 - DECAY) * *variance + DECAY * relative_sqr_delta
+            };
+        }
+
+        self.updates += 1;
+    }
+
+    /// Importance weight of every dimension. The weights sum to one and are uniform until the best
+    /// point has moved.
+    pub fn weights(&self) -> Vec<f64> {
+        let total: f64 = self.variances.iter().sum();
+        let dimension = self.variances.len() as f64;
+
+        if total <= 0.0 {
+            return vec![1.0 / dimension; self.variances.len()];
+        }
+
+        self.variances.iter().map(|v| v / total).collect()
+    }
+
+    /// Fraction of the hypercube side to sample along each dimension. The most important dimension
+    /// is sampled over the whole side, less important ones over proportionally less of it.
+    pub fn sampling_scales(&self) -> Point {
+        let weights = self.weights();
+        let max_weight = weights.iter().copied().fold(0.0, f64::max);
+
+        Point::from_vec(
+            weights
+                .iter()
+                .map(|w| (w / max_weight).sqrt().max(MIN_SAMPLING_SCALE))
+                .collect(),
+        )
+    }
+
+    pub fn get_updates(&self) -> u32 {
+        self.updates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point;
+
+    #[test]
+    fn uniform_before_any_record() {
+        let importance = DimensionImportance::new(4);
+
+        assert_eq!(importance.weights(), vec![0.25; 4]);
+        assert_eq!(importance.sampling_scales(), point![1.0; 4]);
+    }
+
+    #[test]
+    fn moving_dimension_is_most_important() {
+        let mut importance = DimensionImportance::new(3);
+
+        importance.record(&point![0.0, 0.0, 0.0], &point![2.0, 0.0, 1.0], 4.0);
+        importance.record(&point![2.0, 0.0, 1.0], &point![0.0, 0.0, 1.0], 4.0);
+
+        let weights = importance.weights();
+
+        assert!(weights[0] > weights[2]);
+        assert_eq!(weights[1], 0.0);
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        let scales = importance.sampling_scales();
+
+        assert_eq!(*scales.get(0).unwrap(), 1.0);
+        assert_eq!(*scales.get(1).unwrap(), MIN_SAMPLING_SCALE);
+    }
+}
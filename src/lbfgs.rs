@@ -0,0 +1,241 @@
+use std::collections::VecDeque;
+
+use crate::bounds::HypercubeBounds;
+use crate::evaluation::PointEval;
+use crate::point::Point;
+
+/// Number of correction pairs kept by L-BFGS
+const MEMORY: usize = 10;
+
+/// Maximum number of step halvings in a single line search
+const MAX_BACKTRACKS: u32 = 30;
+
+/// Sufficient increase constant of the Armijo line search
+const ARMIJO: f64 = 1e-4;
+
+/// An objective function that can also compute its gradient. Implemented for `(value, gradient)`
+/// tuples of functions, e.g. `(neg_sphere, neg_sphere_gradient)`.
+pub trait DifferentiableObjective {
+    /// Image of the objective function at `x`
+    fn value(&self, x: &Point) -> f64;
+
+    /// Gradient of the objective function at `x`
+    fn gradient(&self, x: &Point) -> Point;
+}
+
+impl<F, G> DifferentiableObjective for (F, G)
+where
+    F: Fn(&Point) -> f64,
+    G: Fn(&Point) -> Point,
+{
+    fn value(&self, x: &Point) -> f64 {
+        (self.0)(x)
+    }
+
+    fn gradient(&self, x: &Point) -> Point {
+        (self.1)(x)
+    }
+}
+
+/// Refines `start` by maximizing `objective` with projected L-BFGS, keeping every iterate inside
+/// `bounds`. Every value and gradient pair counts as one evaluation and at most `max_evals` are
+/// used. Returns the best point found and the number of evaluations used.
+pub(crate) fn polish<D>(
+    objective: &D,
+    start: &PointEval,
+    bounds: &HypercubeBounds,
+    max_evals: u32,
+) -> (PointEval, u32)
+where
+    D: DifferentiableObjective + ?Sized,
+{
+    // L-BFGS minimizes, so work on the negated objective
+    let mut x: Vec<f64> = start.get_point().iter().copied().collect();
+    let mut f = -start.get_eval();
+    let mut g = negate(&objective.gradient(start.get_point()));
+    let mut evals = 1;
+
+    let mut corrections: VecDeque<(Vec<f64>, Vec<f64>)> = VecDeque::with_capacity(MEMORY);
+
+    'outer: while evals < max_evals {
+        let mut direction = two_loop_recursion(&g, &corrections);
+
+        // fall back to steepest descent if the quasi-Newton direction isn't a descent direction
+        if dot(&direction, &g) >= 0.0 {
+            corrections.clear();
+            direction = g.iter().map(|g| -g).collect();
+        }
+
+        // <----- projected backtracking line search ----->
+
+        let mut step = 1.0;
+
+        let (x_new, f_new) = loop {
+            if evals >= max_evals {
+                break 'outer;
+            }
+
+            let candidate = Point::from_vec(
+                x.iter()
+                    .zip(&direction)
+                    .map(|(x, d)| x + step * d)
+                    .collect(),
+            )
+            .clamp(bounds);
+            let f_candidate = -objective.value(&candidate);
+            evals += 1;
+
+            let x_candidate: Vec<f64> = candidate.iter().copied().collect();
+            let displacement: Vec<f64> = x_candidate.iter().zip(&x).map(|(a, b)| a - b).collect();
+
+            if f_candidate <= f + ARMIJO * dot(&g, &displacement) {
+                break (x_candidate, f_candidate);
+            }
+
+            step *= 0.5;
+
+            if step < 0.5f64.powi(MAX_BACKTRACKS as i32) {
+                break 'outer;
+            }
+        };
+
+        let g_new = negate(&objective.gradient(&Point::from_vec(x_new.clone())));
+
+        let s: Vec<f64> = x_new.iter().zip(&x).map(|(a, b)| a - b).collect();
+        let y: Vec<f64> = g_new.iter().zip(&g).map(|(a, b)| a - b).collect();
+
+        let step_len = dot(&s, &s).sqrt();
+        let improvement = f - f_new;
+
+        x = x_new;
+        f = f_new;
+        g = g_new;
+
+        // only keep pairs that preserve a positive definite inverse Hessian estimate
+        if dot(&s, &y) > 1e-12 {
+            if corrections.len() == MEMORY {
+                corrections.pop_front();
+            }
+            corrections.push_back((s, y));
+        }
+
+        if step_len < 1e-12 || improvement.abs() <= 1e-15 * f.abs().max(1.0) {
+            break;
+        }
+    }
+
+    let polished = PointEval::from_image(Point::from_vec(x), -f);
+
+    if polished > *start {
+        (polished, evals)
+    } else {
+        (start.clone(), evals)
+    }
+}
+
+/// Applies the L-BFGS inverse Hessian estimate to the gradient and returns the search direction
+fn two_loop_recursion(g: &[f64], corrections: &VecDeque<(Vec<f64>, Vec<f64>)>) -> Vec<f64> {
+    let mut q = g.to_vec();
+    let mut alphas = Vec::with_capacity(corrections.len());
+
+    for (s, y) in corrections.iter().rev() {
+        let rho = 1.0 / dot(y, s);
+        let alpha = rho * dot(s, &q);
+
+        for (q, y) in q.iter_mut().zip(y) {
+            *q -= alpha * y;
+        }
+
+        alphas.push((rho, alpha));
+    }
+
+    // scale by the most recent curvature estimate
+    let gamma = match corrections.back() {
+        Some((s, y)) => dot(s, y) / dot(y, y),
+        None => 1.0,
+    };
+
+    let mut r: Vec<f64> = q.iter().map(|q| gamma * q).collect();
+
+    for ((s, y), (rho, alpha)) in corrections.iter().zip(alphas.into_iter().rev()) {
+        let beta = rho * dot(y, &r);
+
+        for (r, s) in r.iter_mut().zip(s) {
+            *r += (alpha - beta) * s;
+        }
+    }
+
+    r.iter().map(|r| -r).collect()
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn negate(point: &Point) -> Vec<f64> {
+    point.iter().map(|x| -x).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::{
+        neg_rosenbrock, neg_rosenbrock_gradient, neg_sphere, neg_sphere_gradient,
+    };
+    use crate::point;
+
+    #[test]
+    fn polish_sphere() {
+        let bounds = HypercubeBounds::new(3, -5.0, 5.0);
+        let start = PointEval::with_eval(point![1.0, -2.0, 0.5], neg_sphere);
+
+        let (polished, evals) = polish(&(neg_sphere, neg_sphere_gradient), &start, &bounds, 100);
+
+        assert!(polished.get_eval() > -1e-12);
+        assert!(evals <= 100);
+    }
+
+    #[test]
+    fn polish_rosenbrock() {
+        let bounds = HypercubeBounds::new(4, -5.0, 10.0);
+        let start = PointEval::with_eval(point![0.5; 4], neg_rosenbrock);
+
+        let (polished, _) = polish(
+            &(neg_rosenbrock, neg_rosenbrock_gradient),
+            &start,
+            &bounds,
+            1000,
+        );
+
+        for coord in polished.get_point().iter() {
+            assert!((coord - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn polish_respects_bounds() {
+        // the optimum of the sphere function lies outside the bounds
+        let bounds = HypercubeBounds::new(2, 1.0, 5.0);
+        let start = PointEval::with_eval(point![4.0, 3.0], neg_sphere);
+
+        let (polished, _) = polish(&(neg_sphere, neg_sphere_gradient), &start, &bounds, 100);
+
+        assert_eq!(polished.get_point(), &point![1.0, 1.0]);
+    }
+
+    #[test]
+    fn polish_respects_eval_budget() {
+        let bounds = HypercubeBounds::new(4, -5.0, 10.0);
+        let start = PointEval::with_eval(point![0.5; 4], neg_rosenbrock);
+
+        let (polished, evals) = polish(
+            &(neg_rosenbrock, neg_rosenbrock_gradient),
+            &start,
+            &bounds,
+            5,
+        );
+
+        assert!(evals <= 5);
+        assert!(polished >= start);
+    }
+}
@@ -1,7 +1,44 @@
+#[cfg(feature = "arrow-export")]
+pub mod arrow_export;
+pub mod backend;
+pub mod bayesian;
+pub mod benchmark;
 pub mod bounds;
+pub mod checkpoint;
+pub mod cmaes;
+pub mod eval_log;
 pub mod evaluation;
+pub mod events;
+pub mod experiment;
+#[cfg(feature = "gpu-backend")]
+pub mod gpu_backend;
+pub mod grid_search;
+pub mod handle;
+#[cfg(feature = "http-backend")]
+pub mod http_backend;
 pub mod hypercube;
+pub mod importance;
+pub mod lbfgs;
+mod linalg;
+pub mod manifest;
+pub mod nelder_mead;
+pub mod normalization;
 pub mod objective_functions;
 pub mod optimizer;
+pub mod particle_swarm;
+#[cfg(feature = "plotting")]
+mod plotting;
 pub mod point;
+mod population_file;
+#[cfg(feature = "progress")]
+pub mod progress;
+pub mod random_search;
 pub mod result;
+pub mod sampler;
+pub mod simulated_annealing;
+pub mod snapshot;
+mod statistics;
+pub mod stopping;
+pub mod successive_halving;
+mod text_format;
+pub mod transform;
@@ -0,0 +1,264 @@
+// Small dense linear algebra helpers used by the optimizers. Matrices are stored row-major in
+// flat vectors.
+
+/// Computes the eigenvalues and eigenvectors of the symmetric `n` x `n` `matrix` using cyclic
+/// Jacobi rotations. Returns the eigenvalues and a matrix whose columns are the corresponding
+/// unit eigenvectors.
+pub(crate) fn symmetric_eigen(matrix: &[f64], n: usize) -> (Vec<f64>, Vec<f64>) {
+    assert_eq!(matrix.len(), n * n, "matrix is not {} x {}", n, n);
+
+    let mut a = matrix.to_vec();
+    let mut v = identity(n);
+
+    let scale: f64 = a.iter().map(|x| x * x).sum::<f64>().max(f64::MIN_POSITIVE);
+
+    for _sweep in 0..100 {
+        // sum of squares of the off-diagonal elements
+        let off_diagonal: f64 = (0..n)
+            .flat_map(|p| (p + 1..n).map(move |q| (p, q)))
+            .map(|(p, q)| a[p * n + q].powi(2))
+            .sum();
+
+        if off_diagonal <= 1e-30 * scale {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let a_pq = a[p * n + q];
+
+                if a_pq == 0.0 {
+                    continue;
+                }
+
+                // rotation angle that zeroes a_pq
+                let theta = (a[q * n + q] - a[p * n + p]) / (2.0 * a_pq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                // A <- A J
+                for k in 0..n {
+                    let a_kp = a[k * n + p];
+                    let a_kq = a[k * n + q];
+                    a[k * n + p] = c * a_kp - s * a_kq;
+                    a[k * n + q] = s * a_kp + c * a_kq;
+                }
+
+                // A <- J^T A
+                for k in 0..n {
+                    let a_pk = a[p * n + k];
+                    let a_qk = a[q * n + k];
+                    a[p * n + k] = c * a_pk - s * a_qk;
+                    a[q * n + k] = s * a_pk + c * a_qk;
+                }
+
+                // V <- V J
+                for k in 0..n {
+                    let v_kp = v[k * n + p];
+                    let v_kq = v[k * n + q];
+                    v[k * n + p] = c * v_kp - s * v_kq;
+                    v[k * n + q] = s * v_kp + c * v_kq;
+                }
+            }
+        }
+    }
+
+    let eigenvalues = (0..n).map(|i| a[i * n + i]).collect();
+
+    (eigenvalues, v)
+}
+
+/// Returns the `n` x `n` identity matrix
+pub(crate) fn identity(n: usize) -> Vec<f64> {
+    let mut matrix = vec![0.0; n * n];
+
+    for i in 0..n {
+        matrix[i * n + i] = 1.0;
+    }
+
+    matrix
+}
+
+/// Multiplies the `n` x `n` `matrix` with `vector`
+pub(crate) fn mat_vec(matrix: &[f64], vector: &[f64]) -> Vec<f64> {
+    let n = vector.len();
+
+    (0..n)
+        .map(|i| (0..n).map(|j| matrix[i * n + j] * vector[j]).sum())
+        .collect()
+}
+
+/// Inverts the `n` x `n` `matrix` by Gauss-Jordan elimination with partial pivoting. Returns
+/// `None` if the matrix is singular.
+pub(crate) fn invert(matrix: &[f64], n: usize) -> Option<Vec<f64>> {
+    assert_eq!(matrix.len(), n * n, "matrix is not {} x {}", n, n);
+
+    let mut a = matrix.to_vec();
+    let mut inverse = identity(n);
+
+    for col in 0..n {
+        // swap the row with the largest pivot into place
+        let pivot_row = (col..n)
+            .max_by(|&i, &j| a[i * n + col].abs().total_cmp(&a[j * n + col].abs()))
+            .unwrap();
+
+        if a[pivot_row * n + col].abs() < 1e-12 {
+            return None;
+        }
+
+        for k in 0..n {
+            a.swap(col * n + k, pivot_row * n + k);
+            inverse.swap(col * n + k, pivot_row * n + k);
+        }
+
+        let pivot = a[col * n + col];
+
+        for k in 0..n {
+            a[col * n + k] /= pivot;
+            inverse[col * n + k] /= pivot;
+        }
+
+        for row in (0..n).filter(|&row| row != col) {
+            let factor = a[row * n + col];
+
+            for k in 0..n {
+                a[row * n + k] -= factor * a[col * n + k];
+                inverse[row * n + k] -= factor * inverse[col * n + k];
+            }
+        }
+    }
+
+    Some(inverse)
+}
+
+/// Computes the Cholesky factor `L` of the symmetric positive definite `n` x `n` `matrix` such
+/// that `matrix = L L^T`. Returns `None` if the matrix is not positive definite.
+pub(crate) fn cholesky(matrix: &[f64], n: usize) -> Option<Vec<f64>> {
+    assert_eq!(matrix.len(), n * n, "matrix is not {} x {}", n, n);
+
+    let mut l = vec![0.0; n * n];
+
+    for i in 0..n {
+        for j in 0..=i {
+            let dot: f64 = (0..j).map(|k| l[i * n + k] * l[j * n + k]).sum();
+
+            if i == j {
+                let diagonal = matrix[i * n + i] - dot;
+
+                if diagonal <= 0.0 || diagonal.is_nan() {
+                    return None;
+                }
+
+                l[i * n + i] = diagonal.sqrt();
+            } else {
+                l[i * n + j] = (matrix[i * n + j] - dot) / l[j * n + j];
+            }
+        }
+    }
+
+    Some(l)
+}
+
+/// Solves `L x = b` for the lower triangular `L` by forward substitution
+pub(crate) fn forward_substitution(l: &[f64], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut x = vec![0.0; n];
+
+    for i in 0..n {
+        let dot: f64 = (0..i).map(|k| l[i * n + k] * x[k]).sum();
+        x[i] = (b[i] - dot) / l[i * n + i];
+    }
+
+    x
+}
+
+/// Solves `L^T x = b` for the lower triangular `L` by backward substitution
+pub(crate) fn backward_substitution(l: &[f64], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut x = vec![0.0; n];
+
+    for i in (0..n).rev() {
+        let dot: f64 = (i + 1..n).map(|k| l[k * n + i] * x[k]).sum();
+        x[i] = (b[i] - dot) / l[i * n + i];
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symmetric_eigen_reconstructs_matrix() {
+        let n = 3;
+        let matrix = vec![4.0, 1.0, 2.0, 1.0, 3.0, 0.5, 2.0, 0.5, 5.0];
+
+        let (values, vectors) = symmetric_eigen(&matrix, n);
+
+        // A = V diag(values) V^T
+        for i in 0..n {
+            for j in 0..n {
+                let reconstructed: f64 = (0..n)
+                    .map(|k| vectors[i * n + k] * values[k] * vectors[j * n + k])
+                    .sum();
+
+                assert!((reconstructed - matrix[i * n + j]).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_eigen_diagonal() {
+        let matrix = vec![2.0, 0.0, 0.0, 7.0];
+
+        let (values, vectors) = symmetric_eigen(&matrix, 2);
+
+        assert_eq!(values, vec![2.0, 7.0]);
+        assert_eq!(vectors, identity(2));
+    }
+
+    #[test]
+    fn cholesky_solves_system() {
+        let matrix = vec![4.0, 2.0, 0.6, 2.0, 5.0, 1.0, 0.6, 1.0, 3.0];
+        let b = vec![1.0, 2.0, 3.0];
+
+        let l = cholesky(&matrix, 3).unwrap();
+        let x = backward_substitution(&l, &forward_substitution(&l, &b));
+
+        for (ax, b) in mat_vec(&matrix, &x).iter().zip(&b) {
+            assert!((ax - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn invert_reconstructs_identity() {
+        let matrix = vec![0.0, 2.0, 1.0, 1.0, 0.0, 3.0, 4.0, 1.0, 0.0];
+
+        let inverse = invert(&matrix, 3).unwrap();
+
+        for i in 0..3 {
+            for j in 0..3 {
+                let product: f64 = (0..3).map(|k| matrix[i * 3 + k] * inverse[k * 3 + j]).sum();
+                let expected = if i == j { 1.0 } else { 0.0 };
+
+                assert!((product - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn invert_singular() {
+        let matrix = vec![1.0, 2.0, 2.0, 4.0];
+
+        assert!(invert(&matrix, 2).is_none());
+    }
+
+    #[test]
+    fn cholesky_not_positive_definite() {
+        let matrix = vec![1.0, 2.0, 2.0, 1.0];
+
+        assert!(cholesky(&matrix, 2).is_none());
+    }
+}
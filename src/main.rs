@@ -1,18 +1,68 @@
-use hypercube_optimizer::objective_functions::neg_rastrigin;
+use std::env;
+use std::process;
+
+use hypercube_optimizer::objective_functions::{
+    neg_ackley, neg_griewank, neg_rastrigin, neg_rosenbrock, neg_sphere,
+};
 use hypercube_optimizer::optimizer::HypercubeOptimizer;
+#[cfg(feature = "progress")]
+use hypercube_optimizer::optimizer::Verbosity;
 use hypercube_optimizer::point;
 use hypercube_optimizer::point::Point;
+#[cfg(feature = "progress")]
+use hypercube_optimizer::progress::ProgressReporter;
 
 use hypercube_optimizer::result::HypercubeOptimizerResult;
 use simple_logger::SimpleLogger;
 
+const USAGE: &str = "usage: hypercube-optimization [--dimension N] [--bounds LOWER,UPPER] \
+                     [--objective rastrigin|sphere|rosenbrock|ackley|griewank] [--max-loop N] \
+                     [--max-eval N] [--seed N] [--progress]";
+
 fn main() {
-    SimpleLogger::new().with_level(log::LevelFilter::Info).init().unwrap();
+    let mut dimension: u32 = 8;
+    let mut lower_bound = 0.0;
+    let mut upper_bound = 120.0;
+    let mut objective: fn(&Point) -> f64 = neg_rastrigin;
+    let mut max_loop: u32 = 2000;
+    let mut max_eval: u32 = 5000;
+    let mut seed: Option<u64> = None;
+    #[cfg(feature = "progress")]
+    let mut progress = false;
+
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| exit_with_usage(&arg));
+
+        match arg.as_str() {
+            "--dimension" => dimension = parse_or_exit(&arg, &value()),
+            "--bounds" => (lower_bound, upper_bound) = parse_bounds_or_exit(&arg, &value()),
+            "--objective" => objective = objective_or_exit(&arg, &value()),
+            "--max-loop" => max_loop = parse_or_exit(&arg, &value()),
+            "--max-eval" => max_eval = parse_or_exit(&arg, &value()),
+            "--seed" => seed = Some(parse_or_exit(&arg, &value())),
+            #[cfg(feature = "progress")]
+            "--progress" => progress = true,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => exit_with_usage(&arg),
+        }
+    }
+
+    if dimension == 0 {
+        exit_with_usage("--dimension");
+    }
 
-    let dimension = 8;
-    let initial_point = point![60.0; dimension];
-    let lower_bound = 0.0;
-    let upper_bound = 120.0;
+    SimpleLogger::new()
+        .with_level(log::LevelFilter::Info)
+        .init()
+        .unwrap();
+
+    // start at the center of the search space
+    let initial_point = point![(lower_bound + upper_bound) / 2.0; dimension];
 
     let mut optimizer = HypercubeOptimizer::new(
         initial_point,
@@ -20,11 +70,71 @@ fn main() {
         upper_bound,
         0.01,
         0.1,
-        2000,
-        5000,
+        max_loop,
+        max_eval,
         120,
     );
 
-    let result: HypercubeOptimizerResult = optimizer.maximize(neg_rastrigin);
+    if let Some(seed) = seed {
+        optimizer.set_seed(seed);
+    }
+
+    // per-loop log messages would scroll the progress bar away
+    #[cfg(feature = "progress")]
+    if progress {
+        optimizer.set_verbosity(Verbosity::Summary);
+        optimizer.set_observer(ProgressReporter::new(max_eval));
+    }
+
+    // Ctrl-C stops the optimization after the current loop instead of killing the process, so the
+    // best value found so far still gets reported
+    #[cfg(feature = "cli")]
+    {
+        let cancel = optimizer.cancel_handle();
+        ctrlc::set_handler(move || {
+            log::warn!("interrupted, stopping after the current loop");
+            cancel.cancel();
+        })
+        .expect("failed to set Ctrl-C handler");
+    }
+
+    let result: HypercubeOptimizerResult = optimizer.maximize(objective);
     log::info!("final result: {:#?}", result);
 }
+
+/// Parses bounds given as `LOWER,UPPER`
+fn parse_bounds_or_exit(arg: &str, value: &str) -> (f64, f64) {
+    let (lower, upper) = value
+        .split_once(',')
+        .unwrap_or_else(|| exit_with_usage(arg));
+    let bounds = (
+        parse_or_exit(arg, lower.trim()),
+        parse_or_exit(arg, upper.trim()),
+    );
+
+    if bounds.1 <= bounds.0 {
+        exit_with_usage(arg);
+    }
+
+    bounds
+}
+
+fn objective_or_exit(arg: &str, name: &str) -> fn(&Point) -> f64 {
+    match name {
+        "rastrigin" => neg_rastrigin,
+        "sphere" => neg_sphere,
+        "rosenbrock" => neg_rosenbrock,
+        "ackley" => neg_ackley,
+        "griewank" => neg_griewank,
+        _ => exit_with_usage(arg),
+    }
+}
+
+fn parse_or_exit<T: std::str::FromStr>(arg: &str, value: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with_usage(arg))
+}
+
+fn exit_with_usage(arg: &str) -> ! {
+    eprintln!("invalid argument: {}\n{}", arg, USAGE);
+    process::exit(2);
+}
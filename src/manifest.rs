@@ -0,0 +1,171 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::optimizer::HypercubeOptimizerConfig;
+use crate::point::Point;
+use crate::text_format::json_string;
+use crate::transform::InputTransform;
+
+/// Everything needed to reproduce an optimization run: the seed it started from, the problem
+/// bounds, population size and starting points, the full optimizer configuration, and the crate
+/// version. Attached to every result of `HypercubeOptimizer::maximize`, and turned back into an
+/// optimizer that repeats the run with `HypercubeOptimizer::from_manifest`.
+///
+/// Repair functions, observers, deadlines, checkpoints and initial population files are not part
+/// of the manifest and have to be set up again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunManifest {
+    /// version of this crate that performed the run
+    pub crate_version: String,
+
+    /// seed of the random number generator at the start of the run
+    pub seed: u64,
+
+    pub config: HypercubeOptimizerConfig,
+
+    pub init_point: Point,
+
+    pub initial_candidates: Vec<Point>,
+
+    pub lower_bound: f64,
+
+    pub upper_bound: f64,
+
+    /// number of points sampled in every loop before any restart grows the population, as set
+    /// with `HypercubeOptimizer::set_population_size` or given by the default formula
+    pub init_population_size: u64,
+
+    pub input_transform: Option<InputTransform>,
+
+    pub grid_steps: Option<Point>,
+
+    /// name of the objective function set with `HypercubeOptimizer::set_objective_name`
+    pub objective_name: Option<String>,
+
+    /// hash of the starting points and their images. A reproduced run evaluating a different
+    /// objective function almost certainly gets a different hash, which `from_manifest`
+    /// optimizers warn about.
+    pub objective_hash: Option<u64>,
+}
+
+impl RunManifest {
+    /// Formats the manifest as a JSON object. The configuration and the input transform are
+    /// written as their `Debug` representations.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\n");
+
+        writeln!(
+            json,
+            "  \"crate_version\": {},",
+            json_string(&self.crate_version)
+        )
+        .unwrap();
+        writeln!(json, "  \"seed\": {},", self.seed).unwrap();
+        writeln!(
+            json,
+            "  \"config\": {},",
+            json_string(&format!("{:?}", self.config))
+        )
+        .unwrap();
+        writeln!(json, "  \"init_point\": {},", json_point(&self.init_point)).unwrap();
+        writeln!(
+            json,
+            "  \"initial_candidates\": [{}],",
+            self.initial_candidates
+                .iter()
+                .map(json_point)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .unwrap();
+        writeln!(json, "  \"lower_bound\": {},", self.lower_bound).unwrap();
+        writeln!(json, "  \"upper_bound\": {},", self.upper_bound).unwrap();
+        writeln!(
+            json,
+            "  \"init_population_size\": {},",
+            self.init_population_size
+        )
+        .unwrap();
+        writeln!(
+            json,
+            "  \"input_transform\": {},",
+            self.input_transform
+                .as_ref()
+                .map_or("null".to_string(), |t| json_string(&format!("{:?}", t)))
+        )
+        .unwrap();
+        writeln!(
+            json,
+            "  \"grid_steps\": {},",
+            self.grid_steps
+                .as_ref()
+                .map_or("null".to_string(), json_point)
+        )
+        .unwrap();
+        writeln!(
+            json,
+            "  \"objective_name\": {},",
+            self.objective_name
+                .as_deref()
+                .map_or("null".to_string(), json_string)
+        )
+        .unwrap();
+        writeln!(
+            json,
+            "  \"objective_hash\": {}",
+            self.objective_hash
+                .map_or("null".to_string(), |h| format!("\"{:016x}\"", h))
+        )
+        .unwrap();
+
+        json.push_str("}\n");
+        json
+    }
+
+    /// Writes the manifest to `path` as JSON
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+}
+
+/// Formats a point as a JSON array of numbers
+fn json_point(point: &Point) -> String {
+    let coords: Vec<String> = point.iter().map(|c| format!("{:?}", c)).collect();
+    format!("[{}]", coords.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point;
+
+    #[test]
+    fn json_format() {
+        let manifest = RunManifest {
+            crate_version: "0.1.2".to_string(),
+            seed: 42,
+            config: HypercubeOptimizerConfig::default(),
+            init_point: point![1.0, -2.5],
+            initial_candidates: vec![point![0.0, 0.5]],
+            lower_bound: -5.0,
+            upper_bound: 5.0,
+            init_population_size: 40,
+            input_transform: None,
+            grid_steps: None,
+            objective_name: Some("neg \"sphere\"".to_string()),
+            objective_hash: Some(255),
+        };
+
+        let json = manifest.to_json();
+
+        assert!(json.starts_with("{\n  \"crate_version\": \"0.1.2\",\n  \"seed\": 42,\n"));
+        assert!(json.contains("\n  \"init_point\": [1.0, -2.5],\n"));
+        assert!(json.contains("\n  \"initial_candidates\": [[0.0, 0.5]],\n"));
+        assert!(json.contains("\n  \"init_population_size\": 40,\n"));
+        assert!(json.contains("\n  \"grid_steps\": null,\n"));
+        assert!(json.contains("\n  \"objective_name\": \"neg \\\"sphere\\\"\",\n"));
+        assert!(json.ends_with("\n  \"objective_hash\": \"00000000000000ff\"\n}\n"));
+    }
+}
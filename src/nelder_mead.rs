@@ -0,0 +1,364 @@
+use crate::backend::{EvaluationBackend, LocalBackend, Objective};
+use crate::bounds::HypercubeBounds;
+use crate::evaluation::PointEval;
+use crate::optimizer::Optimizer;
+use crate::point::Point;
+use crate::result::{HypercubeOptimizerResult, IterationRecord};
+use crate::stopping::{Budget, StoppingCriteria};
+
+/// Nelder–Mead downhill simplex optimizer. Moves a simplex of `n + 1` points through the search
+/// space by reflecting, expanding, and contracting its worst vertex, which makes it well suited to
+/// low-dimensional smooth problems. Every trial point is clamped to the search space bounds.
+///
+/// Failed evaluations (errors or NaN images) are treated as the worst possible value.
+pub struct NelderMead {
+    /// dimension of the optimization problem
+    dimension: u32,
+
+    /// vertex the initial simplex is built around
+    init_point: Point,
+
+    /// bounds of the search space
+    bounds: HypercubeBounds,
+
+    /// distance between the initial point and the other vertices of the initial simplex
+    init_step: f64,
+
+    /// conditions that terminate the optimization
+    criteria: StoppingCriteria,
+}
+
+/// Reflection, expansion, contraction, and shrink coefficients
+const ALPHA: f64 = 1.0;
+const GAMMA: f64 = 2.0;
+const RHO: f64 = 0.5;
+const SIGMA: f64 = 0.5;
+
+/// A simplex vertex and its image. Failed evaluations are stored as negative infinity.
+#[derive(Debug, Clone)]
+struct Vertex {
+    point: Point,
+    image: f64,
+}
+
+impl NelderMead {
+    /// Returns a new `NelderMead` optimizer
+    ///
+    /// # Arguments
+    ///
+    /// * `init_point` - a vertex of the initial simplex
+    /// * `lower_bound` - the lower bound of the search space
+    /// * `upper_bound` - the upper bound of the search space
+    /// * `criteria` - conditions that terminate the optimization. `tol_x` is compared against the
+    ///   largest distance between the best vertex and the others and `tol_f` against the spread of
+    ///   the vertex images.
+    ///
+    pub fn new(
+        init_point: Point,
+        lower_bound: f64,
+        upper_bound: f64,
+        criteria: StoppingCriteria,
+    ) -> Self {
+        assert!(
+            upper_bound > lower_bound,
+            "upper bound not strictly larger than lower bound"
+        );
+        assert!(
+            init_point.max_val().unwrap() <= upper_bound,
+            "init_point not inside upper bound"
+        );
+        assert!(
+            init_point.min_val().unwrap() >= lower_bound,
+            "init_point not inside lower bound"
+        );
+
+        let dimension = init_point.dim();
+
+        Self {
+            dimension,
+            bounds: HypercubeBounds::new(dimension, lower_bound, upper_bound),
+            init_point,
+            init_step: 0.05 * (upper_bound - lower_bound),
+            criteria,
+        }
+    }
+
+    /// Sets the distance between the initial point and the other vertices of the initial simplex.
+    /// Defaults to 5% of the search space side length.
+    pub fn set_initial_step(&mut self, init_step: f64) {
+        assert!(init_step > 0.0, "initial step must be positive");
+        self.init_step = init_step;
+    }
+
+    /// Maximizes the objective function evaluated by `backend`
+    pub fn maximize_with<B>(&mut self, backend: &B) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + ?Sized,
+    {
+        let n = self.dimension as usize;
+        let mut budget = Budget::start(&self.criteria);
+        let mut history: Vec<IterationRecord> = Vec::new();
+
+        // a single iteration needs at most n + 2 evaluations: reflection, expansion or
+        // contraction, and a shrink of n vertices
+        let max_iteration_evals = self.dimension + 2;
+
+        if let Some(exit_code) = budget.check(self.dimension + 1) {
+            return HypercubeOptimizerResult::new(exit_code, 0, 0, None, budget.elapsed());
+        }
+
+        let mut simplex = evaluate(backend, &mut budget, self.initial_simplex());
+        sort(&mut simplex);
+
+        if simplex[0].image == f64::NEG_INFINITY {
+            log::error!("every vertex of the initial simplex failed to evaluate");
+            return HypercubeOptimizerResult::new(1, 0, budget.fn_evals(), None, budget.elapsed());
+        }
+
+        let exit_code = loop {
+            if let Some(exit_code) = budget.check(max_iteration_evals) {
+                break exit_code;
+            }
+
+            // <----- convergence checks ----->
+
+            let best = &simplex[0];
+            let worst = &simplex[n];
+
+            let simplex_size = simplex[1..]
+                .iter()
+                .map(|v| (&v.point - &best.point).len())
+                .fold(0.0, f64::max);
+
+            if simplex_size < self.criteria.tol_x {
+                log::info!("optimization process terminated due to input convergence");
+                break 0;
+            }
+
+            if worst.image.is_finite() && best.image - worst.image <= self.criteria.tol_f {
+                log::info!("optimization process terminated due to image convergence");
+                break 0;
+            }
+
+            // <----- transform simplex ----->
+
+            let centroid = simplex[..n]
+                .iter()
+                .fold(Point::fill(0.0, self.dimension), |acc, v| &acc + &v.point)
+                .scale(1.0 / n as f64);
+
+            let reflected = self.trial(backend, &mut budget, &centroid, &simplex[n].point, -ALPHA);
+
+            if reflected.image > simplex[0].image {
+                let expanded =
+                    self.trial(backend, &mut budget, &centroid, &simplex[n].point, -GAMMA);
+
+                simplex[n] = if expanded.image > reflected.image {
+                    expanded
+                } else {
+                    reflected
+                };
+            } else if reflected.image > simplex[n - 1].image {
+                simplex[n] = reflected;
+            } else {
+                // contract towards the better of the reflected and worst vertex
+                let contracted = if reflected.image > simplex[n].image {
+                    let contracted =
+                        self.trial(backend, &mut budget, &centroid, &reflected.point, RHO);
+                    (contracted.image >= reflected.image).then_some(contracted)
+                } else {
+                    let contracted =
+                        self.trial(backend, &mut budget, &centroid, &simplex[n].point, RHO);
+                    (contracted.image > simplex[n].image).then_some(contracted)
+                };
+
+                match contracted {
+                    Some(contracted) => simplex[n] = contracted,
+                    None => self.shrink(backend, &mut budget, &mut simplex),
+                }
+            }
+
+            sort(&mut simplex);
+            budget.record_loop();
+
+            history.push(IterationRecord {
+                loop_index: budget.loops() - 1,
+                fn_evals: budget.fn_evals(),
+                best_f: simplex[0].image,
+                diagonal_len: simplex_size,
+            });
+        };
+
+        let best = PointEval::from_image(simplex[0].point.clone(), simplex[0].image);
+
+        HypercubeOptimizerResult::new(
+            exit_code,
+            budget.loops(),
+            budget.fn_evals(),
+            Some(&best),
+            budget.elapsed(),
+        )
+        .with_history(history)
+    }
+
+    /// Builds the initial simplex by stepping away from the initial point along every axis. Steps
+    /// that would leave the search space are taken in the opposite direction instead.
+    fn initial_simplex(&self) -> Vec<Point> {
+        let mut vertices = vec![self.init_point.clone()];
+
+        for axis in 0..self.dimension as usize {
+            let mut coords: Vec<f64> = self.init_point.iter().copied().collect();
+            let upper = *self.bounds.get_upper().get(axis).unwrap();
+
+            if coords[axis] + self.init_step <= upper {
+                coords[axis] += self.init_step;
+            } else {
+                coords[axis] -= self.init_step;
+            }
+
+            vertices.push(Point::from_vec(coords).clamp(&self.bounds));
+        }
+
+        vertices
+    }
+
+    /// Evaluates the point `centroid + coefficient * (vertex - centroid)`
+    fn trial<B>(
+        &self,
+        backend: &B,
+        budget: &mut Budget,
+        centroid: &Point,
+        vertex: &Point,
+        coefficient: f64,
+    ) -> Vertex
+    where
+        B: EvaluationBackend + ?Sized,
+    {
+        let point = (centroid + &(vertex - centroid).scale(coefficient)).clamp(&self.bounds);
+
+        evaluate(backend, budget, vec![point]).remove(0)
+    }
+
+    /// Shrinks every vertex towards the best one
+    fn shrink<B>(&self, backend: &B, budget: &mut Budget, simplex: &mut Vec<Vertex>)
+    where
+        B: EvaluationBackend + ?Sized,
+    {
+        let best = simplex[0].point.clone();
+
+        let shrunk: Vec<Point> = simplex[1..]
+            .iter()
+            .map(|v| &best + &(&v.point - &best).scale(SIGMA))
+            .collect();
+
+        simplex.truncate(1);
+        simplex.extend(evaluate(backend, budget, shrunk));
+    }
+}
+
+impl Optimizer for NelderMead {
+    fn maximize(&mut self, obj_function: &Objective) -> HypercubeOptimizerResult {
+        self.maximize_with(&LocalBackend::new(obj_function))
+    }
+}
+
+/// Evaluates `points` with `backend`, mapping failed evaluations to negative infinity
+fn evaluate<B>(backend: &B, budget: &mut Budget, points: Vec<Point>) -> Vec<Vertex>
+where
+    B: EvaluationBackend + ?Sized,
+{
+    let images = backend.evaluate_batch(&points);
+    budget.record_evals(points.len() as u32);
+
+    points
+        .into_iter()
+        .zip(images)
+        .map(|(point, image)| {
+            let image = match image {
+                Ok(image) if !image.is_nan() => image,
+                Ok(_) => f64::NEG_INFINITY,
+                Err(e) => {
+                    log::warn!("evaluation failed at {:?}: {:?}", point, e);
+                    f64::NEG_INFINITY
+                }
+            };
+
+            Vertex { point, image }
+        })
+        .collect()
+}
+
+/// Sorts the simplex from best to worst vertex
+fn sort(simplex: &mut [Vertex]) {
+    simplex.sort_by(|a, b| b.image.partial_cmp(&a.image).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::{neg_rosenbrock, neg_sphere};
+    use crate::point;
+
+    fn criteria(max_eval: u32) -> StoppingCriteria {
+        StoppingCriteria {
+            tol_x: 1e-8,
+            tol_f: 1e-12,
+            max_loop: 10_000,
+            max_eval,
+            max_timeout: 60,
+        }
+    }
+
+    #[test]
+    fn converges_on_sphere() {
+        let mut nelder_mead = NelderMead::new(point![3.0; 3], -5.0, 5.0, criteria(5_000));
+
+        let result = Optimizer::maximize(&mut nelder_mead, &neg_sphere);
+
+        assert_eq!(result.get_exit_code(), 0);
+        assert!(result.get_best_f().unwrap() > -1e-8);
+    }
+
+    #[test]
+    fn converges_on_rosenbrock() {
+        let mut nelder_mead = NelderMead::new(point![-1.2, 1.0], -5.0, 10.0, criteria(5_000));
+
+        let result = Optimizer::maximize(&mut nelder_mead, &neg_rosenbrock);
+
+        for coord in result.get_best_x().unwrap().iter() {
+            assert!((coord - 1.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn respects_eval_budget() {
+        let mut nelder_mead = NelderMead::new(point![3.0; 4], -5.0, 5.0, criteria(50));
+
+        let result = Optimizer::maximize(&mut nelder_mead, &neg_sphere);
+
+        assert_eq!(result.get_exit_code(), 2);
+        assert!(result.get_fn_evals() <= 50);
+    }
+
+    #[test]
+    fn initial_simplex_within_bounds() {
+        let nelder_mead = NelderMead::new(point![5.0, 0.0], -5.0, 5.0, criteria(100));
+
+        let simplex = nelder_mead.initial_simplex();
+
+        assert_eq!(simplex.len(), 3);
+        assert_eq!(simplex[1], point![4.5, 0.0]);
+        assert_eq!(simplex[2], point![5.0, 0.5]);
+    }
+
+    #[test]
+    fn best_point_within_bounds() {
+        let mut nelder_mead = NelderMead::new(point![3.0; 2], 1.0, 5.0, criteria(2_000));
+
+        let result = Optimizer::maximize(&mut nelder_mead, &neg_sphere);
+        let best_x = result.get_best_x().unwrap();
+
+        assert!(best_x.iter().all(|x| (1.0..=5.0).contains(x)));
+        assert!((result.get_best_f().unwrap() + 2.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,136 @@
+/// Transform applied to objective function values before the optimizer uses them to decide when
+/// to stop and how to move the hypercube. Useful when values span many orders of magnitude, where
+/// absolute tolerances and averages are dominated by the largest values. Reported values are
+/// always raw.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutputNormalization {
+    /// use values as they are
+    #[default]
+    Raw,
+
+    /// signed logarithm `sign(f) * ln(1 + |f|)`, which compresses large magnitudes
+    Log,
+
+    /// fraction of the values seen so far that are smaller than the value, between 0 and 1
+    Rank,
+
+    /// number of standard deviations from the running mean of the values seen so far
+    ZScore,
+}
+
+/// Applies an `OutputNormalization` using the statistics of the values observed so far
+#[derive(Debug, Clone)]
+pub(crate) struct OutputNormalizer {
+    normalization: OutputNormalization,
+
+    /// observed values in increasing order, only kept for rank normalization
+    sorted: Vec<f64>,
+
+    /// number of observed values
+    count: u64,
+
+    /// running mean of the observed values
+    mean: f64,
+
+    /// running sum of squared differences from the mean
+    sqr_diff_sum: f64,
+}
+
+impl OutputNormalizer {
+    pub(crate) fn new(normalization: OutputNormalization) -> Self {
+        Self {
+            normalization,
+            sorted: Vec::new(),
+            count: 0,
+            mean: 0.0,
+            sqr_diff_sum: 0.0,
+        }
+    }
+
+    /// Adds `value` to the statistics used by rank and z-score normalization
+    pub(crate) fn observe(&mut self, value: f64) {
+        match self.normalization {
+            OutputNormalization::Rank => {
+                let index = self.sorted.partition_point(|v| *v < value);
+                self.sorted.insert(index, value);
+            }
+            OutputNormalization::ZScore => {
+                // Welford's online algorithm
+                self.count += 1;
+                let delta = value - self.mean;
+                self.mean += delta / self.count as f64;
+                self.sqr_diff_sum += delta * (value - self.mean);
+            }
+            OutputNormalization::Raw | OutputNormalization::Log => {}
+        }
+    }
+
+    /// Normalized `value`. Normalization preserves order, so better values stay better.
+    pub(crate) fn normalize(&self, value: f64) -> f64 {
+        match self.normalization {
+            OutputNormalization::Raw => value,
+            OutputNormalization::Log => value.signum() * value.abs().ln_1p(),
+            OutputNormalization::Rank => {
+                if self.sorted.is_empty() {
+                    return 0.0;
+                }
+
+                self.sorted.partition_point(|v| *v < value) as f64 / self.sorted.len() as f64
+            }
+            OutputNormalization::ZScore => {
+                let std_dev = if self.count > 1 {
+                    (self.sqr_diff_sum / (self.count - 1) as f64).sqrt()
+                } else {
+                    0.0
+                };
+
+                // a single value or identical values carry no scale information
+                if std_dev > 0.0 {
+                    (value - self.mean) / std_dev
+                } else {
+                    value - self.mean
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_compresses_magnitudes() {
+        let normalizer = OutputNormalizer::new(OutputNormalization::Log);
+
+        assert_eq!(normalizer.normalize(0.0), 0.0);
+        assert!((normalizer.normalize(-1e6) + 1e6f64.ln_1p()).abs() < 1e-12);
+        assert!(normalizer.normalize(1e9) - normalizer.normalize(1e8) < 3.0);
+    }
+
+    #[test]
+    fn rank_of_observed_values() {
+        let mut normalizer = OutputNormalizer::new(OutputNormalization::Rank);
+
+        for value in [-1e9, 5.0, -3.0, 1e12] {
+            normalizer.observe(value);
+        }
+
+        assert_eq!(normalizer.normalize(-1e9), 0.0);
+        assert_eq!(normalizer.normalize(0.0), 0.5);
+        assert_eq!(normalizer.normalize(1e12), 0.75);
+        assert_eq!(normalizer.normalize(1e13), 1.0);
+    }
+
+    #[test]
+    fn z_score_of_observed_values() {
+        let mut normalizer = OutputNormalizer::new(OutputNormalization::ZScore);
+
+        for value in [2.0, 4.0, 6.0] {
+            normalizer.observe(value);
+        }
+
+        assert!((normalizer.normalize(4.0)).abs() < 1e-12);
+        assert!((normalizer.normalize(6.0) - 1.0).abs() < 1e-12);
+    }
+}
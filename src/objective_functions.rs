@@ -16,7 +16,7 @@ pub fn rastrigin(input_point: &Point) -> f64 {
 
 pub fn neg_rastrigin(input_point: &Point) -> f64 {
     let res = rastrigin(input_point);
-    res * -1.0
+    -res
 }
 
 pub fn sphere(input_point: &Point) -> f64 {
@@ -25,11 +25,74 @@ pub fn sphere(input_point: &Point) -> f64 {
 }
 
 pub fn neg_sphere(input_point: &Point) -> f64 {
-    let res = sphere(&input_point);
-    res * -1.0
+    let res = sphere(input_point);
+    -res
 }
 
-pub fn nan_function(input_point: &Point) -> f64 {
+pub fn neg_sphere_gradient(input_point: &Point) -> Point {
+    input_point.scale(-2.0)
+}
+
+pub fn rosenbrock(input_point: &Point) -> f64 {
+    let coords: Vec<f64> = input_point.iter().copied().collect();
+
+    coords.windows(2).fold(0.0, |acc, pair| {
+        acc + 100.0 * (pair[1] - pair[0].powf(2.0)).powf(2.0) + (1.0 - pair[0]).powf(2.0)
+    })
+}
+
+pub fn neg_rosenbrock(input_point: &Point) -> f64 {
+    let res = rosenbrock(input_point);
+    -res
+}
+
+pub fn neg_rosenbrock_gradient(input_point: &Point) -> Point {
+    let coords: Vec<f64> = input_point.iter().copied().collect();
+    let mut gradient = vec![0.0; coords.len()];
+
+    for (i, pair) in coords.windows(2).enumerate() {
+        let inner = pair[1] - pair[0].powf(2.0);
+
+        gradient[i] += 400.0 * pair[0] * inner + 2.0 * (1.0 - pair[0]);
+        gradient[i + 1] -= 200.0 * inner;
+    }
+
+    Point::from_vec(gradient)
+}
+
+pub fn ackley(input_point: &Point) -> f64 {
+    let dimension = input_point.dim() as f64;
+    let sum_sqr = input_point.iter().fold(0.0, |acc, x| acc + x.powf(2.0));
+    let sum_cos = input_point
+        .iter()
+        .fold(0.0, |acc, x| acc + (2.0 * PI * x).cos());
+
+    -20.0 * (-0.2 * (sum_sqr / dimension).sqrt()).exp() - (sum_cos / dimension).exp()
+        + 20.0
+        + std::f64::consts::E
+}
+
+pub fn neg_ackley(input_point: &Point) -> f64 {
+    let res = ackley(input_point);
+    -res
+}
+
+pub fn griewank(input_point: &Point) -> f64 {
+    let sum = input_point.iter().fold(0.0, |acc, x| acc + x.powf(2.0)) / 4000.0;
+    let product = input_point
+        .iter()
+        .enumerate()
+        .fold(1.0, |acc, (i, x)| acc * (x / ((i + 1) as f64).sqrt()).cos());
+
+    sum - product + 1.0
+}
+
+pub fn neg_griewank(input_point: &Point) -> f64 {
+    let res = griewank(input_point);
+    -res
+}
+
+pub fn nan_function(_input_point: &Point) -> f64 {
     f64::NAN
 }
 
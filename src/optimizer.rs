@@ -1,12 +1,55 @@
+use crate::backend::{EvaluationBackend, LocalBackend, MaybeSync, MetadataBackend, Objective};
+use crate::bounds::HypercubeBounds;
+use crate::checkpoint::{self, Checkpoint};
+use crate::eval_log::EvalLogWriter;
 use crate::evaluation::PointEval;
-use crate::hypercube::Hypercube;
+use crate::events::{OptimizerEvent, OptimizerObserver};
+use crate::handle::{BestHandle, CancelHandle};
+use crate::hypercube::{Hypercube, MAX_POPULATION_SIZE};
+use crate::importance::DimensionImportance;
+use crate::lbfgs;
+use crate::lbfgs::DifferentiableObjective;
+use crate::manifest::RunManifest;
+use crate::normalization::{OutputNormalization, OutputNormalizer};
+use crate::point;
 use crate::point::Point;
-use crate::result::HypercubeOptimizerResult;
-use std::collections::BinaryHeap;
+use crate::population_file::read_population;
+use crate::result::{
+    ConfidenceInterval, HypercubeOptimizerResult, IterationRecord, PhaseTimings, Stagnation,
+};
+use crate::sampler::Sampler;
+use crate::snapshot::{PopulationSnapshots, SnapshotFormat};
+use crate::statistics::student_t_quantile;
+use crate::stopping::{mann_kendall_z, Budget, StoppingCriteria};
+use crate::transform::{InputTransform, TransformedBackend, TransformedObjective};
+#[cfg(feature = "parallel")]
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use std::collections::{BinaryHeap, VecDeque};
 use std::f32::consts::E;
-use std::time::Instant;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Common interface of the optimization algorithms in this crate. Every optimizer maximizes an
+/// objective function and reports its outcome as a `HypercubeOptimizerResult`, so algorithms can
+/// be swapped for one another (e.g. behind a `Box<dyn Optimizer>`).
+pub trait Optimizer {
+    /// Maximizes `obj_function` and returns the best point found
+    fn maximize(&mut self, obj_function: &Objective) -> HypercubeOptimizerResult;
+}
+
+/// Projection of a point onto the feasible region of a constrained problem
+type Repair = Arc<dyn Fn(&Point) -> Point + Send + Sync>;
+
+/// Observer shared by an optimizer and its clones
+type SharedObserver = Arc<Mutex<dyn OptimizerObserver + Send>>;
 
 /// Represents a hypercube optimizer
+///
+/// Optimizers are `Send` and `Sync`, so they can be moved to other threads. Clones start out with
+/// their own best value and cancellation handles, but share the observer, evaluation log, repair
+/// function, and checkpoint file of the optimizer they were cloned from.
 pub struct HypercubeOptimizer {
     /// dimension of the optimization problem
     dimension: u32,
@@ -14,29 +57,317 @@ pub struct HypercubeOptimizer {
     /// first point inside the search space to evaluate
     init_point: Point,
 
+    /// additional known-good starting points evaluated together with `init_point`
+    initial_candidates: Vec<Point>,
+
     /// hypercube used for optimization
     hypercube: Hypercube,
 
-    /// desired tolerance for the difference between consecutive function inputs
-    tol_x: f64,
-
-    /// desired tolerance for the difference between consective function evaluations
-    tol_f: f64,
-
-    /// maximum number of optimization loops allowed
-    max_loop: u32,
-
-    /// maximum number of function evaluations allowed
-    max_eval: u32,
+    /// population size of the hypercube before any restart grew it
+    init_population_size: u64,
 
-    /// maximum amount of time to optimize objective function
-    max_timeout: u32,
+    /// tuning options of the optimization process
+    config: HypercubeOptimizerConfig,
 
     /// lower bound of the search space
     lower_bound: f64,
 
     /// upper bound of the search space
     upper_bound: f64,
+
+    /// best value found so far, shared with the handles returned by `best_handle`
+    best: BestHandle,
+
+    /// stops the optimization when cancelled, shared with the handles returned by `cancel_handle`
+    cancel: CancelHandle,
+
+    /// log that every successful evaluation is appended to, shared with the hypercube
+    eval_log: Option<Arc<Mutex<EvalLogWriter>>>,
+
+    /// receives the events of the optimization
+    observer: Option<SharedObserver>,
+
+    /// point in time by which every optimization must finish
+    deadline: Option<Instant>,
+
+    /// maps points of the search space to the parameters of the objective function
+    input_transform: Option<InputTransform>,
+
+    /// projects sampled points that violate the constraints back into the feasible region
+    repair: Option<Repair>,
+
+    /// spacing of the grid that every dimension is restricted to, zero for continuous dimensions
+    grid_steps: Option<Point>,
+
+    /// writes the optimization progress to a file every few loops
+    checkpointing: Option<Checkpointing>,
+
+    /// continue from the checkpoint file of `checkpointing` if it belongs to this optimization
+    auto_resume: bool,
+
+    /// checkpoint the next optimization continues from
+    resume_from: Option<Checkpoint>,
+
+    /// writes the population of every loop to a directory
+    population_snapshots: Option<PopulationSnapshots>,
+
+    /// population of the first loop, loaded from a file
+    initial_population: Option<Vec<Point>>,
+
+    /// `initial_population` until the first loop of the current optimization has used it
+    pending_population: Option<Vec<Point>>,
+
+    /// seed of the random number generator for the next optimization, drawn from the current
+    /// generator if there is none
+    next_seed: Option<u64>,
+
+    /// name of the objective function recorded in the run manifest
+    objective_name: Option<String>,
+
+    /// hash of the starting points of the current optimization and their images
+    objective_hash: Option<u64>,
+
+    /// objective hash recorded in the manifest this optimizer was created from, compared with the
+    /// hash of every optimization
+    manifest_objective_hash: Option<u64>,
+
+    /// the default population size of the search space exceeds `MAX_POPULATION_SIZE` and no
+    /// population size was set
+    population_too_large: bool,
+}
+
+/// Where and how often the optimization progress is checkpointed
+#[derive(Clone)]
+struct Checkpointing {
+    /// number of loops between checkpoints
+    every: u32,
+
+    path: PathBuf,
+}
+
+/// Tuning options of a `HypercubeOptimizer` that are independent of the problem being optimized.
+/// The same configuration can be reused across problems, e.g. when benchmarking.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HypercubeOptimizerConfig {
+    /// loop, evaluation, and time budgets and the input and image tolerances, shared with the
+    /// other optimizers of this crate
+    pub stopping: StoppingCriteria,
+
+    /// desired tolerance for the difference between consecutive function inputs along each
+    /// dimension. Overrides `stopping.tol_x` if set.
+    pub tol_x_per_dimension: Option<Point>,
+
+    /// weight of every dimension in the distances between best points, which drive the
+    /// convergence factor and the input tolerance check. All dimensions weigh 1 if `None`.
+    pub dimension_weights: Option<Point>,
+
+    /// desired tolerance for the difference between consecutive function evaluations relative to
+    /// the magnitude of the previous best evaluation. Either this or `stopping.tol_f` being met
+    /// counts towards image convergence.
+    pub rel_tol_f: f64,
+
+    /// number of consecutive loops whose best evaluations must change by less than the function
+    /// tolerances for the optimization to count as converged
+    pub stagnation_window: u32,
+
+    /// maximum amount of time every call to `maximize` may take. Unlike `stopping.max_timeout`,
+    /// the optimization stops before starting a loop that is not expected to finish in time.
+    pub time_budget: Option<Duration>,
+
+    /// seed for the random number generator that samples the hypercube population. A random seed
+    /// is used if `None`.
+    pub seed: Option<u64>,
+
+    /// how the hypercube is resized between optimization loops
+    pub shrink_strategy: ShrinkStrategy,
+
+    /// side length of the first hypercube as a fraction of the search space side length, between
+    /// 0 and 1. Smaller first hypercubes are centered on the initial point, like the initial
+    /// radius of a trust region.
+    pub initial_scale: f64,
+
+    /// sample the population over more of the hypercube along dimensions in which the best point
+    /// moves the most, and less along the others
+    pub importance_sampling: bool,
+
+    /// distribution the population is sampled from inside the hypercube
+    pub sampler: Sampler,
+
+    /// restarts the optimization with a fresh hypercube after it converges. No restarts are done
+    /// if `None`.
+    pub restart_policy: Option<RestartPolicy>,
+
+    /// stops the optimization as soon as the best evaluation reaches this value
+    pub stop_at_value: Option<f64>,
+
+    /// replaces the function tolerance checks with a trend test on the recent best evaluations
+    pub plateau_detection: Option<PlateauDetection>,
+
+    /// re-evaluates every new best point to estimate a confidence interval on its value. No
+    /// resampling is done if `None`.
+    pub resampling: Option<Resampling>,
+
+    /// transform applied to objective function values before they are compared against the
+    /// function tolerances and used to resize the hypercube
+    pub output_normalization: OutputNormalization,
+
+    /// which optimization messages are logged
+    pub verbosity: Verbosity,
+
+    /// also log every `OptimizerEvent` at debug level
+    pub log_events: bool,
+}
+
+/// Controls which messages a `HypercubeOptimizer` logs. Every level includes the messages of the
+/// levels before it. Messages are still filtered by the installed logger afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// only warnings and errors
+    Quiet,
+
+    /// start and end of the optimization, e.g. hypercube sizes and why it terminated
+    Summary,
+
+    /// progress of every optimization loop
+    Loops,
+
+    /// detailed geometry updates within every loop
+    #[default]
+    Detailed,
+}
+
+/// Controls how an optimization is restarted after it converges. Each restart starts over with a
+/// hypercube spanning the whole search space, so other basins of attraction get explored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestartPolicy {
+    /// maximum number of restarts
+    pub max_restarts: u32,
+
+    /// factor by which the population size grows with every restart
+    pub population_growth: f64,
+}
+
+/// Statistical convergence test for noisy objectives. The optimization counts as converged once
+/// the best evaluations of the most recent loops show no significant upward trend in a
+/// Mann-Kendall test, instead of once consecutive best evaluations differ by less than the
+/// function tolerances.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlateauDetection {
+    /// number of recent loops tested for a trend
+    pub window: u32,
+
+    /// Mann-Kendall z-score the trend of the recent loops must exceed to count as improving.
+    /// 1.645 corresponds to a one-sided test at 5% significance.
+    pub z_threshold: f64,
+}
+
+impl Default for PlateauDetection {
+    fn default() -> Self {
+        Self {
+            window: 30,
+            z_threshold: 1.645,
+        }
+    }
+}
+
+/// Repeated evaluation of best points for stochastic objectives. Each candidate best point is
+/// evaluated `samples` times, and it only replaces the current best once the lower end of its
+/// confidence interval exceeds the upper end of the current best's, so a lucky draw of the noise
+/// is not mistaken for progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resampling {
+    /// number of evaluations of every candidate best point
+    pub samples: u32,
+
+    /// confidence level of the intervals, e.g. 0.95
+    pub confidence: f64,
+}
+
+impl Default for Resampling {
+    fn default() -> Self {
+        Self {
+            samples: 5,
+            confidence: 0.95,
+        }
+    }
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 5,
+            population_growth: 2.0,
+        }
+    }
+}
+
+/// Controls how the hypercube is resized between optimization loops
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ShrinkStrategy {
+    /// Shrink after every loop that improves the best value, by a convergence factor derived from
+    /// the distance between the previous and current best points
+    #[default]
+    ConvergenceFactor,
+
+    /// Expand the hypercube after a successful loop and contract it after a failed one, like the
+    /// radius of a trust region. A loop is successful when it improves the best value by more than
+    /// `success_threshold`.
+    TrustRegion {
+        /// factor by which the hypercube grows after a successful loop, at least 1
+        expansion: f64,
+
+        /// factor by which the hypercube shrinks after a failed loop, between 0 and 1
+        contraction: f64,
+
+        /// minimum improvement of the best value for a loop to count as successful
+        success_threshold: f64,
+    },
+}
+
+impl ShrinkStrategy {
+    fn validate(&self) {
+        if let ShrinkStrategy::TrustRegion {
+            expansion,
+            contraction,
+            success_threshold,
+        } = *self
+        {
+            assert!(expansion >= 1.0, "expansion cannot be less than one");
+            assert!(
+                contraction > 0.0 && contraction < 1.0,
+                "contraction must be between zero and one"
+            );
+            assert!(
+                success_threshold >= 0.0,
+                "success threshold cannot be negative"
+            );
+        }
+    }
+}
+
+impl Default for HypercubeOptimizerConfig {
+    fn default() -> Self {
+        Self {
+            stopping: StoppingCriteria::default(),
+            tol_x_per_dimension: None,
+            dimension_weights: None,
+            rel_tol_f: 0.0,
+            stagnation_window: 30,
+            time_budget: None,
+            seed: None,
+            shrink_strategy: ShrinkStrategy::default(),
+            initial_scale: 1.0,
+            importance_sampling: false,
+            sampler: Sampler::default(),
+            restart_policy: None,
+            stop_at_value: None,
+            plateau_detection: None,
+            resampling: None,
+            output_normalization: OutputNormalization::default(),
+            verbosity: Verbosity::default(),
+            log_events: false,
+        }
+    }
 }
 
 impl HypercubeOptimizer {
@@ -48,14 +379,16 @@ impl HypercubeOptimizer {
     /// * `lower_bound` - the lower bound of the initial hypercube that defines the search space
     /// * `upper_bound` - the upper bound of the initial hypercube that defines the search space
     /// * `tol_x` - once the delta between consecutive best objective function inputs falls below this
-    /// value, the optimization process will terminate
+    ///   value while the outputs improve by at most `tol_f`, the optimization process will
+    ///   terminate
     /// * `tol_f` - once the delta between consecutive best objective function outputs falls below
-    /// this value, the optimization process will terminate
+    ///   this value, the optimization process will terminate
     /// * `max_loop` - the maximum number of times the optimization loop is allowed to run
     /// * `max_eval` - the maximum number of objective function evaluations the optimizer will
-    /// execute
+    ///   execute
     /// * `max_timeout` - the maximum amount of time for the optimization process to run for
     ///
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         init_point: Point,
         lower_bound: f64,
@@ -65,6 +398,35 @@ impl HypercubeOptimizer {
         max_loop: u32,
         max_eval: u32,
         max_timeout: u32,
+    ) -> Self {
+        let config = HypercubeOptimizerConfig {
+            stopping: StoppingCriteria {
+                tol_x,
+                tol_f,
+                max_loop,
+                max_eval,
+                max_timeout,
+            },
+            ..HypercubeOptimizerConfig::default()
+        };
+
+        Self::with_config(init_point, lower_bound, upper_bound, config)
+    }
+
+    /// Returns a new `HypercubeOptimizer` using the tuning options in `config`
+    ///
+    /// # Arguments
+    ///
+    /// * `init_point` - the initial point inside the optimization search space to evaluate
+    /// * `lower_bound` - the lower bound of the initial hypercube that defines the search space
+    /// * `upper_bound` - the upper bound of the initial hypercube that defines the search space
+    /// * `config` - tuning options of the optimization process
+    ///
+    pub fn with_config(
+        init_point: Point,
+        lower_bound: f64,
+        upper_bound: f64,
+        config: HypercubeOptimizerConfig,
     ) -> Self {
         assert!(
             upper_bound > lower_bound,
@@ -78,187 +440,1879 @@ impl HypercubeOptimizer {
             init_point.min_val().unwrap() >= lower_bound,
             "init_point not inside lower bound"
         );
+        config.shrink_strategy.validate();
+        assert_ne!(
+            config.stagnation_window, 0,
+            "stagnation window cannot be zero"
+        );
+        assert!(
+            config.initial_scale > 0.0 && config.initial_scale <= 1.0,
+            "initial scale must be between zero and one"
+        );
+
+        // bounds too large for the default population size make every optimization stop with
+        // exit code 4 until a population size is set
+        let default_population_size =
+            Hypercube::default_population_size(init_point.dim(), lower_bound, upper_bound);
 
         // create initial hypercube based on initial bounds and place inside vector
-        let hypercube = Hypercube::new(init_point.dim(), lower_bound, upper_bound);
+        let mut hypercube = Hypercube::with_population_size(
+            init_point.dim(),
+            lower_bound,
+            upper_bound,
+            default_population_size.unwrap_or(1),
+        );
+
+        if let Some(seed) = config.seed {
+            hypercube.set_seed(seed);
+        }
+        hypercube.set_sampler(config.sampler.clone());
+
+        Self::fit_initial_hypercube(&mut hypercube, &init_point, config.initial_scale);
+
+        let next_seed = config.seed;
 
         Self {
             dimension: init_point.dim(),
             init_point,
+            initial_candidates: Vec::new(),
+            init_population_size: hypercube.get_population_size(),
             hypercube,
-            tol_x,
-            tol_f,
-            max_loop,
-            max_eval,
-            max_timeout,
+            config,
             lower_bound,
             upper_bound,
+            best: BestHandle::new(),
+            cancel: CancelHandle::new(),
+            eval_log: None,
+            observer: None,
+            deadline: None,
+            input_transform: None,
+            repair: None,
+            grid_steps: None,
+            checkpointing: None,
+            auto_resume: false,
+            resume_from: None,
+            population_snapshots: None,
+            initial_population: None,
+            pending_population: None,
+            next_seed,
+            objective_name: None,
+            objective_hash: None,
+            manifest_objective_hash: None,
+            population_too_large: default_population_size.is_none(),
         }
     }
 
-    pub fn maximize<F>(&mut self, obj_function: F) -> HypercubeOptimizerResult
-    where
-        F: Fn(&Point) -> f64,
-    {
-        // <----- Optimization result set-up ----->
+    /// Returns a new `HypercubeOptimizer` for users without an initial guess. The center of the
+    /// search space is used as the initial point.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimension` - the dimension of the optimization problem
+    /// * `lower_bound` - the lower bound of the initial hypercube that defines the search space
+    /// * `upper_bound` - the upper bound of the initial hypercube that defines the search space
+    /// * `config` - tuning options of the optimization process
+    ///
+    pub fn from_bounds(
+        dimension: u32,
+        lower_bound: f64,
+        upper_bound: f64,
+        config: HypercubeOptimizerConfig,
+    ) -> Self {
+        assert_ne!(dimension, 0, "dimension cannot be zero");
 
-        let start_time = Instant::now();
+        let center = point![(lower_bound + upper_bound) / 2.0; dimension];
 
-        let fn_eval = 0;
+        Self::with_config(center, lower_bound, upper_bound, config)
+    }
 
-        let init_eval = PointEval::with_eval(self.init_point.clone(), &obj_function);
+    /// Returns a new `HypercubeOptimizer` that repeats the run recorded in `manifest`, e.g. the one
+    /// returned by `HypercubeOptimizerResult::get_manifest`. Repair functions, observers, logs,
+    /// deadlines, and initial population files are not recorded and have to be set again. A
+    /// warning is logged if the optimization evaluates an objective function other than the
+    /// recorded one.
+    pub fn from_manifest(manifest: &RunManifest) -> Self {
+        if manifest.crate_version != env!("CARGO_PKG_VERSION") {
+            log::warn!(
+                "manifest was recorded by version {} but this is version {}, the run may differ",
+                manifest.crate_version,
+                env!("CARGO_PKG_VERSION")
+            );
+        }
 
-        // TODO: compute no. of allowed hypercube evaluations from max_eval and number of points
-        // in hypercube
+        let mut optimizer = Self::with_config(
+            manifest.init_point.clone(),
+            manifest.lower_bound,
+            manifest.upper_bound,
+            manifest.config.clone(),
+        );
 
-        // keep track of average image
-        let mut average_f = init_eval.get_eval();
+        if !manifest.initial_candidates.is_empty() {
+            optimizer.set_initial_candidates(manifest.initial_candidates.clone());
+        }
+        if let Some(input_transform) = &manifest.input_transform {
+            optimizer.set_input_transform(input_transform.clone());
+        }
+        if let Some(grid_steps) = &manifest.grid_steps {
+            optimizer.set_grid_steps(grid_steps.clone());
+        }
+        if manifest.init_population_size != optimizer.init_population_size {
+            optimizer.set_population_size(manifest.init_population_size);
+        }
 
-        let mut best_evaluations: BinaryHeap<PointEval> = BinaryHeap::new();
+        optimizer.objective_name = manifest.objective_name.clone();
+        optimizer.manifest_objective_hash = manifest.objective_hash;
+        optimizer.next_seed = Some(manifest.seed);
+        optimizer
+    }
 
-        // records absolute change in F to compare with tolF
-        let mut abs_delta_f_vec = Vec::with_capacity(30);
+    /// Adds known-good starting points, e.g. from a previous tuning round. They are evaluated
+    /// together with the initial point before the first loop, count against the evaluation budget,
+    /// and the first hypercube is centered on the best of them.
+    pub fn set_initial_candidates(&mut self, candidates: Vec<Point>) {
+        for candidate in candidates.iter() {
+            assert_eq!(
+                candidate.dim(),
+                self.dimension,
+                "candidate dimension does not match problem dimension"
+            );
+            assert!(
+                candidate.max_val().unwrap() <= self.upper_bound,
+                "candidate not inside upper bound"
+            );
+            assert!(
+                candidate.min_val().unwrap() >= self.lower_bound,
+                "candidate not inside lower bound"
+            );
+        }
+
+        self.initial_candidates = candidates;
+    }
 
-        log::info!("initial hypercube size: {}", self.hypercube.diagonal_len());
-        log::info!(
-            "initial hypercube population size: {}",
-            self.hypercube.get_population_size()
+    /// Starts the optimization with a hypercube whose side is `initial_scale` times the side of the
+    /// search space, centered on the initial point, instead of one spanning the whole search
+    /// space. The hypercube still never leaves the search space.
+    pub fn set_initial_scale(&mut self, initial_scale: f64) {
+        assert!(
+            initial_scale > 0.0 && initial_scale <= 1.0,
+            "initial scale must be between zero and one"
         );
+        self.config.initial_scale = initial_scale;
 
-        let mut previous_best_eval = init_eval;
+        let population_size = self.hypercube.get_population_size();
+        self.hypercube.restart(population_size);
+        Self::fit_initial_hypercube(&mut self.hypercube, &self.init_point, initial_scale);
+    }
+
+    /// Sets the number of points sampled in every loop. Defaults to `n^2 (upper - lower)` for an
+    /// `n`-dimensional problem. Restarts grow the population from this size.
+    pub fn set_population_size(&mut self, population_size: u64) {
+        assert_ne!(population_size, 0, "population size cannot be zero");
+
+        self.population_too_large = false;
+        self.init_population_size = population_size;
+        self.hypercube.restart(population_size);
+        Self::fit_initial_hypercube(
+            &mut self.hypercube,
+            &self.init_point,
+            self.config.initial_scale,
+        );
+    }
 
-        // start optimization loop
-        for i in 0..self.max_loop {
-            // <----- hypercube randomize ----->
+    /// Seeds the random number generator that samples the hypercube population, making the
+    /// optimization reproducible
+    pub fn set_seed(&mut self, seed: u64) {
+        self.config.seed = Some(seed);
+        self.hypercube.set_seed(seed);
+        self.next_seed = Some(seed);
+    }
 
-            self.hypercube.randomize_pop();
+    /// Sets how the hypercube is resized between optimization loops. Defaults to
+    /// `ShrinkStrategy::ConvergenceFactor`.
+    pub fn set_shrink_strategy(&mut self, shrink_strategy: ShrinkStrategy) {
+        shrink_strategy.validate();
+        self.config.shrink_strategy = shrink_strategy;
+    }
 
-            // <----- hypercube evaluation ----->
+    /// Enables sampling the population over more of the hypercube along the dimensions that matter
+    /// most, as learned from the moves of the best point. Disabled by default. The learned weights
+    /// are reported by `HypercubeOptimizerResult::get_dimension_importance` either way.
+    pub fn set_importance_sampling(&mut self, importance_sampling: bool) {
+        self.config.importance_sampling = importance_sampling;
+    }
 
-            self.hypercube.evaluate(&obj_function);
+    /// Sets the distribution the population is sampled from inside the hypercube. Defaults to
+    /// `Sampler::Uniform`.
+    pub fn set_sampler(&mut self, sampler: Sampler) {
+        self.hypercube.set_sampler(sampler.clone());
+        self.config.sampler = sampler;
+    }
 
-            // get best eval from current hypercube evaluation
-            let current_best_eval = self.hypercube.peek_best_value().unwrap();
+    /// Restarts the optimization with a fresh hypercube after it converges, as long as the
+    /// `max_eval` budget allows it. The best value of every run is reported by
+    /// `HypercubeOptimizerResult::get_restart_bests`.
+    pub fn set_restart_policy(&mut self, restart_policy: RestartPolicy) {
+        assert!(
+            restart_policy.population_growth >= 1.0,
+            "population growth cannot be less than one"
+        );
+        self.config.restart_policy = Some(restart_policy);
+    }
 
-            if current_best_eval > previous_best_eval {
-                best_evaluations.push(current_best_eval.clone());
-            } else {
-                best_evaluations.push(previous_best_eval.clone())
-            }
+    /// Sets the relative function tolerance. Consecutive best evaluations whose difference is
+    /// within `rel_tol_f` times the magnitude of the previous best evaluation count towards image
+    /// convergence, just like differences within `tol_f`. Disabled (zero) by default.
+    pub fn set_rel_tol_f(&mut self, rel_tol_f: f64) {
+        assert!(rel_tol_f >= 0.0, "relative tolerance cannot be negative");
+        self.config.rel_tol_f = rel_tol_f;
+    }
 
-            // calculate difference between previous best and current best
-            let abs_delta_f = (current_best_eval.get_eval() - previous_best_eval.get_eval()).abs();
+    /// Sets a separate input tolerance for every dimension, for problems whose dimensions have
+    /// very different scales. The optimization converges once the best point moves by less than
+    /// the tolerance along every dimension, without its image improving by more than `tol_f`, for
+    /// a whole stagnation window.
+    pub fn set_tol_x(&mut self, tol_x: Point) {
+        assert_eq!(
+            tol_x.dim(),
+            self.dimension,
+            "tolerance dimension does not match problem dimension"
+        );
+        assert!(
+            tol_x.iter().all(|t| *t >= 0.0),
+            "tolerances cannot be negative"
+        );
 
-            if abs_delta_f <= self.tol_f {
-                abs_delta_f_vec.push(abs_delta_f);
+        self.config.tol_x_per_dimension = Some(tol_x);
+    }
 
-                // if the delta_f is within the tolerance consecutively more than 30 times, break
-                // optimization loop
-                if abs_delta_f_vec.len() >= 30 {
-                    log::warn!("optimization process terminated due to image convergence");
-                    let best_value: Option<&PointEval> = best_evaluations.peek();
+    /// Weighs the dimensions in the distances between consecutive best points, which decide how
+    /// much the hypercube shrinks and when the best point counts as settled. A dimension with
+    /// weight `w` counts like one scaled by `sqrt(w)`, so disparate-scale problems can make every
+    /// dimension count equally.
+    pub fn set_dimension_weights(&mut self, dimension_weights: Point) {
+        assert_eq!(
+            dimension_weights.dim(),
+            self.dimension,
+            "weights dimension does not match problem dimension"
+        );
+        assert!(
+            dimension_weights.iter().all(|w| *w > 0.0),
+            "weights must be positive"
+        );
 
-                    let time_elapsed = start_time.elapsed();
+        self.config.dimension_weights = Some(dimension_weights);
+    }
 
-                    return HypercubeOptimizerResult::new(0, i, fn_eval, best_value, time_elapsed);
-                }
-            } else {
-                abs_delta_f_vec.clear();
-            }
+    /// Sets the number of consecutive loops whose best evaluations must change by less than the
+    /// function tolerances before the optimization stops due to image convergence. Defaults to 30;
+    /// shorter windows suit expensive objectives.
+    pub fn set_stagnation_window(&mut self, stagnation_window: u32) {
+        assert_ne!(stagnation_window, 0, "stagnation window cannot be zero");
+        self.config.stagnation_window = stagnation_window;
+    }
 
-            // calculate new average
-            average_f = average_f + ((current_best_eval.get_eval() - average_f) / ((i + 1) as f64));
+    /// Sets which optimization messages are logged, e.g. `Verbosity::Summary` to avoid a log line
+    /// per loop in long runs. Defaults to `Verbosity::Detailed`.
+    pub fn set_verbosity(&mut self, verbosity: Verbosity) {
+        self.config.verbosity = verbosity;
+    }
 
-            // if current best is worse than average best value skip hypercube displacement and shrink
-            if current_best_eval.get_eval() < average_f || current_best_eval < previous_best_eval {
-                continue;
-            } else {
-                log::info!(
-                    "--------------- loop {} of {} ---------------",
-                    i,
-                    self.max_loop
-                );
-                log::info!("current best eval: {}", current_best_eval);
-                log::info!("previous best eval: {}", previous_best_eval);
-            }
+    /// Reports the events of every optimization to `observer` as they happen
+    pub fn set_observer(&mut self, observer: impl OptimizerObserver + Send + 'static) {
+        self.observer = Some(Arc::new(Mutex::new(observer)));
+    }
 
-            // <----- hypercube displace preparation ----->
+    /// Also logs every `OptimizerEvent` at debug level. Disabled by default.
+    pub fn set_log_events(&mut self, log_events: bool) {
+        self.config.log_events = log_events;
+    }
 
-            // compute new hypercube center (will be the average of old and new best value)
-            let temp = &current_best_eval.get_point() + &previous_best_eval.get_point();
-            let new_hypercube_center = temp.scale(0.5);
+    /// Stops the optimization with exit code 5 as soon as the best evaluation reaches `target`,
+    /// instead of using up the whole loop budget
+    pub fn set_stop_at_value(&mut self, target: f64) {
+        assert!(!target.is_nan(), "target value cannot be NaN");
+        self.config.stop_at_value = Some(target);
+    }
 
-            // <----- hypercube shrink preparation ----->
+    /// Stops every optimization with exit code 3 by `deadline`. Loops that are not expected to
+    /// finish before the deadline are not started, so the deadline is not overshot by a whole
+    /// population evaluation.
+    pub fn set_deadline(&mut self, deadline: Instant) {
+        self.deadline = Some(deadline);
+    }
 
-            // compute X_n
-            let previous_normalized = (&previous_best_eval.get_point()
-                - self.hypercube.get_center())
-            .scale(1.0 / self.hypercube.get_side_length());
+    /// Stops every optimization with exit code 3 once it has run for `time_budget`. Like
+    /// `set_deadline`, but measured from the start of each call to `maximize`.
+    pub fn set_time_budget(&mut self, time_budget: Duration) {
+        assert!(!time_budget.is_zero(), "time budget cannot be zero");
+        self.config.time_budget = Some(time_budget);
+    }
 
-            // compute X_min_n
-            let current_normalized = (&current_best_eval.get_point() - self.hypercube.get_center())
-                .scale(1.0 / self.hypercube.get_side_length());
+    /// Detects image convergence with a trend test on the best evaluations of recent loops instead
+    /// of with `tol_f` and `rel_tol_f`, which trigger spuriously or never on noisy objectives
+    pub fn set_plateau_detection(&mut self, plateau_detection: PlateauDetection) {
+        assert!(
+            plateau_detection.window >= 3,
+            "plateau window must span at least three loops"
+        );
+        self.config.plateau_detection = Some(plateau_detection);
+    }
+
+    /// Re-evaluates candidate best points according to `resampling`. The result then reports the
+    /// mean of the best point's evaluations along with a confidence interval, see
+    /// `HypercubeOptimizerResult::get_best_f_interval`.
+    pub fn set_resampling(&mut self, resampling: Resampling) {
+        assert!(
+            resampling.samples >= 2,
+            "resampling needs at least two samples"
+        );
+        assert!(
+            resampling.confidence > 0.0 && resampling.confidence < 1.0,
+            "confidence must be between zero and one"
+        );
+        self.config.resampling = Some(resampling);
+    }
+
+    /// Normalizes objective function values with `output_normalization` before the optimizer uses
+    /// them for convergence checks and hypercube updates. Results still report raw values, while
+    /// `tol_f`, `rel_tol_f` and trust region success thresholds apply to normalized values.
+    pub fn set_output_normalization(&mut self, output_normalization: OutputNormalization) {
+        self.config.output_normalization = output_normalization;
+    }
+
+    /// Evaluates the objective function at `input_transform.to_native(x)` instead of at the points
+    /// `x` of the search space, so the hypercube can span a well-scaled space while the objective
+    /// receives its native parameters. The best points of results and of `best_handle` are
+    /// reported as native parameters; evaluation logs and events use search space coordinates.
+    pub fn set_input_transform(&mut self, input_transform: InputTransform) {
+        assert_eq!(
+            input_transform.get_dimension(),
+            self.dimension,
+            "transform dimension does not match problem dimension"
+        );
+        self.input_transform = Some(input_transform);
+    }
 
-            // compute normalized distance
-            let normalized_sqr_diff = &(&current_normalized - &previous_normalized)
-                * &(&current_normalized - &previous_normalized);
+    /// Projects every sampled population point with `repair` before it is evaluated, so no
+    /// evaluations are spent on points that violate the constraints of the problem. `repair` should
+    /// return the nearest feasible point; repaired points are kept inside the search space.
+    pub fn set_repair(&mut self, repair: impl Fn(&Point) -> Point + Send + Sync + 'static) {
+        self.repair = Some(Arc::new(repair));
+    }
+
+    /// Repairs sampled population points by clamping every coordinate between the corresponding
+    /// coordinates of `lower` and `upper`, for problems whose feasible region is a box narrower
+    /// than the search space along some dimensions
+    pub fn set_repair_bounds(&mut self, lower: Point, upper: Point) {
+        assert_eq!(
+            lower.dim(),
+            self.dimension,
+            "lower bound dimension does not match problem dimension"
+        );
+        assert_eq!(
+            upper.dim(),
+            self.dimension,
+            "upper bound dimension does not match problem dimension"
+        );
+        assert!(
+            lower.iter().zip(upper.iter()).all(|(l, u)| l <= u),
+            "lower bound cannot be larger than upper bound"
+        );
 
-            let sum_normalized_sqr_diff = normalized_sqr_diff.sum();
+        self.set_repair(move |point: &Point| {
+            Point::from_vec(
+                point
+                    .iter()
+                    .zip(lower.iter().zip(upper.iter()))
+                    .map(|(x, (l, u))| x.clamp(*l, *u))
+                    .collect(),
+            )
+        });
+    }
 
-            let normalized_distance =
-                sum_normalized_sqr_diff.powf(0.5) / self.hypercube.get_side_length();
+    /// Restricts every dimension to a grid of points `grid_steps` apart, starting at the lower
+    /// bound. A step of zero leaves that dimension continuous. Starting points and sampled points
+    /// are snapped to the nearest grid point inside the search space before they are evaluated
+    /// (after any repair), and the hypercube stops shrinking once its side is as small as the
+    /// largest step.
+    pub fn set_grid_steps(&mut self, grid_steps: Point) {
+        assert_eq!(
+            grid_steps.dim(),
+            self.dimension,
+            "grid steps dimension does not match problem dimension"
+        );
+        assert!(
+            grid_steps.iter().all(|s| *s >= 0.0),
+            "grid steps cannot be negative"
+        );
+        assert!(
+            grid_steps
+                .iter()
+                .all(|s| *s <= self.upper_bound - self.lower_bound),
+            "grid steps cannot be larger than the search space"
+        );
 
-            // compute renormalized distance
-            let renormalized_distance = normalized_distance / ((self.dimension as f64).sqrt());
+        self.grid_steps = Some(grid_steps);
+    }
 
-            // compute convergence factor
-            let convergence_factor =
-                HypercubeOptimizer::calculate_convergence(renormalized_distance);
+    /// Streams every successful evaluation to `eval_log`, so huge runs can be analyzed offline
+    /// without keeping their evaluations in memory. The log is flushed when every optimization
+    /// finishes.
+    pub fn set_eval_log(&mut self, eval_log: EvalLogWriter) {
+        assert_eq!(
+            eval_log.get_dimension(),
+            self.dimension,
+            "evaluation log dimension does not match problem dimension"
+        );
 
-            log::info!("hypercube convergence factor: {}", convergence_factor);
+        let eval_log = Arc::new(Mutex::new(eval_log));
+        self.hypercube.set_eval_log(Some(eval_log.clone()));
+        self.eval_log = Some(eval_log);
+    }
 
-            // <----- hypercube shrink ----->
+    /// Writes a checkpoint to `path` every `n_loops` loops, so long unattended runs can be resumed
+    /// with `set_resume_from` or `set_auto_resume` after being interrupted. Checkpoints cover the
+    /// optimization up to the first restart.
+    pub fn set_checkpoint_every(&mut self, n_loops: u32, path: impl Into<PathBuf>) {
+        assert_ne!(n_loops, 0, "checkpoint period cannot be zero");
 
-            let pre_shrink_size = self.hypercube.diagonal_len();
+        self.checkpointing = Some(Checkpointing {
+            every: n_loops,
+            path: path.into(),
+        });
+    }
 
-            self.hypercube.shrink(convergence_factor);
+    /// Writes the population of every loop and the evaluations of its points to its own file in
+    /// `dir`, numbered from `population_00000` in the order the loops ran, so the movement of the
+    /// hypercube can be animated. Points are in the search space of the optimizer, see
+    /// `set_input_transform`. Numbering starts over with every optimization.
+    pub fn set_population_snapshots(&mut self, dir: impl Into<PathBuf>, format: SnapshotFormat) {
+        self.population_snapshots = Some(PopulationSnapshots::new(dir.into(), format));
+    }
 
-            let post_shrink_size = self.hypercube.diagonal_len();
+    /// Uses the points in the file at `path` as the population of the first loop instead of random
+    /// points, e.g. a design of experiments or the points of an earlier run. The number of points
+    /// in the file becomes the population size. Files with a `.json` extension hold an array of
+    /// coordinate arrays, e.g. `[[0.5, 1.0], [2.0, -1.5]]`, any other file is read as CSV with one
+    /// point per line and an optional header line. Every point must lie inside the search space.
+    pub fn set_initial_population_file(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let search_space = HypercubeBounds::new(self.dimension, self.lower_bound, self.upper_bound);
+        let population = read_population(path, &search_space)?;
 
-            log::info!(
-                "shrunk hypercube from {} => {}",
-                pre_shrink_size,
-                post_shrink_size
-            );
+        self.population_too_large = false;
+        self.init_population_size = population.len() as u64;
+        self.hypercube.restart(self.init_population_size);
+        Self::fit_initial_hypercube(
+            &mut self.hypercube,
+            &self.init_point,
+            self.config.initial_scale,
+        );
 
-            // <----- hypercube displace ----->
+        self.initial_population = Some(population);
+        Ok(())
+    }
+
+    /// Names the objective function in the manifests of the results, e.g. `"neg_rastrigin"`
+    pub fn set_objective_name(&mut self, name: impl Into<String>) {
+        self.objective_name = Some(name.into());
+    }
+
+    /// Makes `maximize` continue from the file set with `set_checkpoint_every` if it holds a
+    /// checkpoint of the same problem and configuration
+    pub fn set_auto_resume(&mut self, auto_resume: bool) {
+        self.auto_resume = auto_resume;
+    }
 
-            log::trace!("attempting displacement to {:#?}", new_hypercube_center);
-            self.hypercube.displace_to(&new_hypercube_center);
+    /// Makes the next optimization continue from `checkpoint` instead of the initial point. Its
+    /// loops and evaluations count against the budgets of the optimization.
+    pub fn set_resume_from(&mut self, checkpoint: Checkpoint) {
+        assert_eq!(
+            checkpoint.get_dimension(),
+            self.dimension,
+            "checkpoint dimension does not match problem dimension"
+        );
+        assert!(
+            checkpoint.get_side_length() > 0.0,
+            "checkpoint side length must be positive"
+        );
 
-            log::trace!("new hypercube center is {:#?}", self.hypercube.get_center());
+        self.resume_from = Some(checkpoint);
+    }
 
-            previous_best_eval = current_best_eval;
+    /// Puts the optimizer back into the state it was created in, so that it can be reused for
+    /// another call to `maximize`. The hypercube returns to its initial bounds, center, and
+    /// population size, and the random number generator is reseeded with the configured seed, if
+    /// any. Settings such as callbacks, logs, and tolerances are kept.
+    pub fn reset(&mut self) {
+        self.hypercube.restart(self.init_population_size);
+        Self::fit_initial_hypercube(
+            &mut self.hypercube,
+            &self.init_point,
+            self.config.initial_scale,
+        );
 
-            // end loop:
+        if let Some(seed) = self.config.seed {
+            self.hypercube.set_seed(seed);
         }
+        self.next_seed = self.config.seed;
 
-        log::info!("final hypercube size: {}", self.hypercube.diagonal_len());
+        self.best.clear();
+        self.cancel.reset();
+        self.resume_from = None;
+    }
 
-        let best_value: Option<&PointEval> = best_evaluations.peek();
-        let time_elapsed  = start_time.elapsed();
+    /// `reset`, but with a new seed for the random number generator
+    pub fn reset_with_seed(&mut self, seed: u64) {
+        self.config.seed = Some(seed);
+        self.reset();
+    }
 
-        HypercubeOptimizerResult::new(0, self.max_loop, fn_eval, best_value, time_elapsed)
+    /// Returns a handle to the best value found so far that other threads can poll while the
+    /// optimization runs. The value is cleared at the start of every optimization.
+    pub fn best_handle(&self) -> BestHandle {
+        self.best.clone()
     }
 
-    /// Calculates the factor by which to shrink the hypercube during optimization
-    ///
-    /// # Arguments
-    ///
-    /// * `renormalized_distance` - the distance between the previous best and current best points
-    /// in the search space if they existed within a unit hypercube
-    fn calculate_convergence(renormalized_distance: f64) -> f64 {
-        let s = 1.0 - (0.2 * E.powf((-3.0 * renormalized_distance) as f32));
-        s as f64
+    /// Returns a handle that stops the running optimization from another thread or a signal
+    /// handler. The optimization finishes its current loop and returns the best value found so
+    /// far with exit code 6. Cancellations are withdrawn at the start of every optimization.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    pub fn get_config(&self) -> &HypercubeOptimizerConfig {
+        &self.config
+    }
+
+    /// Returns the number of points sampled in every loop
+    pub fn get_population_size(&self) -> u64 {
+        self.hypercube.get_population_size()
+    }
+
+    /// Uses an existing rayon thread pool to sample and evaluate the hypercube population instead
+    /// of the global pool. Useful when embedding the optimizer in applications that manage their
+    /// own CPU usage.
+    #[cfg(feature = "parallel")]
+    pub fn set_thread_pool(&mut self, thread_pool: Arc<ThreadPool>) {
+        self.hypercube.set_thread_pool(Some(thread_pool));
+    }
+
+    /// Limits the number of threads used to sample and evaluate the hypercube population by
+    /// giving the optimizer its own thread pool with `max_threads` threads.
+    #[cfg(feature = "parallel")]
+    pub fn set_max_threads(&mut self, max_threads: usize) -> Result<(), ThreadPoolBuildError> {
+        assert_ne!(max_threads, 0, "max_threads cannot be zero");
+
+        let thread_pool = ThreadPoolBuilder::new().num_threads(max_threads).build()?;
+        self.set_thread_pool(Arc::new(thread_pool));
+
+        Ok(())
+    }
+
+    pub fn maximize<F>(&mut self, obj_function: F) -> HypercubeOptimizerResult
+    where
+        F: Fn(&Point) -> f64 + MaybeSync,
+    {
+        self.maximize_with(&LocalBackend::new(obj_function))
+    }
+
+    /// Maximizes an objective function that returns auxiliary output along with its value, e.g. a
+    /// per-metric breakdown or the path of a model artifact. The auxiliary output of the best point
+    /// is reported by `HypercubeOptimizerResult::get_best_metadata`, so the objective doesn't have
+    /// to be evaluated again at the best point. It is only reported if the best point is exactly
+    /// the evaluated one, which is not the case when resampling replaces the best value or the
+    /// best point had to be moved back inside the bounds.
+    pub fn maximize_with_metadata<F, T>(&mut self, obj_function: F) -> HypercubeOptimizerResult
+    where
+        F: Fn(&Point) -> (f64, T) + MaybeSync,
+        T: Send + Sync + 'static,
+    {
+        let backend = MetadataBackend::new(obj_function);
+        let result = self.maximize_with(&backend);
+
+        let best_metadata = backend
+            .into_best()
+            .filter(|best| {
+                result.get_best_x() == Some(best.get_point())
+                    && result.get_best_f() == Some(best.get_eval())
+            })
+            .and_then(PointEval::into_metadata);
+
+        result.with_best_metadata(best_metadata)
+    }
+
+    /// Maximizes an objective function whose gradient is known. After the hypercube optimization
+    /// finishes, the best point is refined with L-BFGS inside the initial bounds using the
+    /// evaluations left in the `max_eval` budget. Smooth objectives typically gain several digits
+    /// of accuracy this way.
+    pub fn maximize_with_gradient<D>(&mut self, objective: &D) -> HypercubeOptimizerResult
+    where
+        D: DifferentiableObjective + Sync,
+    {
+        let result = self.maximize(|p: &Point| objective.value(p));
+
+        let (best_x, best_f) = match (result.get_best_x(), result.get_best_f()) {
+            (Some(best_x), Some(best_f)) => (best_x.clone(), best_f),
+            _ => return result,
+        };
+
+        // polish in the search space, where the bounds apply
+        let transform = self.input_transform.clone();
+        let best_x = match &transform {
+            Some(transform) => transform.to_internal(&best_x),
+            None => best_x,
+        };
+
+        // on a grid, one evaluation is kept to evaluate the polished point snapped to the grid
+        let snap_evals = self.grid_steps.is_some() as u32;
+        let remaining_evals = self
+            .config
+            .stopping
+            .max_eval
+            .saturating_sub(result.get_fn_evals() + snap_evals);
+
+        // a cancelled optimization returns right away
+        if remaining_evals == 0 || self.cancel.is_cancelled() {
+            return result;
+        }
+
+        let start_time = Instant::now();
+        let init_bounds = HypercubeBounds::new(self.dimension, self.lower_bound, self.upper_bound);
+
+        let start = PointEval::from_image(best_x, best_f);
+
+        let (polished, polish_evals) = match &transform {
+            Some(transform) => lbfgs::polish(
+                &TransformedObjective::new(objective, transform),
+                &start,
+                &init_bounds,
+                remaining_evals,
+            ),
+            None => lbfgs::polish(objective, &start, &init_bounds, remaining_evals),
+        };
+
+        let (polished, polish_evals) = if self.grid_steps.is_some() {
+            let snapped = self.snap(polished.get_point());
+            let image = match &transform {
+                Some(transform) => objective.value(&transform.to_native(&snapped)),
+                None => objective.value(&snapped),
+            };
+            let snapped = match PointEval::try_from_image(snapped, image) {
+                Some(snapped) => snapped.max(start),
+                None => start,
+            };
+
+            (snapped, polish_evals + snap_evals)
+        } else {
+            (polished, polish_evals)
+        };
+
+        if self.logs(Verbosity::Summary) {
+            log::info!(
+                "L-BFGS polish improved best eval from {} to {} in {} evaluations",
+                best_f,
+                polished.get_eval(),
+                polish_evals
+            );
+        }
+
+        self.offer_best(&polished);
+
+        let polished = match &transform {
+            Some(transform) => PointEval::from_image(
+                transform.to_native(polished.get_point()),
+                polished.get_eval(),
+            ),
+            None => polished,
+        };
+
+        result.with_refined_best(&polished, polish_evals, start_time.elapsed())
+    }
+
+    /// Maximizes the objective function evaluated by `backend`. This is the integration point for
+    /// evaluating the objective function somewhere other than the local machine.
+    pub fn maximize_with<B>(&mut self, backend: &B) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + MaybeSync + ?Sized,
+        B::Error: Send,
+    {
+        self.best.clear();
+        self.cancel.reset();
+        self.objective_hash = None;
+
+        if self.population_too_large {
+            log::error!(
+                "search space too large for the default population size, set one with \
+                 set_population_size"
+            );
+            return HypercubeOptimizerResult::new(4, 0, 0, None, Duration::ZERO);
+        }
+
+        // every optimization starts from a known seed, so that its manifest can reproduce it
+        let seed = self
+            .next_seed
+            .take()
+            .unwrap_or_else(|| self.hypercube.draw_seed());
+        self.hypercube.set_seed(seed);
+
+        if let Some(snapshots) = self.population_snapshots.as_mut() {
+            snapshots.rewind();
+        }
+
+        // the earliest of the deadline, the end of the time budget, and the timeout
+        let timeout_end =
+            Instant::now() + Duration::from_secs(self.config.stopping.max_timeout as u64);
+        let time_budget_end = self
+            .config
+            .time_budget
+            .map(|budget| Instant::now() + budget);
+        let deadline = [self.deadline, time_budget_end]
+            .into_iter()
+            .flatten()
+            .fold(timeout_end, Instant::min);
+
+        // a resumed optimization starts from the best point and hypercube of the checkpoint
+        let resume_from = self.resume_from.take().or_else(|| self.find_checkpoint());
+
+        let init_points = match &resume_from {
+            Some(checkpoint) => {
+                if self.logs(Verbosity::Summary) {
+                    log::info!(
+                        "resuming optimization after loop {}",
+                        checkpoint.get_loops()
+                    );
+                }
+                self.restore_hypercube(checkpoint);
+
+                vec![self.snap(checkpoint.get_best().get_point())]
+            }
+            None => {
+                let mut init_points = Vec::with_capacity(1 + self.initial_candidates.len());
+                init_points.push(self.snap(&self.init_point));
+                init_points.extend(self.initial_candidates.iter().map(|c| self.snap(c)));
+                init_points
+            }
+        };
+
+        // a resumed optimization depends on the run that wrote the checkpoint, so it cannot be
+        // reproduced from its manifest alone
+        let resumed = resume_from.is_some();
+
+        // the population file replaces the random population of the first loop unless resumed
+        self.pending_population = if resumed {
+            None
+        } else {
+            self.initial_population.clone()
+        };
+
+        let init_bounds = HypercubeBounds::new(self.dimension, self.lower_bound, self.upper_bound);
+
+        let result = match self.input_transform.clone() {
+            Some(transform) => {
+                let backend = TransformedBackend::new(backend, &transform);
+
+                self.run_with_restarts(&backend, init_points, deadline, resume_from.as_ref())
+                    .with_best_x_within(&init_bounds)
+                    .with_native_best_x(&transform)
+            }
+            None => self
+                .run_with_restarts(backend, init_points, deadline, resume_from.as_ref())
+                .with_best_x_within(&init_bounds),
+        };
+
+        if let Some(eval_log) = &self.eval_log {
+            if let Err(e) = eval_log.lock().unwrap().flush() {
+                log::warn!("failed to flush evaluation log: {}", e);
+            }
+        }
+
+        if resumed {
+            return result;
+        }
+
+        result.with_manifest(self.manifest(seed))
+    }
+
+    /// Runs the optimization from `init_points`, continuing the progress of `resumed` if any, then
+    /// restarts it according to the restart policy
+    fn run_with_restarts<B>(
+        &mut self,
+        backend: &B,
+        init_points: Vec<Point>,
+        deadline: Instant,
+        resumed: Option<&Checkpoint>,
+    ) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + MaybeSync + ?Sized,
+        B::Error: Send,
+    {
+        let progress = resumed.map_or((0, 0), |c| (c.get_loops(), c.get_fn_evals()));
+
+        let mut result = self
+            .run(
+                backend,
+                init_points,
+                StoppingCriteria {
+                    max_loop: self.config.stopping.max_loop.saturating_sub(progress.0),
+                    max_eval: self.config.stopping.max_eval.saturating_sub(progress.1),
+                    ..self.config.stopping.clone()
+                },
+                deadline,
+                Some(progress),
+            )
+            .with_resumed(progress.0, progress.1);
+
+        let restart_policy = match self.config.restart_policy.clone() {
+            Some(restart_policy) => restart_policy,
+            None => return result,
+        };
+
+        // a run converged if it stopped successfully before running out of loops
+        let mut converged =
+            result.get_exit_code() == 0 && result.get_loops() < self.config.stopping.max_loop;
+        let mut population_size = self.hypercube.get_population_size();
+
+        for restart in 1..=restart_policy.max_restarts {
+            if !converged {
+                break;
+            }
+
+            population_size = (population_size as f64 * restart_policy.population_growth) as u64;
+
+            if population_size > MAX_POPULATION_SIZE {
+                if self.logs(Verbosity::Summary) {
+                    log::info!("population of restart {} would be too large", restart);
+                }
+                break;
+            }
+
+            // the restart must at least evaluate its starting point and one population
+            let remaining_evals = self
+                .config
+                .stopping
+                .max_eval
+                .saturating_sub(result.get_fn_evals());
+            let affordable_loops = (remaining_evals.saturating_sub(1) as u64 / population_size)
+                .min(self.config.stopping.max_loop as u64)
+                as u32;
+
+            if Instant::now() >= deadline {
+                if self.logs(Verbosity::Summary) {
+                    log::info!("no time left for restart {}", restart);
+                }
+                break;
+            }
+
+            if affordable_loops == 0 {
+                if self.logs(Verbosity::Summary) {
+                    log::info!("not enough evaluations left for restart {}", restart);
+                }
+                break;
+            }
+
+            if self.logs(Verbosity::Summary) {
+                log::info!(
+                    "restart {} of {} with population size {}",
+                    restart,
+                    restart_policy.max_restarts,
+                    population_size
+                );
+            }
+
+            // start from a random point of the fresh hypercube to explore another basin
+            self.hypercube.restart(population_size);
+            self.emit(OptimizerEvent::Restarted {
+                restart,
+                population_size,
+            });
+            self.repair_population();
+            self.snap_population();
+            let starting_point = self.hypercube.get_population()[0].clone();
+
+            let restart_result = self.run(
+                backend,
+                vec![starting_point],
+                StoppingCriteria {
+                    max_loop: affordable_loops,
+                    max_eval: remaining_evals,
+                    ..self.config.stopping.clone()
+                },
+                deadline,
+                None,
+            );
+            converged = restart_result.get_exit_code() == 0
+                && restart_result.get_loops() < affordable_loops;
+
+            result = result.with_restart(restart_result);
+        }
+
+        result
+    }
+
+    /// Runs the optimization once within the budgets of `criteria` and until `deadline`, starting
+    /// from the best of `init_points`
+    fn run<B>(
+        &mut self,
+        backend: &B,
+        init_points: Vec<Point>,
+        criteria: StoppingCriteria,
+        deadline: Instant,
+        checkpoint_progress: Option<(u32, u32)>,
+    ) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + MaybeSync + ?Sized,
+        B::Error: Send,
+    {
+        // <----- Optimization result set-up ----->
+
+        let mut budget = Budget::start(&criteria);
+
+        // the hypercube is centered on the best starting point when there is a choice
+        let has_candidates = init_points.len() > 1;
+
+        let init_images = backend.evaluate_batch(&init_points);
+        budget.record_evals(init_points.len() as u32);
+
+        // the first run of an optimization identifies the objective function for the manifest
+        if self.objective_hash.is_none() {
+            let images: Vec<Option<f64>> = init_images
+                .iter()
+                .map(|i| i.as_ref().ok().copied())
+                .collect();
+            let objective_hash =
+                checkpoint::fingerprint(&format!("{:?} {:?}", init_points, images));
+            self.objective_hash = Some(objective_hash);
+
+            if self
+                .manifest_objective_hash
+                .is_some_and(|manifest_hash| manifest_hash != objective_hash)
+            {
+                log::warn!(
+                    "objective function differs from the one recorded in the manifest, the run \
+                     may differ"
+                );
+            }
+        }
+
+        // the best of the successfully evaluated starting points
+        let mut init_eval: Option<PointEval> = None;
+
+        for (point, image) in init_points.into_iter().zip(init_images) {
+            match image {
+                Ok(image) if !image.is_nan() => {
+                    self.log_evaluation(&point, image);
+
+                    let eval = PointEval::from_image(point, image);
+
+                    if init_eval.as_ref().is_none_or(|best| eval > *best) {
+                        init_eval = Some(eval);
+                    }
+                }
+                Ok(_) => log::warn!("evaluation of starting point {:?} returned NaN", point),
+                Err(e) => log::warn!("evaluation of starting point {:?} failed: {:?}", point, e),
+            }
+        }
+
+        let init_eval = match init_eval {
+            Some(init_eval) => init_eval,
+            None => {
+                log::error!(
+                    "optimization process terminated due to failed evaluation of every starting \
+                    point"
+                );
+                return HypercubeOptimizerResult::new(
+                    1,
+                    0,
+                    budget.fn_evals(),
+                    None,
+                    budget.elapsed(),
+                );
+            }
+        };
+
+        // the best point so far with the confidence interval of its value, when resampling
+        let mut incumbent: Option<(PointEval, ConfidenceInterval)> = None;
+
+        let init_eval = match &self.config.resampling {
+            // the starting point is only resampled if the evaluation budget allows it
+            Some(resampling) if resampling.samples <= budget.remaining_evals() => {
+                budget.record_evals(resampling.samples);
+
+                match self.resample(backend, init_eval.get_point(), resampling) {
+                    Some((resampled, interval)) => {
+                        incumbent = Some((resampled.clone(), interval));
+                        resampled
+                    }
+                    None => init_eval,
+                }
+            }
+            _ => init_eval,
+        };
+
+        self.offer_best(&init_eval);
+
+        // time spent in every phase of the optimization loops
+        let mut timings = PhaseTimings::default();
+
+        // TODO: compute no. of allowed hypercube evaluations from max_eval and number of points
+        // in hypercube
+
+        // control decisions are made on normalized images
+        let mut normalizer = OutputNormalizer::new(self.config.output_normalization);
+        normalizer.observe(init_eval.get_eval());
+
+        // keep track of average image
+        let mut average_f = normalizer.normalize(init_eval.get_eval());
+
+        let mut best_evaluations: BinaryHeap<PointEval> = BinaryHeap::new();
+
+        // records absolute change in F to compare with tolF
+        let mut abs_delta_f_vec = Vec::with_capacity(self.config.stagnation_window as usize);
+
+        // input tolerance along every dimension
+        let tol_x = match &self.config.tol_x_per_dimension {
+            Some(tol_x) => tol_x.clone(),
+            None => point![self.config.stopping.tol_x; self.dimension],
+        };
+
+        // normalized best evaluations of the most recent loops, for plateau detection
+        let mut recent_f: VecDeque<f64> = VecDeque::new();
+
+        // number of consecutive loops in which the best point moved by less than `tol_x` and its
+        // image improved by at most `tol_f`
+        let mut stagnant_x_loops = 0;
+
+        // distances between best points are scaled by the square root of the dimension weights
+        let weights = match &self.config.dimension_weights {
+            Some(weights) => weights.clone(),
+            None => point![1.0; self.dimension],
+        };
+        let sqrt_weights = Point::from_vec(weights.iter().map(|w| w.sqrt()).collect());
+
+        // per-loop snapshots of the optimization progress
+        let mut history: Vec<IterationRecord> = Vec::with_capacity(criteria.max_loop as usize);
+
+        let mut previous_best_eval = init_eval;
+
+        // learns which dimensions matter from the moves of the best point
+        let mut importance = DimensionImportance::new(self.dimension);
+
+        // duration of the previous population evaluation, used to predict whether the next one
+        // finishes before the deadline
+        let mut last_evaluation_duration = Duration::ZERO;
+
+        // every termination breaks out with its exit code and the number of loops run
+        let (exit_code, loops) = 'optimization: {
+            if self.reached_target(&previous_best_eval) {
+                self.emit(OptimizerEvent::TargetReached {
+                    best_f: previous_best_eval.get_eval(),
+                });
+
+                if self.logs(Verbosity::Summary) {
+                    log::info!("optimization process terminated due to reaching the target value");
+                }
+                break 'optimization (5, 0);
+            }
+
+            if has_candidates {
+                let geometry_start = Instant::now();
+                self.displace_hypercube(previous_best_eval.get_point());
+                timings.geometry += geometry_start.elapsed();
+            }
+
+            if self.logs(Verbosity::Summary) {
+                log::info!("initial hypercube size: {}", self.hypercube.diagonal_len());
+                log::info!(
+                    "initial hypercube population size: {}",
+                    self.hypercube.get_population_size()
+                );
+            }
+
+            // start optimization loop
+            loop {
+                let i = budget.loops();
+
+                // loops and evaluations done before this run count towards the checkpoint progress.
+                // Nothing is left to resume once the loop budget is used up.
+                if let Some((loops, fn_evals)) =
+                    checkpoint_progress.filter(|_| i > 0 && i < criteria.max_loop)
+                {
+                    let best = best_evaluations.peek().unwrap_or(&previous_best_eval);
+                    self.checkpoint(loops + i, fn_evals + budget.fn_evals(), best);
+                }
+
+                if Instant::now() + last_evaluation_duration > deadline {
+                    self.emit(OptimizerEvent::DeadlineReached { loop_index: i });
+
+                    if self.logs(Verbosity::Summary) {
+                        log::info!("optimization process terminated due to reaching the deadline");
+                    }
+                    break 'optimization (3, i);
+                }
+
+                if self.cancel.is_cancelled() {
+                    self.emit(OptimizerEvent::Cancelled { loop_index: i });
+
+                    if self.logs(Verbosity::Summary) {
+                        log::info!("optimization process terminated due to cancellation");
+                    }
+                    break 'optimization (6, i);
+                }
+
+                // a population too large to count never fits in the evaluation budget
+                let population_evals =
+                    u32::try_from(self.hypercube.get_population_size()).unwrap_or(u32::MAX);
+
+                // resampling the loop best costs evaluations on top of the population
+                let loop_evals = population_evals
+                    .saturating_add(self.config.resampling.as_ref().map_or(0, |r| r.samples));
+
+                if let Some(exit_code) = budget.check(loop_evals) {
+                    if self.logs(Verbosity::Summary) {
+                        if exit_code == 3 {
+                            log::info!(
+                                "optimization process terminated due to reaching the timeout"
+                            );
+                        } else if i >= criteria.max_loop {
+                            log::info!("final hypercube size: {}", self.hypercube.diagonal_len());
+                            log::warn!(
+                                "optimization process did not converge within the loop budget"
+                            );
+                        } else {
+                            log::warn!(
+                                "optimization process terminated due to reaching the evaluation budget"
+                            );
+                        }
+                    }
+                    break 'optimization (exit_code, i);
+                }
+
+                budget.record_loop();
+
+                self.emit(OptimizerEvent::LoopStarted { loop_index: i });
+
+                // <----- hypercube randomize ----->
+
+                let sampling_start = Instant::now();
+                // Gaussian sampling draws around the best point so far
+                let best = best_evaluations.peek().unwrap_or(&previous_best_eval);
+                self.hypercube.set_sampling_focus(best.get_point().clone());
+                match self.pending_population.take() {
+                    Some(population) => self.hypercube.set_population(population),
+                    None => self.hypercube.randomize_pop(),
+                }
+                self.repair_population();
+                self.snap_population();
+                timings.sampling += sampling_start.elapsed();
+
+                // <----- hypercube evaluation ----->
+
+                let evaluation_start = Instant::now();
+                let images = self.hypercube.evaluate_images_with(backend);
+                last_evaluation_duration = evaluation_start.elapsed();
+                timings.evaluation += last_evaluation_duration;
+                budget.record_evals(
+                    u32::try_from(self.hypercube.get_population_size()).unwrap_or(u32::MAX),
+                );
+
+                if let Some(snapshots) = self.population_snapshots.as_mut() {
+                    if let Err(e) = snapshots.write(self.hypercube.get_population(), &images) {
+                        log::warn!("failed to write population snapshot: {}", e);
+                    }
+                }
+
+                self.emit(OptimizerEvent::Evaluated {
+                    loop_index: i,
+                    fn_evals: budget.fn_evals(),
+                    loop_best_f: self.hypercube.peek_best().map(|(_, image)| image),
+                });
+
+                // get best eval from current hypercube evaluation
+                let current_best_eval = match self.hypercube.peek_best_value() {
+                    Some(best_eval) => best_eval,
+                    None => {
+                        log::warn!("no population point evaluated successfully in loop {}", i);
+                        continue;
+                    }
+                };
+
+                // with resampling, the loop best only counts as an improvement if its confidence
+                // interval lies above the incumbent's
+                let mut significant = true;
+
+                let current_best_eval = match &self.config.resampling {
+                    Some(resampling) => {
+                        let resampling_start = Instant::now();
+                        let resampled =
+                            self.resample(backend, current_best_eval.get_point(), resampling);
+                        timings.evaluation += resampling_start.elapsed();
+                        budget.record_evals(resampling.samples);
+
+                        match resampled {
+                            Some((resampled, interval))
+                                if incumbent
+                                    .as_ref()
+                                    .is_none_or(|(_, best)| interval.lower > best.upper) =>
+                            {
+                                incumbent = Some((resampled.clone(), interval));
+                                resampled
+                            }
+                            _ => {
+                                significant = false;
+
+                                match &incumbent {
+                                    Some((best, _)) => best.clone(),
+                                    None => previous_best_eval.clone(),
+                                }
+                            }
+                        }
+                    }
+                    None => current_best_eval,
+                };
+
+                self.offer_best(&current_best_eval);
+
+                if current_best_eval > previous_best_eval {
+                    best_evaluations.push(current_best_eval.clone());
+                } else {
+                    best_evaluations.push(previous_best_eval.clone())
+                }
+
+                history.push(IterationRecord {
+                    loop_index: i,
+                    fn_evals: budget.fn_evals(),
+                    best_f: best_evaluations.peek().unwrap().get_eval(),
+                    diagonal_len: self.hypercube.diagonal_len(),
+                });
+
+                if self.reached_target(best_evaluations.peek().unwrap()) {
+                    self.emit(OptimizerEvent::TargetReached {
+                        best_f: best_evaluations.peek().unwrap().get_eval(),
+                    });
+
+                    if self.logs(Verbosity::Summary) {
+                        log::info!(
+                            "optimization process terminated due to reaching the target value"
+                        );
+                    }
+                    break 'optimization (5, i + 1);
+                }
+
+                normalizer.observe(current_best_eval.get_eval());
+                let current_f = normalizer.normalize(current_best_eval.get_eval());
+                let previous_f = normalizer.normalize(previous_best_eval.get_eval());
+
+                // calculate difference between previous best and current best
+                let abs_delta_f = (current_f - previous_f).abs();
+
+                // relative change, guarded against division by zero
+                let rel_delta_f = abs_delta_f / previous_f.abs().max(f64::EPSILON);
+
+                if let Some(plateau) = &self.config.plateau_detection {
+                    recent_f.push_back(current_f);
+
+                    if recent_f.len() > plateau.window as usize {
+                        recent_f.pop_front();
+                    }
+
+                    // if the recent loops show no significant improvement, break optimization loop
+                    if recent_f.len() == plateau.window as usize
+                        && mann_kendall_z(recent_f.make_contiguous()) < plateau.z_threshold
+                    {
+                        self.emit(OptimizerEvent::Converged { loop_index: i });
+
+                        if self.logs(Verbosity::Summary) {
+                            log::warn!("optimization process terminated due to an image plateau");
+                        }
+                        break 'optimization (0, i);
+                    }
+                } else if abs_delta_f <= self.config.stopping.tol_f
+                    || rel_delta_f <= self.config.rel_tol_f
+                {
+                    abs_delta_f_vec.push(abs_delta_f);
+
+                    // if the delta_f is within the tolerance for the whole stagnation window, break
+                    // optimization loop
+                    if abs_delta_f_vec.len() >= self.config.stagnation_window as usize {
+                        self.emit(OptimizerEvent::Converged { loop_index: i });
+
+                        if self.logs(Verbosity::Summary) {
+                            log::warn!("optimization process terminated due to image convergence");
+                        }
+                        break 'optimization (0, i);
+                    }
+                } else {
+                    abs_delta_f_vec.clear();
+                }
+
+                let displacement = &(current_best_eval.get_point()
+                    - previous_best_eval.get_point())
+                    * &sqrt_weights;
+
+                // a best point that barely moves while its image keeps improving is still making
+                // progress, e.g. on a steep objective
+                if current_f - previous_f <= self.config.stopping.tol_f
+                    && displacement
+                        .iter()
+                        .zip(tol_x.iter())
+                        .all(|(delta, tol)| delta.abs() <= *tol)
+                {
+                    stagnant_x_loops += 1;
+
+                    // if the best point barely moved for the whole stagnation window, break
+                    // optimization loop
+                    if stagnant_x_loops >= self.config.stagnation_window {
+                        self.emit(OptimizerEvent::Converged { loop_index: i });
+
+                        if self.logs(Verbosity::Summary) {
+                            log::warn!("optimization process terminated due to input convergence");
+                        }
+                        break 'optimization (0, i);
+                    }
+                } else {
+                    stagnant_x_loops = 0;
+                }
+
+                // calculate new average
+                average_f = average_f + ((current_f - average_f) / ((i + 1) as f64));
+
+                // <----- dimension importance ----->
+
+                if current_best_eval > previous_best_eval {
+                    importance.record(
+                        previous_best_eval.get_point(),
+                        current_best_eval.get_point(),
+                        self.hypercube.get_side_length(),
+                    );
+
+                    if self.config.importance_sampling {
+                        self.hypercube.set_axis_scales(importance.sampling_scales());
+                    }
+                }
+
+                if let ShrinkStrategy::TrustRegion {
+                    expansion,
+                    contraction,
+                    success_threshold,
+                } = self.config.shrink_strategy
+                {
+                    let improvement = current_f - previous_f;
+                    let geometry_start = Instant::now();
+
+                    if improvement > success_threshold {
+                        if self.logs(Verbosity::Loops) {
+                            log::info!("successful loop {}, expanding hypercube", i);
+                        }
+
+                        self.expand_hypercube(expansion);
+                        self.displace_hypercube(current_best_eval.get_point());
+                        previous_best_eval = current_best_eval;
+                    } else {
+                        if self.logs(Verbosity::Loops) {
+                            log::info!("failed loop {}, contracting hypercube", i);
+                        }
+
+                        self.shrink_hypercube(contraction);
+                        self.displace_hypercube(previous_best_eval.get_point());
+                    }
+
+                    timings.geometry += geometry_start.elapsed();
+
+                    if self.logs(Verbosity::Loops) {
+                        log::info!("hypercube size: {}", self.hypercube.diagonal_len());
+                    }
+
+                    continue;
+                }
+
+                // if current best is worse than average best value skip hypercube displacement and shrink
+                if !significant || current_f < average_f || current_best_eval < previous_best_eval {
+                    continue;
+                } else {
+                    if self.logs(Verbosity::Loops) {
+                        log::info!(
+                            "--------------- loop {} of {} ---------------",
+                            i,
+                            criteria.max_loop
+                        );
+                        log::info!("current best eval: {}", current_best_eval);
+                        log::info!("previous best eval: {}", previous_best_eval);
+                    }
+                }
+
+                // <----- hypercube displace preparation ----->
+
+                // compute new hypercube center (will be the average of old and new best value)
+                let temp = current_best_eval.get_point() + previous_best_eval.get_point();
+                let new_hypercube_center = temp.scale(0.5);
+
+                // <----- hypercube shrink preparation ----->
+
+                // compute X_n
+                let previous_normalized = (previous_best_eval.get_point()
+                    - self.hypercube.get_center())
+                .scale(1.0 / self.hypercube.get_side_length());
+
+                // compute X_min_n
+                let current_normalized = (current_best_eval.get_point()
+                    - self.hypercube.get_center())
+                .scale(1.0 / self.hypercube.get_side_length());
+
+                // compute normalized distance, weighing every dimension
+                let weighted_diff = &(&current_normalized - &previous_normalized) * &sqrt_weights;
+                let normalized_sqr_diff = &weighted_diff * &weighted_diff;
+
+                let sum_normalized_sqr_diff = normalized_sqr_diff.sum();
+
+                let normalized_distance =
+                    sum_normalized_sqr_diff.powf(0.5) / self.hypercube.get_side_length();
+
+                // compute renormalized distance
+                let renormalized_distance = normalized_distance / weights.sum().sqrt();
+
+                // compute convergence factor
+                let convergence_factor =
+                    HypercubeOptimizer::calculate_convergence(renormalized_distance);
+
+                if self.logs(Verbosity::Loops) {
+                    log::info!("hypercube convergence factor: {}", convergence_factor);
+                }
+
+                // <----- hypercube shrink ----->
+
+                let pre_shrink_size = self.hypercube.diagonal_len();
+
+                let geometry_start = Instant::now();
+                self.shrink_hypercube(convergence_factor);
+                timings.geometry += geometry_start.elapsed();
+
+                let post_shrink_size = self.hypercube.diagonal_len();
+
+                if self.logs(Verbosity::Loops) {
+                    log::info!(
+                        "shrunk hypercube from {} => {}",
+                        pre_shrink_size,
+                        post_shrink_size
+                    );
+                }
+
+                // <----- hypercube displace ----->
+
+                if self.logs(Verbosity::Detailed) {
+                    log::trace!("attempting displacement to {:#?}", new_hypercube_center);
+                }
+                let geometry_start = Instant::now();
+                self.displace_hypercube(&new_hypercube_center);
+                timings.geometry += geometry_start.elapsed();
+
+                if self.logs(Verbosity::Detailed) {
+                    log::trace!("new hypercube center is {:#?}", self.hypercube.get_center());
+                }
+
+                previous_best_eval = current_best_eval;
+
+                // end loop:
+            }
+        };
+
+        // the starting point is the best value if no loop finished yet
+        let best_value = best_evaluations.peek().or(Some(&previous_best_eval));
+
+        HypercubeOptimizerResult::new(
+            exit_code,
+            loops,
+            budget.fn_evals(),
+            best_value,
+            budget.elapsed(),
+        )
+        .with_history(history)
+        .with_dimension_importance(importance.weights())
+        .with_stagnation(Stagnation {
+            window: self.config.stagnation_window,
+            stagnant_loops: abs_delta_f_vec.len() as u32,
+        })
+        .with_phase_timings(timings)
+        .with_best_f_interval(incumbent.map(|(_, interval)| interval))
+    }
+
+    /// Shrinks a hypercube spanning the whole search space by `initial_scale` and centers it on
+    /// `init_point` as far as the search space allows
+    fn fit_initial_hypercube(hypercube: &mut Hypercube, init_point: &Point, initial_scale: f64) {
+        if initial_scale < 1.0 {
+            hypercube.shrink(initial_scale);
+            hypercube.displace_to(init_point);
+        }
+    }
+
+    /// Replaces the population with its repaired points if a repair is set
+    fn repair_population(&mut self) {
+        let repair = match &self.repair {
+            Some(repair) => repair,
+            None => return,
+        };
+
+        let init_bounds = HypercubeBounds::new(self.dimension, self.lower_bound, self.upper_bound);
+
+        let repaired = self
+            .hypercube
+            .get_population()
+            .iter()
+            .map(|point| repair(point).clamp(&init_bounds))
+            .collect();
+
+        self.hypercube.set_population(repaired);
+    }
+
+    /// Moves `point` to the nearest grid point inside the search space if grid steps are set
+    fn snap(&self, point: &Point) -> Point {
+        let grid_steps = match &self.grid_steps {
+            Some(grid_steps) => grid_steps,
+            None => return point.clone(),
+        };
+
+        let range = self.upper_bound - self.lower_bound;
+
+        Point::from_vec(
+            point
+                .iter()
+                .zip(grid_steps.iter())
+                .map(|(x, step)| {
+                    if *step == 0.0 {
+                        return *x;
+                    }
+
+                    // index of the nearest grid point, without stepping past the upper bound
+                    let max_index = (range / step).floor();
+                    let index = ((x - self.lower_bound) / step)
+                        .round()
+                        .clamp(0.0, max_index);
+
+                    self.lower_bound + index * step
+                })
+                .collect(),
+        )
+    }
+
+    /// Snaps every population point to the grid if grid steps are set
+    fn snap_population(&mut self) {
+        if self.grid_steps.is_none() {
+            return;
+        }
+
+        let snapped = self
+            .hypercube
+            .get_population()
+            .iter()
+            .map(|point| self.snap(point))
+            .collect();
+
+        self.hypercube.set_population(snapped);
+    }
+
+    /// Evaluates `point` `resampling.samples` times and returns the mean of the successful
+    /// evaluations with its Student's t confidence interval, or `None` if fewer than two succeeded
+    fn resample<B>(
+        &self,
+        backend: &B,
+        point: &Point,
+        resampling: &Resampling,
+    ) -> Option<(PointEval, ConfidenceInterval)>
+    where
+        B: EvaluationBackend + ?Sized,
+    {
+        let points = vec![point.clone(); resampling.samples as usize];
+        let mut images = Vec::with_capacity(points.len());
+
+        for image in backend.evaluate_batch(&points) {
+            match image {
+                Ok(image) if !image.is_nan() => {
+                    self.log_evaluation(point, image);
+                    images.push(image);
+                }
+                Ok(_) => log::warn!("resampling of point {:?} returned NaN", point),
+                Err(e) => log::warn!("resampling of point {:?} failed: {:?}", point, e),
+            }
+        }
+
+        if images.len() < 2 {
+            return None;
+        }
+
+        let n = images.len() as f64;
+        let mean = images.iter().sum::<f64>() / n;
+        let variance = images.iter().map(|f| (f - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+        let t = student_t_quantile(
+            1.0 - (1.0 - resampling.confidence) / 2.0,
+            images.len() as u32 - 1,
+        );
+        let half_width = t * (variance / n).sqrt();
+
+        let interval = ConfidenceInterval {
+            mean,
+            lower: mean - half_width,
+            upper: mean + half_width,
+            confidence: resampling.confidence,
+        };
+
+        Some((PointEval::from_image(point.clone(), mean), interval))
+    }
+
+    /// Writes a checkpoint if checkpointing is set and `loops` is a multiple of its period
+    fn checkpoint(&self, loops: u32, fn_evals: u32, best: &PointEval) {
+        let checkpointing = match &self.checkpointing {
+            Some(checkpointing) => checkpointing,
+            None => return,
+        };
+
+        if !loops.is_multiple_of(checkpointing.every) {
+            return;
+        }
+
+        let checkpoint = Checkpoint::new(
+            self.checkpoint_fingerprint(),
+            loops,
+            fn_evals,
+            best.clone(),
+            self.hypercube.get_center().clone(),
+            self.hypercube.get_side_length(),
+        );
+
+        if let Err(e) = checkpoint.write(&checkpointing.path) {
+            log::warn!("failed to write checkpoint: {}", e);
+        }
+    }
+
+    /// Sets the loop budget of the next optimizations, counting the loops of a resumed checkpoint
+    pub(crate) fn set_max_loop(&mut self, max_loop: u32) {
+        self.config.stopping.max_loop = max_loop;
+    }
+
+    /// Returns a checkpoint of where the optimization that returned `result` stopped, so it can be
+    /// continued with `set_resume_from`, or `None` if nothing was evaluated successfully
+    pub(crate) fn checkpoint_after(&self, result: &HypercubeOptimizerResult) -> Option<Checkpoint> {
+        let best_x = match &self.input_transform {
+            Some(transform) => transform.to_internal(result.get_best_x()?),
+            None => result.get_best_x()?.clone(),
+        };
+
+        Some(Checkpoint::new(
+            self.checkpoint_fingerprint(),
+            result.get_loops(),
+            result.get_fn_evals(),
+            PointEval::from_image(best_x, result.get_best_f()?),
+            self.hypercube.get_center().clone(),
+            self.hypercube.get_side_length(),
+        ))
+    }
+
+    /// Reads the checkpoint file if auto resume is on and the file belongs to this optimization
+    fn find_checkpoint(&self) -> Option<Checkpoint> {
+        if !self.auto_resume {
+            return None;
+        }
+
+        let path = &self.checkpointing.as_ref()?.path;
+
+        if !path.exists() {
+            return None;
+        }
+
+        match Checkpoint::read(path) {
+            Ok(checkpoint) if checkpoint.get_fingerprint() == self.checkpoint_fingerprint() => {
+                Some(checkpoint)
+            }
+            Ok(_) => {
+                log::warn!("ignoring checkpoint {:?} of another optimization", path);
+                None
+            }
+            Err(e) => {
+                log::warn!("failed to read checkpoint {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Identifies the problem and configuration of the optimization, so checkpoints are only
+    /// resumed automatically by the optimization they were taken for
+    fn checkpoint_fingerprint(&self) -> u64 {
+        checkpoint::fingerprint(&format!(
+            "{:?} {:?} {} {} {:?}",
+            self.init_point,
+            self.initial_candidates,
+            self.lower_bound,
+            self.upper_bound,
+            self.config
+        ))
+    }
+
+    /// Moves and resizes the hypercube to where it was when `checkpoint` was taken
+    fn restore_hypercube(&mut self, checkpoint: &Checkpoint) {
+        self.hypercube.restart(self.hypercube.get_population_size());
+
+        let factor = checkpoint.get_side_length() / self.hypercube.get_side_length();
+
+        if factor < 1.0 {
+            self.hypercube.shrink(factor);
+        }
+        self.hypercube.displace_to(checkpoint.get_center());
+    }
+
+    /// Records the problem, the configuration, and the `seed` the current optimization started from
+    fn manifest(&self, seed: u64) -> RunManifest {
+        RunManifest {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            seed,
+            config: self.config.clone(),
+            init_point: self.init_point.clone(),
+            initial_candidates: self.initial_candidates.clone(),
+            lower_bound: self.lower_bound,
+            upper_bound: self.upper_bound,
+            init_population_size: self.init_population_size,
+            input_transform: self.input_transform.clone(),
+            grid_steps: self.grid_steps.clone(),
+            objective_name: self.objective_name.clone(),
+            objective_hash: self.objective_hash,
+        }
+    }
+
+    /// Offers `best`, a point of the search space, to the best handle as native parameters
+    fn offer_best(&self, best: &PointEval) {
+        match &self.input_transform {
+            Some(transform) => {
+                let native = transform.to_native(best.get_point());
+                self.best
+                    .offer(&PointEval::from_image(native, best.get_eval()));
+            }
+            None => self.best.offer(best),
+        }
+    }
+
+    /// Checks whether `best` reaches the target value set with `set_stop_at_value`
+    fn reached_target(&self, best: &PointEval) -> bool {
+        self.config
+            .stop_at_value
+            .is_some_and(|target| best.get_eval() >= target)
+    }
+
+    /// Reports `event` to the observer and the log
+    fn emit(&mut self, event: OptimizerEvent) {
+        if self.config.log_events {
+            log::debug!("{:?}", event);
+        }
+
+        if let Some(observer) = &self.observer {
+            observer.lock().unwrap().notify(&event);
+        }
+    }
+
+    /// Shrinks the hypercube by `factor`
+    fn shrink_hypercube(&mut self, factor: f64) {
+        // a hypercube narrower than a grid step would only ever sample a single grid point
+        let factor = match &self.grid_steps {
+            Some(grid_steps) => {
+                let min_side = grid_steps.iter().copied().fold(0.0, f64::max);
+                factor
+                    .max(min_side / self.hypercube.get_side_length())
+                    .min(1.0)
+            }
+            None => factor,
+        };
+
+        let from = self.hypercube.diagonal_len();
+        self.hypercube.shrink(factor);
+        let to = self.hypercube.diagonal_len();
+
+        self.emit(OptimizerEvent::Shrunk { from, to });
+    }
+
+    /// Expands the hypercube by `factor`
+    fn expand_hypercube(&mut self, factor: f64) {
+        let from = self.hypercube.diagonal_len();
+        self.hypercube.expand(factor);
+        let to = self.hypercube.diagonal_len();
+
+        self.emit(OptimizerEvent::Expanded { from, to });
+    }
+
+    /// Moves the hypercube towards `destination`, staying within the search space
+    fn displace_hypercube(&mut self, destination: &Point) {
+        self.hypercube.displace_to(destination);
+
+        let to = self.hypercube.get_center().clone();
+        self.emit(OptimizerEvent::Displaced { to });
+    }
+
+    /// Checks whether messages of the given verbosity are logged
+    fn logs(&self, verbosity: Verbosity) -> bool {
+        self.config.verbosity >= verbosity
+    }
+
+    /// Appends an evaluation made outside of the hypercube to the evaluation log, if any
+    fn log_evaluation(&self, point: &Point, image: f64) {
+        if let Some(eval_log) = &self.eval_log {
+            if let Err(e) = eval_log.lock().unwrap().append(point, image) {
+                log::warn!("failed to append to evaluation log: {}", e);
+            }
+        }
+    }
+
+    /// Calculates the factor by which to shrink the hypercube during optimization
+    ///
+    /// # Arguments
+    ///
+    /// * `renormalized_distance` - the distance between the previous best and current best points
+    ///   in the search space if they existed within a unit hypercube
+    fn calculate_convergence(renormalized_distance: f64) -> f64 {
+        let s = 1.0 - (0.2 * E.powf((-3.0 * renormalized_distance) as f32));
+        s as f64
+    }
+}
+
+impl Clone for HypercubeOptimizer {
+    fn clone(&self) -> Self {
+        Self {
+            dimension: self.dimension,
+            init_point: self.init_point.clone(),
+            initial_candidates: self.initial_candidates.clone(),
+            hypercube: self.hypercube.clone(),
+            init_population_size: self.init_population_size,
+            config: self.config.clone(),
+            lower_bound: self.lower_bound,
+            upper_bound: self.upper_bound,
+            // a clone optimizes on its own, so it reports and gets cancelled on its own
+            best: BestHandle::new(),
+            cancel: CancelHandle::new(),
+            eval_log: self.eval_log.clone(),
+            observer: self.observer.clone(),
+            deadline: self.deadline,
+            input_transform: self.input_transform.clone(),
+            repair: self.repair.clone(),
+            grid_steps: self.grid_steps.clone(),
+            checkpointing: self.checkpointing.clone(),
+            auto_resume: self.auto_resume,
+            resume_from: self.resume_from.clone(),
+            population_snapshots: self.population_snapshots.clone(),
+            initial_population: self.initial_population.clone(),
+            pending_population: self.pending_population.clone(),
+            next_seed: self.next_seed,
+            objective_name: self.objective_name.clone(),
+            objective_hash: self.objective_hash,
+            manifest_objective_hash: self.manifest_objective_hash,
+            population_too_large: self.population_too_large,
+        }
+    }
+}
+
+// optimizers can be moved to and shared between threads
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync + Clone>() {}
+    assert_send_sync::<HypercubeOptimizer>();
+    assert_send_sync::<HypercubeOptimizerConfig>();
+};
+
+impl Optimizer for HypercubeOptimizer {
+    fn maximize(&mut self, obj_function: &Objective) -> HypercubeOptimizerResult {
+        HypercubeOptimizer::maximize(self, obj_function)
     }
 }
@@ -0,0 +1,379 @@
+use std::collections::VecDeque;
+#[cfg(feature = "parallel")]
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::ThreadPool;
+
+use crate::backend::{EvaluationBackend, LocalBackend, MaybeSync, Objective};
+use crate::bounds::HypercubeBounds;
+use crate::evaluation::PointEval;
+use crate::hypercube::Hypercube;
+use crate::optimizer::Optimizer;
+use crate::point::Point;
+use crate::result::{HypercubeOptimizerResult, IterationRecord};
+use crate::stopping::{Budget, StoppingCriteria};
+
+/// Number of iterations over which the improvement of the global best is compared to `tol_f`
+const IMPROVEMENT_WINDOW: usize = 30;
+
+/// State of a particle besides its position, which lives in the population of the swarm
+struct Particle {
+    /// step the particle moves by every iteration
+    velocity: Point,
+
+    /// best position the particle has visited, `None` until it is evaluated successfully
+    best: Option<PointEval>,
+}
+
+/// Particle swarm optimizer. Particle positions live in the population of a `Hypercube` spanning
+/// the search space, so the swarm is sampled and evaluated in parallel the same way as the
+/// hypercube algorithm. Every iteration, each particle is pulled towards its own best position and
+/// the best position of the whole swarm.
+pub struct ParticleSwarm {
+    /// dimension of the optimization problem
+    dimension: u32,
+
+    /// position of the first particle
+    init_point: Point,
+
+    /// bounds of the search space
+    bounds: HypercubeBounds,
+
+    /// number of particles in the swarm
+    swarm_size: u64,
+
+    /// fraction of the previous velocity kept every iteration
+    inertia: f64,
+
+    /// attraction towards the particle's own best position
+    cognitive: f64,
+
+    /// attraction towards the best position of the swarm
+    social: f64,
+
+    /// conditions that terminate the optimization
+    criteria: StoppingCriteria,
+
+    /// thread pool used to sample and evaluate the swarm
+    #[cfg(feature = "parallel")]
+    thread_pool: Option<Arc<ThreadPool>>,
+
+    /// seed of the swarm's random number generators
+    seed: Option<u64>,
+}
+
+impl ParticleSwarm {
+    /// Returns a new `ParticleSwarm` optimizer with the constriction coefficients of Clerc and
+    /// Kennedy (inertia 0.7298, cognitive and social weights 1.49618)
+    ///
+    /// # Arguments
+    ///
+    /// * `init_point` - the position of the first particle. The remaining particles are placed
+    ///   randomly inside the search space.
+    /// * `lower_bound` - the lower bound of the search space
+    /// * `upper_bound` - the upper bound of the search space
+    /// * `criteria` - conditions that terminate the optimization. `tol_x` is compared against the
+    ///   largest distance between a particle and the best position and `tol_f` against the
+    ///   improvement of the best value over the last 30 iterations.
+    ///
+    pub fn new(
+        init_point: Point,
+        lower_bound: f64,
+        upper_bound: f64,
+        criteria: StoppingCriteria,
+    ) -> Self {
+        assert!(
+            upper_bound > lower_bound,
+            "upper bound not strictly larger than lower bound"
+        );
+        assert!(
+            init_point.max_val().unwrap() <= upper_bound,
+            "init_point not inside upper bound"
+        );
+        assert!(
+            init_point.min_val().unwrap() >= lower_bound,
+            "init_point not inside lower bound"
+        );
+
+        let dimension = init_point.dim();
+
+        Self {
+            dimension,
+            bounds: HypercubeBounds::new(dimension, lower_bound, upper_bound),
+            init_point,
+            swarm_size: 10 + (2.0 * (dimension as f64).sqrt()) as u64,
+            inertia: 0.7298,
+            cognitive: 1.49618,
+            social: 1.49618,
+            criteria,
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
+            seed: None,
+        }
+    }
+
+    /// Sets the number of particles in the swarm. Defaults to `10 + 2 sqrt(n)`.
+    pub fn set_swarm_size(&mut self, swarm_size: u64) {
+        assert!(swarm_size >= 2, "swarm size must be at least 2");
+        self.swarm_size = swarm_size;
+    }
+
+    /// Sets the inertia, cognitive, and social coefficients of the velocity update
+    pub fn set_coefficients(&mut self, inertia: f64, cognitive: f64, social: f64) {
+        assert!(inertia >= 0.0, "inertia cannot be negative");
+        assert!(cognitive >= 0.0, "cognitive coefficient cannot be negative");
+        assert!(social >= 0.0, "social coefficient cannot be negative");
+
+        self.inertia = inertia;
+        self.cognitive = cognitive;
+        self.social = social;
+    }
+
+    /// Seeds the random number generators used to place and move the particles
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    /// Sets the thread pool used to sample and evaluate the swarm. When no pool is set, the
+    /// global rayon thread pool is used.
+    #[cfg(feature = "parallel")]
+    pub fn set_thread_pool(&mut self, thread_pool: Option<Arc<ThreadPool>>) {
+        self.thread_pool = thread_pool;
+    }
+
+    /// Maximizes the objective function evaluated by `backend`
+    pub fn maximize_with<B>(&mut self, backend: &B) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + MaybeSync + ?Sized,
+        B::Error: Send,
+    {
+        let n = self.dimension as usize;
+        let side_length = self.bounds.get_length();
+        let max_velocity = 0.2 * side_length;
+
+        let mut budget = Budget::start(&self.criteria);
+        let mut history: Vec<IterationRecord> = Vec::new();
+
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        // <----- initialize swarm ----->
+
+        let mut swarm = Hypercube::with_population_size(
+            self.dimension,
+            *self.bounds.get_lower().get(0).unwrap(),
+            *self.bounds.get_upper().get(0).unwrap(),
+            self.swarm_size,
+        );
+        #[cfg(feature = "parallel")]
+        swarm.set_thread_pool(self.thread_pool.clone());
+
+        if let Some(seed) = self.seed {
+            swarm.set_seed(seed);
+            swarm.randomize_pop();
+        }
+
+        let mut positions = swarm.get_population().to_vec();
+        positions[0] = self.init_point.clone();
+        swarm.set_population(positions);
+
+        let mut particles: Vec<Particle> = (0..self.swarm_size)
+            .map(|_| Particle {
+                velocity: (0..n)
+                    .map(|_| rng.gen_range(-max_velocity..=max_velocity))
+                    .collect(),
+                best: None,
+            })
+            .collect();
+
+        let mut global_best: Option<PointEval> = None;
+        let mut recent_best: VecDeque<f64> = VecDeque::with_capacity(IMPROVEMENT_WINDOW);
+
+        let exit_code = loop {
+            if let Some(exit_code) = budget.check(self.swarm_size as u32) {
+                break exit_code;
+            }
+
+            // <----- evaluate swarm ----->
+
+            let images = swarm.evaluate_images_with(backend);
+            budget.record_evals(self.swarm_size as u32);
+            budget.record_loop();
+
+            for ((position, image), particle) in swarm
+                .get_population()
+                .iter()
+                .zip(images)
+                .zip(particles.iter_mut())
+            {
+                if let Some(image) = image {
+                    if particle.best.as_ref().is_none_or(|b| image > b.get_eval()) {
+                        particle.best = Some(PointEval::from_image(position.clone(), image));
+                    }
+                }
+            }
+
+            if let Some(swarm_best) = swarm.peek_best_value() {
+                if global_best.as_ref().is_none_or(|b| swarm_best > *b) {
+                    global_best = Some(swarm_best);
+                }
+            }
+
+            let best = match &global_best {
+                Some(best) => best,
+                None => {
+                    log::error!("no particle of the swarm evaluated successfully");
+                    break 1;
+                }
+            };
+
+            let spread = swarm
+                .get_population()
+                .iter()
+                .map(|p| (p - best.get_point()).len())
+                .fold(0.0, f64::max);
+
+            history.push(IterationRecord {
+                loop_index: budget.loops() - 1,
+                fn_evals: budget.fn_evals(),
+                best_f: best.get_eval(),
+                diagonal_len: spread,
+            });
+
+            // <----- convergence checks ----->
+
+            if spread < self.criteria.tol_x {
+                log::info!("optimization process terminated due to input convergence");
+                break 0;
+            }
+
+            if recent_best.len() == IMPROVEMENT_WINDOW {
+                recent_best.pop_front();
+            }
+            recent_best.push_back(best.get_eval());
+
+            if recent_best.len() == IMPROVEMENT_WINDOW
+                && recent_best.back().unwrap() - recent_best.front().unwrap() <= self.criteria.tol_f
+            {
+                log::info!("optimization process terminated due to image convergence");
+                break 0;
+            }
+
+            // <----- move particles ----->
+
+            let global_position = best.get_point();
+
+            let new_positions: Vec<Point> = swarm
+                .get_population()
+                .iter()
+                .zip(particles.iter_mut())
+                .map(|(position, particle)| {
+                    // particles that never evaluated successfully only follow the swarm
+                    let personal_position =
+                        particle.best.as_ref().map_or(position, |p| p.get_point());
+
+                    particle.velocity = (0..n)
+                        .map(|i| {
+                            let x = position.get(i).unwrap();
+                            let r_1: f64 = rng.gen();
+                            let r_2: f64 = rng.gen();
+
+                            (self.inertia * particle.velocity.get(i).unwrap()
+                                + self.cognitive * r_1 * (personal_position.get(i).unwrap() - x)
+                                + self.social * r_2 * (global_position.get(i).unwrap() - x))
+                                .clamp(-max_velocity, max_velocity)
+                        })
+                        .collect();
+
+                    let mut new_position = position + &particle.velocity;
+                    new_position.clamp_in_place(&self.bounds);
+                    new_position
+                })
+                .collect();
+
+            swarm.set_population(new_positions);
+        };
+
+        HypercubeOptimizerResult::new(
+            exit_code,
+            budget.loops(),
+            budget.fn_evals(),
+            global_best.as_ref(),
+            budget.elapsed(),
+        )
+        .with_history(history)
+    }
+}
+
+impl Optimizer for ParticleSwarm {
+    fn maximize(&mut self, obj_function: &Objective) -> HypercubeOptimizerResult {
+        self.maximize_with(&LocalBackend::new(obj_function))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::{neg_rastrigin, neg_sphere};
+    use crate::point;
+
+    fn criteria(max_eval: u32) -> StoppingCriteria {
+        StoppingCriteria {
+            tol_x: 1e-6,
+            tol_f: 1e-9,
+            max_loop: 10_000,
+            max_eval,
+            max_timeout: 60,
+        }
+    }
+
+    #[test]
+    fn converges_on_sphere() {
+        let mut swarm = ParticleSwarm::new(point![3.0; 3], -5.0, 5.0, criteria(20_000));
+        swarm.set_seed(1);
+
+        let result = Optimizer::maximize(&mut swarm, &neg_sphere);
+
+        assert!(result.get_best_f().unwrap() > -1e-6);
+        assert!(result.get_fn_evals() <= 20_000);
+    }
+
+    #[test]
+    fn respects_eval_budget() {
+        let mut swarm = ParticleSwarm::new(point![3.0; 3], -5.0, 5.0, criteria(100));
+        swarm.set_swarm_size(12);
+
+        let result = Optimizer::maximize(&mut swarm, &neg_sphere);
+
+        assert_eq!(result.get_exit_code(), 2);
+        assert_eq!(result.get_fn_evals(), 96);
+    }
+
+    #[test]
+    fn seeded_runs_match() {
+        let run = || {
+            let mut swarm = ParticleSwarm::new(point![3.0; 2], -5.12, 5.12, criteria(1_000));
+            swarm.set_seed(7);
+            Optimizer::maximize(&mut swarm, &neg_rastrigin).get_best_f()
+        };
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn best_point_within_bounds() {
+        let mut swarm = ParticleSwarm::new(point![3.0; 2], 1.0, 5.0, criteria(5_000));
+        swarm.set_seed(2);
+
+        let result = Optimizer::maximize(&mut swarm, &neg_sphere);
+        let best_x = result.get_best_x().unwrap();
+
+        assert!(best_x.iter().all(|x| (1.0..=5.0).contains(x)));
+        assert!((result.get_best_f().unwrap() + 2.0).abs() < 1e-6);
+    }
+}
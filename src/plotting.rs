@@ -0,0 +1,131 @@
+use std::error::Error;
+use std::path::Path;
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::result::{HypercubeOptimizerResult, IterationRecord};
+
+/// Size of the rendered convergence plot in pixels
+const PLOT_SIZE: (u32, u32) = (1024, 1024);
+
+impl HypercubeOptimizerResult {
+    /// Renders the convergence history of the optimization to an image at `path`. The image
+    /// contains two charts: the best objective function value against the number of function
+    /// evaluations, and the hypercube diagonal length against the optimization loop.
+    ///
+    /// The output format is picked from the file extension of `path`. Files ending in `.svg` are
+    /// rendered as SVG, everything else is rendered as a bitmap (e.g. PNG).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - location of the output image file
+    ///
+    pub fn plot_convergence<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        let path = path.as_ref();
+
+        if self.get_history().is_empty() {
+            return Err("cannot plot convergence, result has no optimization history".into());
+        }
+
+        let is_svg = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("svg"))
+            .unwrap_or(false);
+
+        if is_svg {
+            let root = SVGBackend::new(path, PLOT_SIZE).into_drawing_area();
+            draw_convergence(&root, self.get_history())?;
+            root.present()?;
+        } else {
+            let root = BitMapBackend::new(path, PLOT_SIZE).into_drawing_area();
+            draw_convergence(&root, self.get_history())?;
+            root.present()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Draws both convergence charts onto the drawing area, stacked vertically
+fn draw_convergence<DB>(
+    root: &DrawingArea<DB, Shift>,
+    history: &[IterationRecord],
+) -> Result<(), Box<dyn Error>>
+where
+    DB: DrawingBackend,
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+
+    let (upper, lower) = root.split_vertically(PLOT_SIZE.1 / 2);
+
+    // <----- best value vs function evaluations ----->
+
+    let eval_range = value_range(history.iter().map(|r| r.fn_evals as f64));
+    let best_f_range = value_range(history.iter().map(|r| r.best_f));
+
+    let mut best_f_chart = ChartBuilder::on(&upper)
+        .caption("best value vs. function evaluations", ("sans-serif", 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(70)
+        .build_cartesian_2d(eval_range, best_f_range)?;
+
+    best_f_chart
+        .configure_mesh()
+        .x_desc("function evaluations")
+        .y_desc("best value")
+        .draw()?;
+
+    best_f_chart.draw_series(LineSeries::new(
+        history.iter().map(|r| (r.fn_evals as f64, r.best_f)),
+        &BLUE,
+    ))?;
+
+    // <----- hypercube diagonal vs loop ----->
+
+    let loop_range = value_range(history.iter().map(|r| r.loop_index as f64));
+    let diagonal_range = value_range(history.iter().map(|r| r.diagonal_len));
+
+    let mut diagonal_chart = ChartBuilder::on(&lower)
+        .caption("hypercube diagonal vs. loop", ("sans-serif", 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(70)
+        .build_cartesian_2d(loop_range, diagonal_range)?;
+
+    diagonal_chart
+        .configure_mesh()
+        .x_desc("loop")
+        .y_desc("diagonal length")
+        .draw()?;
+
+    diagonal_chart.draw_series(LineSeries::new(
+        history
+            .iter()
+            .map(|r| (r.loop_index as f64, r.diagonal_len)),
+        &RED,
+    ))?;
+
+    Ok(())
+}
+
+/// Computes a non-degenerate axis range spanning all `values`
+fn value_range(values: impl Iterator<Item = f64>) -> std::ops::Range<f64> {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    });
+
+    // pad flat series so the chart still has a visible extent
+    if max - min <= f64::EPSILON {
+        let padding = if min.abs() > 1.0 {
+            min.abs() * 0.05
+        } else {
+            1.0
+        };
+        return (min - padding)..(max + padding);
+    }
+
+    min..max
+}
@@ -1,12 +1,28 @@
 use std::cmp::Ordering;
-use std::ops::{Add, AddAssign, Div, Mul, Sub};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
 use rand::distributions::Uniform;
 use rand::{thread_rng, Rng};
+use rand_distr::StandardNormal;
+use smallvec::SmallVec;
 
 use crate::bounds::HypercubeBounds;
 use std::slice::Iter;
 
+/// Number of coordinates a `Point` stores inline. Points of higher dimension keep their
+/// coordinates on the heap.
+///
+/// The inline buffer is part of every `Point`, so points of higher dimension carry it unused on
+/// top of their heap allocation: a population member takes 64 bytes more than with coordinates
+/// in a boxed slice, e.g. 344 instead of 272 bytes at dimension 32 and 1112 instead of 1040 at
+/// dimension 128 on 64-bit targets (see `benches/point.rs`). In exchange, creating points of up
+/// to this dimension never allocates.
+pub const INLINE_DIMENSION: usize = 8;
+
+/// Coordinates of a point, stored inline up to `INLINE_DIMENSION` so that creating low-dimensional
+/// points doesn't allocate
+type Coords = SmallVec<[f64; INLINE_DIMENSION]>;
+
 /// Defines a point data structure used to represent mathematical vectors that can be elementwise
 /// added, subtracted, multiplied, and divided. Once a point is created, it has a defined and
 /// unchangeable dimension which corresponds to the length of the ordered tuple the point
@@ -14,12 +30,12 @@ use std::slice::Iter;
 #[derive(Debug, PartialEq, Clone)]
 pub struct Point {
     dimension: u32,
-    coords: Box<[f64]>,
+    coords: Coords,
 }
 
 /* <----- Trait implementations for mathematical operations -----> */
 
-impl<'a, 'b> Add<&'b Point> for &'a Point {
+impl<'b> Add<&'b Point> for &Point {
     type Output = Point;
 
     fn add(self, other: &'b Point) -> Point {
@@ -32,17 +48,17 @@ impl<'a, 'b> Add<&'b Point> for &'a Point {
             "addition failed: point dimension cannot be zero"
         );
 
-        let mut add_result = Vec::new();
+        let mut add_result = Coords::new();
 
         for (index, element) in self.coords.iter().enumerate() {
             add_result.push(element + other.get(index).unwrap());
         }
 
-        Point::from_vec(add_result)
+        Point::from_coords(add_result)
     }
 }
 
-impl<'a, 'b> Sub<&'b Point> for &'a Point {
+impl<'b> Sub<&'b Point> for &Point {
     type Output = Point;
 
     fn sub(self, other: &'b Point) -> Point {
@@ -55,17 +71,17 @@ impl<'a, 'b> Sub<&'b Point> for &'a Point {
             "addition failed: point dimension cannot be zero"
         );
 
-        let mut sub_result = Vec::new();
+        let mut sub_result = Coords::new();
 
         for (index, element) in self.coords.iter().enumerate() {
             sub_result.push(element - other.get(index).unwrap());
         }
 
-        Point::from_vec(sub_result)
+        Point::from_coords(sub_result)
     }
 }
 
-impl<'a, 'b> Mul<&'b Point> for &'a Point {
+impl<'b> Mul<&'b Point> for &Point {
     type Output = Point;
 
     fn mul(self, other: &'b Point) -> Point {
@@ -78,17 +94,17 @@ impl<'a, 'b> Mul<&'b Point> for &'a Point {
             "element-wise multiplication failed: point dimension cannot be zero"
         );
 
-        let mut mul_result = Vec::new();
+        let mut mul_result = Coords::new();
 
         for (index, element) in self.coords.iter().enumerate() {
             mul_result.push(element * other.get(index).unwrap());
         }
 
-        Point::from_vec(mul_result)
+        Point::from_coords(mul_result)
     }
 }
 
-impl<'a, 'b> Div<&'b Point> for &'a Point {
+impl<'b> Div<&'b Point> for &Point {
     type Output = Point;
 
     fn div(self, other: &'b Point) -> Point {
@@ -101,37 +117,128 @@ impl<'a, 'b> Div<&'b Point> for &'a Point {
             "element-wise division failed: point dimension cannot be zero"
         );
 
-        let mut div_result = Vec::new();
+        let mut div_result = Coords::new();
 
         for (index, element) in self.coords.iter().enumerate() {
             div_result.push(element / other.get(index).unwrap());
         }
 
-        Point::from_vec(div_result)
+        Point::from_coords(div_result)
     }
 }
 
-impl AddAssign for Point {
-    fn add_assign(&mut self, rhs: Self) {
-        for (index, element) in self.coords.iter_mut().enumerate() {
-            *element += rhs.coords.get(index).unwrap();
-        }
+/// Applies `op` to the coordinates of `target` and `other` pairwise and stores the results in
+/// `target`, so owned operands can be reused instead of allocating a new point
+fn apply_in_place(target: &mut Point, other: &Point, operation: &str, op: impl Fn(&mut f64, f64)) {
+    assert_eq!(
+        target.dimension, other.dimension,
+        "{} failed: operands do not have same dimension",
+        operation
+    );
+
+    for (element, other_element) in target.coords.iter_mut().zip(other.coords.iter()) {
+        op(element, *other_element);
     }
 }
 
+/// Implements an element-wise operator for owned and mixed operands on top of its assignment
+/// operator. The result is written into the allocation of an owned operand.
+macro_rules! impl_owned_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident, $operation:literal, $f:expr) => {
+        impl $assign<&Point> for Point {
+            fn $assign_method(&mut self, other: &Point) {
+                apply_in_place(self, other, $operation, |x, y| *x = $f(*x, y));
+            }
+        }
+
+        impl $assign<Point> for Point {
+            fn $assign_method(&mut self, other: Point) {
+                self.$assign_method(&other);
+            }
+        }
+
+        impl $op<&Point> for Point {
+            type Output = Point;
+
+            fn $method(mut self, other: &Point) -> Point {
+                self.$assign_method(other);
+                self
+            }
+        }
+
+        impl $op<Point> for Point {
+            type Output = Point;
+
+            fn $method(mut self, other: Point) -> Point {
+                self.$assign_method(&other);
+                self
+            }
+        }
+
+        impl $op<Point> for &Point {
+            type Output = Point;
+
+            fn $method(self, mut other: Point) -> Point {
+                apply_in_place(&mut other, self, $operation, |y, x| *y = $f(x, *y));
+                other
+            }
+        }
+    };
+}
+
+impl_owned_op!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    "addition",
+    |a: f64, b: f64| a + b
+);
+impl_owned_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    "subtraction",
+    |a: f64, b: f64| a - b
+);
+impl_owned_op!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    "element-wise multiplication",
+    |a: f64, b: f64| a * b
+);
+impl_owned_op!(
+    Div,
+    div,
+    DivAssign,
+    div_assign,
+    "element-wise division",
+    |a: f64, b: f64| a / b
+);
+
 /* <----- Struct method implementations -----> */
 
 impl Point {
     /// Creates a Point struct from a vector. Consumes vector in the process.
     pub fn from_vec(vector: Vec<f64>) -> Self {
-        assert_ne!(vector.len(), 0, "vector dimension cannot be zero");
+        Self::from_coords(Coords::from_vec(vector))
+    }
+
+    /// Creates a `Point` from a slice of coordinates. Unlike `from_vec`, low-dimensional points
+    /// are created without allocating.
+    pub fn from_slice(coords: &[f64]) -> Self {
+        Self::from_coords(Coords::from_slice(coords))
+    }
 
-        let coords: Vec<f64> = vector;
-        let box_coords = coords.into_boxed_slice();
+    fn from_coords(coords: Coords) -> Self {
+        assert_ne!(coords.len(), 0, "vector dimension cannot be zero");
 
         Self {
-            dimension: box_coords.len() as u32,
-            coords: box_coords,
+            dimension: coords.len() as u32,
+            coords,
         }
     }
 
@@ -139,12 +246,9 @@ impl Point {
     pub fn fill(element: f64, n: u32) -> Self {
         assert_ne!(n, 0, "vector dimension cannot be zero");
 
-        let coords = vec![element; n as usize];
-        let box_coords = coords.into_boxed_slice();
-
         Self {
             dimension: n,
-            coords: box_coords,
+            coords: Coords::from_elem(element, n as usize),
         }
     }
 
@@ -158,21 +262,38 @@ impl Point {
 
     /// Creates a `Point` with random coordinates within given bounds.
     pub fn random(dimension: u32, lower: f64, upper: f64) -> Self {
+        Self::random_with(&mut thread_rng(), dimension, lower, upper)
+    }
+
+    /// Creates a `Point` with random coordinates within given bounds using the random number
+    /// generator `rng`. Useful for reproducible sampling with a seeded generator.
+    pub fn random_with<R: Rng + ?Sized>(
+        rng: &mut R,
+        dimension: u32,
+        lower: f64,
+        upper: f64,
+    ) -> Self {
         assert_ne!(dimension, 0, "vector dimension cannot be zero");
         assert!(
             upper > lower,
             "upper bound not strictly bigger than lower bound"
         );
 
-        let mut rng = thread_rng();
         let uniform_range = Uniform::new_inclusive(lower, upper);
 
-        let random_vec: Vec<f64> = (&mut rng)
-            .sample_iter(uniform_range)
+        rng.sample_iter(uniform_range)
             .take(dimension.try_into().unwrap())
-            .collect();
+            .collect()
+    }
+
+    /// Creates a `Point` whose coordinates are drawn from independent normal distributions
+    /// centered on the coordinates of `mean`, all with standard deviation `stddev`.
+    pub fn random_gaussian<R: Rng + ?Sized>(rng: &mut R, mean: &Point, stddev: f64) -> Self {
+        assert!(stddev >= 0.0, "standard deviation cannot be negative");
 
-        Self::from_vec(random_vec)
+        mean.iter()
+            .map(|m| m + stddev * rng.sample::<f64, _>(StandardNormal))
+            .collect()
     }
 
     /// Shrink point towards a specified center. The scale factor must be
@@ -181,7 +302,7 @@ impl Point {
         assert!(scale_factor >= 0.0, "scale factor cannot be negative");
         assert!(scale_factor <= 1.0, "scale factor cannot be more than 1");
 
-        let point_to_center: Point = center - &self;
+        let point_to_center: Point = center - &*self;
         let scaled_point_to_center = point_to_center.scale(1.0 - scale_factor);
 
         *self += scaled_point_to_center;
@@ -224,28 +345,29 @@ impl Point {
     }
 
     pub fn clamp(&self, bound: &HypercubeBounds) -> Point {
+        let mut result = self.clone();
+        result.clamp_in_place(bound);
+        result
+    }
+
+    /// Moves every coordinate outside `bound` onto the nearest face of `bound` in-place
+    pub fn clamp_in_place(&mut self, bound: &HypercubeBounds) {
         assert_eq!(
             self.dim(),
             bound.get_upper().dim(),
             "point dimension and bounds dimension do not match"
         );
 
-        let mut clipped_vector: Vec<f64> = Vec::new();
-
-        for (index, element) in self.iter().enumerate() {
+        for (index, element) in self.coords.iter_mut().enumerate() {
             let upper_element = bound.get_upper().get(index).unwrap();
             let lower_element = bound.get_lower().get(index).unwrap();
 
-            if element < lower_element {
-                clipped_vector.push(*lower_element);
-            } else if element > upper_element {
-                clipped_vector.push(*upper_element);
-            } else {
-                clipped_vector.push(*element);
+            if *element < *lower_element {
+                *element = *lower_element;
+            } else if *element > *upper_element {
+                *element = *upper_element;
             }
         }
-
-        Point::from_vec(clipped_vector)
     }
 
     pub fn sum(&self) -> f64 {
@@ -259,6 +381,13 @@ impl Point {
     }
 }
 
+/// Collects coordinates into a `Point`. Panics if there are none.
+impl FromIterator<f64> for Point {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        Self::from_coords(iter.into_iter().collect())
+    }
+}
+
 /* Comparison function */
 
 /// comparison function to find max and min of Vec<f64>
@@ -271,7 +400,7 @@ pub fn cmp(lhs: &f64, rhs: &f64) -> Ordering {
 macro_rules! point {
     ( $( $x:expr ),*) => {
         {
-            Point::from_vec(vec![$($x),*])
+            Point::from_slice(&[$($x),*])
         }
     };
 
@@ -291,7 +420,7 @@ mod tests {
         let a = Point::fill(4.3, 10);
         let b = Point {
             dimension: 10,
-            coords: vec![4.3; 10].into_boxed_slice(),
+            coords: Coords::from_vec(vec![4.3; 10]),
         };
 
         assert_eq!(a, b);
@@ -308,7 +437,7 @@ mod tests {
         let a = Point::from_vec(vec![5.2, 4.5, 3.2]);
         let b = Point {
             dimension: 3,
-            coords: vec![5.2, 4.5, 3.2].into_boxed_slice(),
+            coords: Coords::from_vec(vec![5.2, 4.5, 3.2]),
         };
 
         assert_eq!(a, b);
@@ -345,6 +474,24 @@ mod tests {
         let _a = Point::random(10, 10.0, 10.0);
     }
 
+    #[test]
+    fn new_point_random_gaussian_1() {
+        let mut rng = thread_rng();
+        let mean = point![1.0, -2.0, 3.0];
+
+        // zero standard deviation should always return the mean
+        let a = Point::random_gaussian(&mut rng, &mean, 0.0);
+
+        assert_eq!(a, mean);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_point_random_gaussian_2() {
+        let mut rng = thread_rng();
+        let _a = Point::random_gaussian(&mut rng, &point![1.0; 3], -1.0);
+    }
+
     #[test]
     fn clamp_1() {
         let test_bounds = HypercubeBounds::new(3, 23.0, 34.0);
@@ -389,6 +536,53 @@ mod tests {
         assert_eq!(calc_result, expected_result);
     }
 
+    #[test]
+    fn owned_operators_1() {
+        let a = point![1.0, 2.0, 3.0];
+        let b = point![4.0, 8.0, 16.0];
+
+        assert_eq!(a.clone() + b.clone(), &a + &b);
+        assert_eq!(a.clone() - &b, &a - &b);
+        assert_eq!(&a * b.clone(), &a * &b);
+        assert_eq!(&a / b.clone(), &a / &b);
+        assert_eq!(&b - a.clone(), point![3.0, 6.0, 13.0]);
+    }
+
+    #[test]
+    fn owned_operators_2() {
+        // points above the inline dimension, whose coordinates live on the heap
+        let a = point![1.0; 16];
+        let b = point![4.0; 16];
+
+        // the result is written into the owned operand
+        let a_coords = a.coords.as_ptr();
+        let sum = a + &b;
+        assert_eq!(sum.coords.as_ptr(), a_coords);
+
+        let b_coords = b.coords.as_ptr();
+        let quotient = &sum / b;
+        assert_eq!(quotient.coords.as_ptr(), b_coords);
+        assert_eq!(quotient, point![1.25; 16]);
+    }
+
+    #[test]
+    fn assign_operators_1() {
+        let mut a = point![1.0, 2.0];
+
+        a += &point![1.0, 1.0];
+        a -= point![0.5, 0.5];
+        a *= &point![2.0, 4.0];
+        a /= &point![3.0, 5.0];
+
+        assert_eq!(a, point![1.0, 2.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn owned_operators_dimension_mismatch() {
+        let _ = point![1.0, 2.0] + point![1.0, 2.0, 3.0];
+    }
+
     #[test]
     fn clamp_5() {
         let test_bounds = HypercubeBounds::new(3, 23.0, 34.0);
@@ -0,0 +1,181 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use crate::bounds::HypercubeBounds;
+use crate::point::Point;
+
+/// Reads a population from a file of coordinate rows and checks that every point has the
+/// dimension of `bounds` and lies inside them. Files with a `.json` extension hold an array of
+/// coordinate arrays, e.g. `[[0.5, 1.0], [2.0, -1.5]]`. Any other file is read as CSV with one
+/// point per line and an optional header line.
+pub(crate) fn read_population(
+    path: impl AsRef<Path>,
+    bounds: &HypercubeBounds,
+) -> io::Result<Vec<Point>> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)?;
+
+    let is_json = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+
+    let rows = if is_json {
+        parse_json(&text)
+    } else {
+        parse_csv(&text)
+    }
+    .map_err(invalid_data)?;
+
+    if rows.is_empty() {
+        return Err(invalid_data("population file has no points".to_string()));
+    }
+
+    rows.into_iter()
+        .enumerate()
+        .map(|(index, coords)| {
+            if coords.len() != bounds.dim() as usize {
+                return Err(invalid_data(format!(
+                    "point {} has {} coordinates but the problem has {} dimensions",
+                    index,
+                    coords.len(),
+                    bounds.dim()
+                )));
+            }
+
+            let point = Point::from_vec(coords);
+
+            if !bounds.contains(&point) {
+                return Err(invalid_data(format!(
+                    "point {} {:?} lies outside the search space",
+                    index, point
+                )));
+            }
+
+            Ok(point)
+        })
+        .collect()
+}
+
+/// Parses comma separated coordinate rows, skipping blank lines and a header line
+fn parse_csv(text: &str) -> Result<Vec<Vec<f64>>, String> {
+    let mut rows = Vec::new();
+
+    for (line_index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse_numbers(line) {
+            Ok(row) => rows.push(row),
+            // the first line may name the columns instead
+            Err(_) if rows.is_empty() && line_index == 0 => continue,
+            Err(e) => return Err(format!("line {}: {}", line_index + 1, e)),
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Parses a JSON array of arrays of numbers
+fn parse_json(text: &str) -> Result<Vec<Vec<f64>>, String> {
+    let mut rest = text
+        .trim()
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .ok_or("expected an array of points")?
+        .trim_start();
+
+    let mut rows = Vec::new();
+
+    while !rest.is_empty() {
+        let row = rest
+            .strip_prefix('[')
+            .ok_or_else(|| format!("point {}: expected an array of coordinates", rows.len()))?;
+        let end = row
+            .find(']')
+            .ok_or_else(|| format!("point {}: unterminated array", rows.len()))?;
+
+        rows.push(parse_numbers(&row[..end]).map_err(|e| format!("point {}: {}", rows.len(), e))?);
+
+        rest = row[end + 1..].trim_start();
+
+        if let Some(after_comma) = rest.strip_prefix(',') {
+            rest = after_comma.trim_start();
+        } else if !rest.is_empty() {
+            return Err(format!("point {}: expected a comma", rows.len()));
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Parses a comma separated list of numbers
+fn parse_numbers(list: &str) -> Result<Vec<f64>, String> {
+    list.split(',')
+        .map(|field| {
+            let field = field.trim();
+            match field.parse::<f64>() {
+                Ok(value) if value.is_finite() => Ok(value),
+                _ => Err(format!("invalid coordinate {:?}", field)),
+            }
+        })
+        .collect()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point;
+
+    fn write_temp(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("hypercube-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn read_csv_with_header() {
+        let path = write_temp("population.csv", "x0,x1\n0.5, 1\n\n-2,3.25\n");
+        let bounds = HypercubeBounds::new(2, -5.0, 5.0);
+
+        let population = read_population(&path, &bounds).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(population, vec![point![0.5, 1.0], point![-2.0, 3.25]]);
+    }
+
+    #[test]
+    fn read_json() {
+        let path = write_temp("population.json", " [[0.5, 1], [-2, 3.25]]\n");
+        let bounds = HypercubeBounds::new(2, -5.0, 5.0);
+
+        let population = read_population(&path, &bounds).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(population, vec![point![0.5, 1.0], point![-2.0, 3.25]]);
+    }
+
+    #[test]
+    fn reject_invalid_points() {
+        let bounds = HypercubeBounds::new(2, -5.0, 5.0);
+
+        for (name, contents) in [
+            ("dimension.csv", "0.5,1,2\n"),
+            ("bounds.csv", "0.5,6\n"),
+            ("number.csv", "0.5,1\n0.5,one\n"),
+            ("empty.json", "[]"),
+            ("comma.json", "[[0.5, 1] [2, 1]]"),
+        ] {
+            let path = write_temp(name, contents);
+            let error = read_population(&path, &bounds).unwrap_err();
+            fs::remove_file(&path).unwrap();
+
+            assert_eq!(error.kind(), ErrorKind::InvalidData, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::events::{OptimizerEvent, OptimizerObserver};
+
+/// Layout of the progress bar: elapsed time, evaluations used against the budget, ETA, and the
+/// loop and best value message
+const TEMPLATE: &str = "[{elapsed_precise}] {wide_bar} {pos}/{len} evals (ETA {eta}) {msg}";
+
+/// Terminal progress bar for interactive runs, driven by optimizer events. The bar fills up with
+/// the function evaluations used out of the evaluation budget and shows the number of loops
+/// completed, the best value so far, and an estimate of the remaining time. Set it with
+/// `HypercubeOptimizer::set_observer`.
+///
+/// The bar is only drawn when stderr is a terminal.
+pub struct ProgressReporter {
+    bar: ProgressBar,
+
+    /// number of loops evaluated so far, across restarts
+    loops: u32,
+
+    /// function evaluations of the runs before the last restart
+    restart_evals: u64,
+
+    /// best image seen so far
+    best_f: Option<f64>,
+}
+
+impl ProgressReporter {
+    /// Returns a new `ProgressReporter` for an optimization allowed `max_eval` function evaluations
+    pub fn new(max_eval: u32) -> Self {
+        let bar = ProgressBar::new(max_eval as u64)
+            .with_style(ProgressStyle::with_template(TEMPLATE).unwrap());
+
+        Self {
+            bar,
+            loops: 0,
+            restart_evals: 0,
+            best_f: None,
+        }
+    }
+
+    /// Stops updating the bar and leaves it on screen. Called automatically when the reporter is
+    /// dropped.
+    pub fn finish(&self) {
+        if !self.bar.is_finished() {
+            self.bar.finish();
+        }
+    }
+
+    fn update_message(&self) {
+        let best_f = match self.best_f {
+            Some(best_f) => format!("{:.6e}", best_f),
+            None => "-".to_string(),
+        };
+
+        self.bar
+            .set_message(format!("loop {} | best {}", self.loops, best_f));
+    }
+}
+
+impl OptimizerObserver for ProgressReporter {
+    fn notify(&mut self, event: &OptimizerEvent) {
+        match *event {
+            OptimizerEvent::Evaluated {
+                fn_evals,
+                loop_best_f,
+                ..
+            } => {
+                self.loops += 1;
+
+                if let Some(loop_best_f) = loop_best_f {
+                    if self.best_f.is_none_or(|best_f| loop_best_f > best_f) {
+                        self.best_f = Some(loop_best_f);
+                    }
+                }
+
+                self.bar.set_position(self.restart_evals + fn_evals as u64);
+                self.update_message();
+            }
+            OptimizerEvent::TargetReached { best_f } => {
+                self.best_f = Some(best_f);
+                self.update_message();
+            }
+            // every restart counts its function evaluations from zero again
+            OptimizerEvent::Restarted { .. } => {
+                self.restart_evals = self.bar.position();
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Drop for ProgressReporter {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_evaluations_across_restarts() {
+        let mut reporter = ProgressReporter::new(1000);
+
+        reporter.notify(&OptimizerEvent::Evaluated {
+            loop_index: 0,
+            fn_evals: 40,
+            loop_best_f: Some(-3.0),
+        });
+        reporter.notify(&OptimizerEvent::Evaluated {
+            loop_index: 1,
+            fn_evals: 80,
+            loop_best_f: Some(-5.0),
+        });
+        reporter.notify(&OptimizerEvent::Restarted {
+            restart: 1,
+            population_size: 60,
+        });
+        reporter.notify(&OptimizerEvent::Evaluated {
+            loop_index: 0,
+            fn_evals: 60,
+            loop_best_f: None,
+        });
+
+        assert_eq!(reporter.bar.position(), 140);
+        assert_eq!(reporter.loops, 3);
+        assert_eq!(reporter.best_f, Some(-3.0));
+        assert!(reporter.bar.message().starts_with("loop 3 | best -3"));
+    }
+}
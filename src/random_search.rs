@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::backend::{EvaluationBackend, LocalBackend, Objective};
+use crate::bounds::HypercubeBounds;
+use crate::evaluation::PointEval;
+use crate::optimizer::Optimizer;
+use crate::point::Point;
+use crate::result::{HypercubeOptimizerResult, IterationRecord};
+use crate::stopping::{Budget, StoppingCriteria};
+
+/// Number of batches over which the improvement of the best value is compared to `tol_f`
+const IMPROVEMENT_WINDOW: usize = 30;
+
+/// Pure random search. Samples batches of points uniformly within the search space and keeps the
+/// best one. Useful as the baseline every other optimizer should beat.
+pub struct RandomSearch {
+    /// dimension of the optimization problem
+    dimension: u32,
+
+    /// first point evaluated
+    init_point: Point,
+
+    /// bounds of the search space
+    bounds: HypercubeBounds,
+
+    /// number of points sampled and evaluated together
+    batch_size: u32,
+
+    /// conditions that terminate the optimization
+    criteria: StoppingCriteria,
+
+    /// random number generator used to sample points
+    rng: StdRng,
+}
+
+impl RandomSearch {
+    /// Returns a new `RandomSearch` optimizer
+    ///
+    /// # Arguments
+    ///
+    /// * `init_point` - the first point evaluated
+    /// * `lower_bound` - the lower bound of the search space
+    /// * `upper_bound` - the upper bound of the search space
+    /// * `criteria` - conditions that terminate the optimization. `tol_f` is compared against the
+    ///   improvement of the best value over the last 30 batches. `tol_x` is not used since the
+    ///   samples never concentrate.
+    ///
+    pub fn new(
+        init_point: Point,
+        lower_bound: f64,
+        upper_bound: f64,
+        criteria: StoppingCriteria,
+    ) -> Self {
+        assert!(
+            upper_bound > lower_bound,
+            "upper bound not strictly larger than lower bound"
+        );
+        assert!(
+            init_point.max_val().unwrap() <= upper_bound,
+            "init_point not inside upper bound"
+        );
+        assert!(
+            init_point.min_val().unwrap() >= lower_bound,
+            "init_point not inside lower bound"
+        );
+
+        let dimension = init_point.dim();
+
+        Self {
+            dimension,
+            bounds: HypercubeBounds::new(dimension, lower_bound, upper_bound),
+            init_point,
+            batch_size: 100,
+            criteria,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Sets the number of points sampled and evaluated together. Defaults to 100.
+    pub fn set_batch_size(&mut self, batch_size: u32) {
+        assert_ne!(batch_size, 0, "batch size cannot be zero");
+        self.batch_size = batch_size;
+    }
+
+    /// Seeds the random number generator used to sample points
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Maximizes the objective function evaluated by `backend`
+    pub fn maximize_with<B>(&mut self, backend: &B) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + ?Sized,
+    {
+        let lower_bound = *self.bounds.get_lower().get(0).unwrap();
+        let upper_bound = *self.bounds.get_upper().get(0).unwrap();
+
+        let mut budget = Budget::start(&self.criteria);
+        let mut history: Vec<IterationRecord> = Vec::new();
+
+        let mut best: Option<PointEval> = None;
+        let mut recent_best: VecDeque<f64> = VecDeque::with_capacity(IMPROVEMENT_WINDOW);
+
+        let exit_code = loop {
+            // the last batch is trimmed to fit the remaining evaluations
+            let batch_size = self.batch_size.min(budget.remaining_evals()).max(1);
+
+            if let Some(exit_code) = budget.check(batch_size) {
+                break exit_code;
+            }
+
+            // <----- sample and evaluate batch ----->
+
+            let mut batch: Vec<Point> = Vec::with_capacity(batch_size as usize);
+
+            if budget.loops() == 0 {
+                batch.push(self.init_point.clone());
+            }
+
+            while batch.len() < batch_size as usize {
+                batch.push(Point::random_with(
+                    &mut self.rng,
+                    self.dimension,
+                    lower_bound,
+                    upper_bound,
+                ));
+            }
+
+            let images = backend.evaluate_batch(&batch);
+            budget.record_evals(batch_size);
+            budget.record_loop();
+
+            for (point, image) in batch.into_iter().zip(images) {
+                match image {
+                    Ok(image) if !image.is_nan() => {
+                        if best.as_ref().is_none_or(|b| image > b.get_eval()) {
+                            best = Some(PointEval::from_image(point, image));
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => log::warn!("evaluation failed at {:?}: {:?}", point, e),
+                }
+            }
+
+            // nothing evaluated successfully yet, keep sampling
+            let best_f = match &best {
+                Some(best) => best.get_eval(),
+                None => continue,
+            };
+
+            history.push(IterationRecord {
+                loop_index: budget.loops() - 1,
+                fn_evals: budget.fn_evals(),
+                best_f,
+                diagonal_len: self.bounds.get_diagonal().len(),
+            });
+
+            // <----- image convergence ----->
+
+            if recent_best.len() == IMPROVEMENT_WINDOW {
+                recent_best.pop_front();
+            }
+            recent_best.push_back(best_f);
+
+            if recent_best.len() == IMPROVEMENT_WINDOW
+                && recent_best.back().unwrap() - recent_best.front().unwrap() <= self.criteria.tol_f
+            {
+                log::info!("optimization process terminated due to image convergence");
+                break 0;
+            }
+        };
+
+        HypercubeOptimizerResult::new(
+            exit_code,
+            budget.loops(),
+            budget.fn_evals(),
+            best.as_ref(),
+            budget.elapsed(),
+        )
+        .with_history(history)
+    }
+}
+
+impl Optimizer for RandomSearch {
+    fn maximize(&mut self, obj_function: &Objective) -> HypercubeOptimizerResult {
+        self.maximize_with(&LocalBackend::new(obj_function))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::{neg_rastrigin, neg_sphere};
+    use crate::point;
+
+    fn criteria(max_eval: u32) -> StoppingCriteria {
+        StoppingCriteria {
+            tol_f: 0.0,
+            max_eval,
+            ..StoppingCriteria::default()
+        }
+    }
+
+    #[test]
+    fn uses_whole_eval_budget() {
+        let mut random_search = RandomSearch::new(point![3.0; 2], -5.0, 5.0, criteria(250));
+
+        let result = Optimizer::maximize(&mut random_search, &neg_sphere);
+
+        assert_eq!(result.get_exit_code(), 2);
+        assert_eq!(result.get_fn_evals(), 250);
+        assert_eq!(result.get_loops(), 3);
+    }
+
+    #[test]
+    fn never_worse_than_init_point() {
+        let mut random_search = RandomSearch::new(point![0.0; 3], -5.12, 5.12, criteria(500));
+
+        let result = Optimizer::maximize(&mut random_search, &neg_rastrigin);
+
+        assert_eq!(result.get_best_f(), Some(0.0));
+        assert_eq!(result.get_best_x(), Some(&point![0.0; 3]));
+    }
+
+    #[test]
+    fn stops_on_image_convergence() {
+        let mut random_search = RandomSearch::new(
+            point![3.0; 2],
+            -5.0,
+            5.0,
+            StoppingCriteria {
+                tol_f: 1.0,
+                max_eval: 100_000,
+                ..StoppingCriteria::default()
+            },
+        );
+        random_search.set_batch_size(10);
+
+        // constant objective never improves
+        let result = Optimizer::maximize(&mut random_search, &|_: &Point| 1.0);
+
+        assert_eq!(result.get_exit_code(), 0);
+        assert_eq!(result.get_loops(), 30);
+    }
+
+    #[test]
+    fn seeded_runs_match() {
+        let run = || {
+            let mut random_search = RandomSearch::new(point![3.0; 2], -5.12, 5.12, criteria(1_000));
+            random_search.set_seed(11);
+            Optimizer::maximize(&mut random_search, &neg_rastrigin).get_best_f()
+        };
+
+        assert_eq!(run(), run());
+    }
+}
@@ -1,6 +1,13 @@
+use std::any::Any;
 use std::time::Duration;
 
-use crate::{point::Point, evaluation::PointEval};
+use crate::{
+    bounds::HypercubeBounds,
+    evaluation::{Metadata, PointEval},
+    manifest::RunManifest,
+    point::Point,
+    transform::InputTransform,
+};
 
 /// Exit codes:
 /// 0 => successful execution
@@ -8,6 +15,8 @@ use crate::{point::Point, evaluation::PointEval};
 /// 2 => non-convergence within defined bounds
 /// 3 => optimization timeout
 /// 4 => optimization bounds are too large
+/// 5 => target value reached
+/// 6 => optimization cancelled
 
 
 #[derive(Debug)]
@@ -19,6 +28,81 @@ pub struct HypercubeOptimizerResult {
     best_x: Option<Point>,
     best_f: Option<f64>,
     time_elapsed: Duration,
+    history: Vec<IterationRecord>,
+    dimension_importance: Option<Vec<f64>>,
+    restart_bests: Vec<PointEval>,
+    stagnation: Option<Stagnation>,
+    phase_timings: Option<PhaseTimings>,
+    best_f_interval: Option<ConfidenceInterval>,
+    manifest: Option<RunManifest>,
+    best_metadata: Option<Metadata>,
+}
+
+/// Snapshot of the optimizer state taken at the end of every optimization loop
+#[derive(Debug, Clone, PartialEq)]
+pub struct IterationRecord {
+    /// index of the optimization loop
+    pub loop_index: u32,
+
+    /// number of objective function evaluations performed so far
+    pub fn_evals: u32,
+
+    /// best objective function value found so far
+    pub best_f: f64,
+
+    /// length of the hypercube diagonal during the loop
+    pub diagonal_len: f64,
+}
+
+/// Progress of the image convergence check when the optimization stopped
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stagnation {
+    /// number of consecutive stagnant loops required for image convergence
+    pub window: u32,
+
+    /// number of consecutive loops at the end of the optimization whose best evaluations changed
+    /// by less than the function tolerances
+    pub stagnant_loops: u32,
+}
+
+/// Confidence interval of the mean of repeated evaluations of a point
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfidenceInterval {
+    /// mean of the evaluations
+    pub mean: f64,
+
+    /// lower end of the interval
+    pub lower: f64,
+
+    /// upper end of the interval
+    pub upper: f64,
+
+    /// probability that the interval contains the true mean, e.g. 0.95
+    pub confidence: f64,
+}
+
+/// Time spent in each phase of the optimization loops
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhaseTimings {
+    /// time spent sampling new populations
+    pub sampling: Duration,
+
+    /// time spent evaluating the objective function
+    pub evaluation: Duration,
+
+    /// time spent shrinking, expanding and displacing the hypercube
+    pub geometry: Duration,
+}
+
+/// Share of the total optimization time spent in each phase, as percentages
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhasePercentages {
+    pub sampling: f64,
+    pub evaluation: f64,
+    pub geometry: f64,
+
+    /// time not spent in any of the phases above, e.g. bookkeeping and logging
+    pub other: f64,
 }
 
 impl HypercubeOptimizerResult {
@@ -35,7 +119,7 @@ impl HypercubeOptimizerResult {
         // separate best value into point and eval
 
         let best_f = best_value.map(|v| v.get_eval());
-        let best_x = best_value.map(|v| v.get_point());
+        let best_x = best_value.map(|v| v.get_point().clone());
 
         Self {
             exit_code,
@@ -45,7 +129,176 @@ impl HypercubeOptimizerResult {
             best_x,
             best_f,
             time_elapsed,
+            history: Vec::new(),
+            dimension_importance: None,
+            restart_bests: Vec::new(),
+            stagnation: None,
+            phase_timings: None,
+            best_f_interval: None,
+            manifest: None,
+            best_metadata: None,
+        }
+    }
+
+    /// Attaches the per-loop optimization history to the result
+    pub fn with_history(mut self, history: Vec<IterationRecord>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Attaches the learned importance weight of every dimension to the result
+    pub fn with_dimension_importance(mut self, dimension_importance: Vec<f64>) -> Self {
+        self.dimension_importance = Some(dimension_importance);
+        self
+    }
+
+    /// Attaches the state of the image convergence check to the result
+    pub fn with_stagnation(mut self, stagnation: Stagnation) -> Self {
+        self.stagnation = Some(stagnation);
+        self
+    }
+
+    /// Attaches the time spent in each phase of the optimization loops to the result
+    pub fn with_phase_timings(mut self, phase_timings: PhaseTimings) -> Self {
+        self.phase_timings = Some(phase_timings);
+        self
+    }
+
+    /// Attaches the confidence interval of the best value to the result
+    pub fn with_best_f_interval(mut self, best_f_interval: Option<ConfidenceInterval>) -> Self {
+        self.best_f_interval = best_f_interval;
+        self
+    }
+
+    /// Attaches the manifest that reproduces the optimization run to the result
+    pub fn with_manifest(mut self, manifest: RunManifest) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    /// Attaches the auxiliary objective output of the best point to the result
+    pub(crate) fn with_best_metadata(mut self, best_metadata: Option<Metadata>) -> Self {
+        self.best_metadata = best_metadata;
+        self
+    }
+
+    /// Combines the result of a restarted optimization run into this one. Loops, evaluations and
+    /// time add up, and the history of the restart continues where this one ends.
+    pub(crate) fn with_restart(mut self, restart: HypercubeOptimizerResult) -> Self {
+        // the first run's best is recorded when the first restart is added
+        if self.restart_bests.is_empty() {
+            if let (Some(best_x), Some(best_f)) = (&self.best_x, self.best_f) {
+                let first_best = PointEval::from_image(best_x.clone(), best_f);
+                self.restart_bests.push(first_best);
+            }
+        }
+
+        // history records carry the best value across runs, not just within the restart
+        for record in restart.history {
+            self.history.push(IterationRecord {
+                loop_index: record.loop_index + self.loops,
+                fn_evals: record.fn_evals + self.fn_evals,
+                best_f: self.best_f.map_or(record.best_f, |f| f.max(record.best_f)),
+                diagonal_len: record.diagonal_len,
+            });
         }
+
+        if let (Some(best_x), Some(best_f)) = (&restart.best_x, restart.best_f) {
+            let restart_best = PointEval::from_image(best_x.clone(), best_f);
+            self.restart_bests.push(restart_best);
+
+            if self.best_f.is_none_or(|f| best_f > f) {
+                self.best_x = Some(best_x.clone());
+                self.best_f = Some(best_f);
+                self.best_f_interval = restart.best_f_interval;
+            }
+        }
+
+        self.exit_code = restart.exit_code;
+        self.message = restart.message;
+        self.loops += restart.loops;
+        self.fn_evals += restart.fn_evals;
+        self.time_elapsed += restart.time_elapsed;
+
+        if restart.dimension_importance.is_some() {
+            self.dimension_importance = restart.dimension_importance;
+        }
+
+        if restart.stagnation.is_some() {
+            self.stagnation = restart.stagnation;
+        }
+
+        if let Some(restart_timings) = restart.phase_timings {
+            let timings = self.phase_timings.get_or_insert_with(PhaseTimings::default);
+            timings.sampling += restart_timings.sampling;
+            timings.evaluation += restart_timings.evaluation;
+            timings.geometry += restart_timings.geometry;
+        }
+
+        self
+    }
+
+    /// Counts `loops` and `fn_evals` done before the optimization was resumed from a checkpoint
+    pub(crate) fn with_resumed(mut self, loops: u32, fn_evals: u32) -> Self {
+        for record in self.history.iter_mut() {
+            record.loop_index += loops;
+            record.fn_evals += fn_evals;
+        }
+
+        self.loops += loops;
+        self.fn_evals += fn_evals;
+        self
+    }
+
+    /// Replaces the best value with `best`, found by refining the previous best value using
+    /// `extra_evals` more function evaluations and `extra_time`. The confidence interval and
+    /// metadata of the previous best point are dropped if `best` lies elsewhere.
+    pub(crate) fn with_refined_best(
+        mut self,
+        best: &PointEval,
+        extra_evals: u32,
+        extra_time: Duration,
+    ) -> Self {
+        if self.best_x.as_ref() != Some(best.get_point()) {
+            self.best_f_interval = None;
+            self.best_metadata = None;
+        }
+
+        self.best_x = Some(best.get_point().clone());
+        self.best_f = Some(best.get_eval());
+        self.fn_evals += extra_evals;
+        self.time_elapsed += extra_time;
+        self
+    }
+
+    /// Makes sure the best point lies inside `bounds`, moving it onto the nearest face of `bounds`
+    /// if rounding pushed it out
+    pub(crate) fn with_best_x_within(mut self, bounds: &HypercubeBounds) -> Self {
+        if let Some(best_x) = self.best_x.as_mut() {
+            if !bounds.contains(best_x) {
+                log::warn!(
+                    "best point {:?} lies outside the search space, clamping it",
+                    best_x
+                );
+                best_x.clamp_in_place(bounds);
+            }
+        }
+        self
+    }
+
+    /// Maps the best points from the search space of the optimizer to the native parameter space
+    /// of the objective function
+    pub(crate) fn with_native_best_x(mut self, transform: &InputTransform) -> Self {
+        if let Some(best_x) = self.best_x.as_mut() {
+            *best_x = transform.to_native(best_x);
+        }
+
+        for restart_best in self.restart_bests.iter_mut() {
+            let native = transform.to_native(restart_best.get_point());
+            *restart_best = PointEval::from_image(native, restart_best.get_eval());
+        }
+
+        self
     }
 
     pub fn map_to_message(exit_code: u32) -> &'static str {
@@ -55,7 +308,110 @@ impl HypercubeOptimizerResult {
             2 => "non-convergence within defined bounds",
             3 => "optimization timeout",
             4 => "optimization bounds are too large",
+            5 => "target value reached",
+            6 => "optimization cancelled",
             _ => "",
         }
     }
+
+    pub fn get_exit_code(&self) -> u32 {
+        self.exit_code
+    }
+
+    pub fn get_message(&self) -> &'static str {
+        self.message
+    }
+
+    pub fn get_loops(&self) -> u32 {
+        self.loops
+    }
+
+    pub fn get_fn_evals(&self) -> u32 {
+        self.fn_evals
+    }
+
+    pub fn get_best_x(&self) -> Option<&Point> {
+        self.best_x.as_ref()
+    }
+
+    pub fn get_best_f(&self) -> Option<f64> {
+        self.best_f
+    }
+
+    pub fn get_time_elapsed(&self) -> Duration {
+        self.time_elapsed
+    }
+
+    pub fn get_history(&self) -> &[IterationRecord] {
+        &self.history
+    }
+
+    /// State of the image convergence check when the optimization stopped. Only reported by
+    /// optimizers that check for stagnation over a window of loops, e.g. `HypercubeOptimizer`.
+    pub fn get_stagnation(&self) -> Option<&Stagnation> {
+        self.stagnation.as_ref()
+    }
+
+    /// Best value found by the first run and by every restart, in order. Empty if the optimization
+    /// was never restarted.
+    pub fn get_restart_bests(&self) -> &[PointEval] {
+        &self.restart_bests
+    }
+
+    /// Importance weight of every dimension, summing to one. Only reported by optimizers that
+    /// learn them, e.g. `HypercubeOptimizer`.
+    pub fn get_dimension_importance(&self) -> Option<&[f64]> {
+        self.dimension_importance.as_deref()
+    }
+
+    /// Confidence interval of the best value, estimated from repeated evaluations of the best
+    /// point. Only reported when resampling is enabled, e.g. with
+    /// `HypercubeOptimizer::set_resampling`.
+    pub fn get_best_f_interval(&self) -> Option<&ConfidenceInterval> {
+        self.best_f_interval.as_ref()
+    }
+
+    /// Seed, configuration and problem of the optimization run, which
+    /// `HypercubeOptimizer::from_manifest` turns back into an optimizer that repeats the run. Only
+    /// reported by optimizers that record them, e.g. `HypercubeOptimizer`.
+    pub fn get_manifest(&self) -> Option<&RunManifest> {
+        self.manifest.as_ref()
+    }
+
+    /// Auxiliary output the objective function returned for the best point, if it is of type `T`.
+    /// Only reported by `HypercubeOptimizer::maximize_with_metadata`.
+    pub fn get_best_metadata<T: Any>(&self) -> Option<&T> {
+        self.best_metadata.as_ref()?.downcast_ref()
+    }
+
+    /// Time spent in each phase of the optimization loops. Only reported by optimizers that
+    /// measure them, e.g. `HypercubeOptimizer`.
+    pub fn get_phase_timings(&self) -> Option<&PhaseTimings> {
+        self.phase_timings.as_ref()
+    }
+
+    /// Share of the elapsed time spent in each phase of the optimization loops
+    pub fn get_phase_percentages(&self) -> Option<PhasePercentages> {
+        let timings = self.phase_timings.as_ref()?;
+        let total = self.time_elapsed.as_secs_f64();
+
+        let percentage = |phase: Duration| {
+            if total > 0.0 {
+                100.0 * phase.as_secs_f64() / total
+            } else {
+                0.0
+            }
+        };
+
+        let sampling = percentage(timings.sampling);
+        let evaluation = percentage(timings.evaluation);
+        let geometry = percentage(timings.geometry);
+
+        Some(PhasePercentages {
+            sampling,
+            evaluation,
+            geometry,
+            other: (100.0 - sampling - evaluation - geometry).max(0.0),
+        })
+    }
 }
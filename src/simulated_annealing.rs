@@ -0,0 +1,396 @@
+use std::collections::VecDeque;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::backend::{EvaluationBackend, LocalBackend, Objective};
+use crate::bounds::HypercubeBounds;
+use crate::evaluation::PointEval;
+use crate::optimizer::Optimizer;
+use crate::point::Point;
+use crate::result::{HypercubeOptimizerResult, IterationRecord};
+use crate::stopping::{Budget, StoppingCriteria};
+
+/// Determines the temperature of a simulated annealing run at every iteration
+pub trait CoolingSchedule {
+    /// Returns the temperature at `iteration`, starting from iteration 0
+    fn temperature(&self, iteration: u32) -> f64;
+}
+
+/// Cooling schedule `T(k) = T0 * rate^k`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExponentialCooling {
+    pub initial_temperature: f64,
+    pub rate: f64,
+}
+
+/// Cooling schedule `T(k) = max(T0 - decrement * k, 0)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearCooling {
+    pub initial_temperature: f64,
+    pub decrement: f64,
+}
+
+/// Cooling schedule `T(k) = T0 / ln(k + e)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogarithmicCooling {
+    pub initial_temperature: f64,
+}
+
+impl ExponentialCooling {
+    /// Creates a new `ExponentialCooling` schedule. `rate` must lie in (0, 1).
+    pub fn new(initial_temperature: f64, rate: f64) -> Self {
+        assert!(
+            initial_temperature > 0.0,
+            "initial temperature must be positive"
+        );
+        assert!(rate > 0.0 && rate < 1.0, "cooling rate must lie in (0, 1)");
+
+        Self {
+            initial_temperature,
+            rate,
+        }
+    }
+}
+
+impl LinearCooling {
+    /// Creates a new `LinearCooling` schedule
+    pub fn new(initial_temperature: f64, decrement: f64) -> Self {
+        assert!(
+            initial_temperature > 0.0,
+            "initial temperature must be positive"
+        );
+        assert!(decrement > 0.0, "temperature decrement must be positive");
+
+        Self {
+            initial_temperature,
+            decrement,
+        }
+    }
+}
+
+impl LogarithmicCooling {
+    /// Creates a new `LogarithmicCooling` schedule
+    pub fn new(initial_temperature: f64) -> Self {
+        assert!(
+            initial_temperature > 0.0,
+            "initial temperature must be positive"
+        );
+
+        Self {
+            initial_temperature,
+        }
+    }
+}
+
+impl CoolingSchedule for ExponentialCooling {
+    fn temperature(&self, iteration: u32) -> f64 {
+        self.initial_temperature * self.rate.powf(iteration as f64)
+    }
+}
+
+impl CoolingSchedule for LinearCooling {
+    fn temperature(&self, iteration: u32) -> f64 {
+        (self.initial_temperature - self.decrement * iteration as f64).max(0.0)
+    }
+}
+
+impl CoolingSchedule for LogarithmicCooling {
+    fn temperature(&self, iteration: u32) -> f64 {
+        self.initial_temperature / (iteration as f64 + std::f64::consts::E).ln()
+    }
+}
+
+/// Simulated annealing optimizer. Every iteration proposes a Gaussian step away from the current
+/// point, which is always accepted when it improves the image and otherwise accepted with
+/// probability `exp(delta_f / T)`. The step size shrinks with the square root of the relative
+/// temperature, so the search narrows as the run cools down.
+pub struct SimulatedAnnealing {
+    /// dimension of the optimization problem
+    dimension: u32,
+
+    /// starting point of the search
+    init_point: Point,
+
+    /// bounds of the search space
+    bounds: HypercubeBounds,
+
+    /// standard deviation of the proposal steps at the initial temperature
+    step_size: f64,
+
+    /// temperature of the run at every iteration
+    schedule: Box<dyn CoolingSchedule>,
+
+    /// conditions that terminate the optimization
+    criteria: StoppingCriteria,
+
+    /// random number generator used for proposals and acceptance
+    rng: StdRng,
+}
+
+impl SimulatedAnnealing {
+    /// Returns a new `SimulatedAnnealing` optimizer that cools down exponentially from a
+    /// temperature of 1
+    ///
+    /// # Arguments
+    ///
+    /// * `init_point` - the starting point of the search
+    /// * `lower_bound` - the lower bound of the search space
+    /// * `upper_bound` - the upper bound of the search space
+    /// * `criteria` - conditions that terminate the optimization. `tol_x` is compared against the
+    ///   current step size and `tol_f` against the improvement of the best value over the last
+    ///   `100 * n` iterations.
+    ///
+    pub fn new(
+        init_point: Point,
+        lower_bound: f64,
+        upper_bound: f64,
+        criteria: StoppingCriteria,
+    ) -> Self {
+        assert!(
+            upper_bound > lower_bound,
+            "upper bound not strictly larger than lower bound"
+        );
+        assert!(
+            init_point.max_val().unwrap() <= upper_bound,
+            "init_point not inside upper bound"
+        );
+        assert!(
+            init_point.min_val().unwrap() >= lower_bound,
+            "init_point not inside lower bound"
+        );
+
+        let dimension = init_point.dim();
+
+        Self {
+            dimension,
+            bounds: HypercubeBounds::new(dimension, lower_bound, upper_bound),
+            init_point,
+            step_size: 0.1 * (upper_bound - lower_bound),
+            schedule: Box::new(ExponentialCooling::new(1.0, 0.995)),
+            criteria,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Sets the cooling schedule. The initial temperature should be on the scale of typical
+    /// differences between images of the objective function.
+    pub fn set_cooling_schedule(&mut self, schedule: impl CoolingSchedule + 'static) {
+        assert!(
+            schedule.temperature(0) > 0.0,
+            "initial temperature must be positive"
+        );
+        self.schedule = Box::new(schedule);
+    }
+
+    /// Sets the standard deviation of the proposal steps at the initial temperature. Defaults to
+    /// 10% of the search space side length.
+    pub fn set_step_size(&mut self, step_size: f64) {
+        assert!(step_size > 0.0, "step size must be positive");
+        self.step_size = step_size;
+    }
+
+    /// Seeds the random number generator used for proposals and acceptance
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Maximizes the objective function evaluated by `backend`
+    pub fn maximize_with<B>(&mut self, backend: &B) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + ?Sized,
+    {
+        let mut budget = Budget::start(&self.criteria);
+        let mut history: Vec<IterationRecord> = Vec::new();
+
+        if let Some(exit_code) = budget.check(1) {
+            return HypercubeOptimizerResult::new(exit_code, 0, 0, None, budget.elapsed());
+        }
+
+        let mut current = match evaluate(backend, &mut budget, self.init_point.clone()) {
+            Some(current) => current,
+            None => {
+                log::error!("initial point failed to evaluate");
+                return HypercubeOptimizerResult::new(1, 0, 1, None, budget.elapsed());
+            }
+        };
+        let mut best = current.clone();
+
+        let initial_temperature = self.schedule.temperature(0);
+
+        // best values of recent iterations, used for the tol_f check
+        let window = 100 * self.dimension as usize;
+        let mut recent_best: VecDeque<f64> = VecDeque::with_capacity(window);
+
+        let exit_code = loop {
+            if let Some(exit_code) = budget.check(1) {
+                break exit_code;
+            }
+
+            let temperature = self.schedule.temperature(budget.loops());
+            let step_size = self.step_size * (temperature / initial_temperature).sqrt();
+
+            if step_size < self.criteria.tol_x {
+                log::info!("optimization process terminated due to input convergence");
+                break 0;
+            }
+
+            // <----- propose and accept ----->
+
+            let candidate = Point::random_gaussian(&mut self.rng, current.get_point(), step_size)
+                .clamp(&self.bounds);
+
+            if let Some(candidate) = evaluate(backend, &mut budget, candidate) {
+                let delta_f = candidate.get_eval() - current.get_eval();
+
+                if delta_f >= 0.0 || self.rng.gen::<f64>() < (delta_f / temperature).exp() {
+                    current = candidate;
+                }
+
+                if current > best {
+                    best = current.clone();
+                }
+            }
+
+            budget.record_loop();
+
+            history.push(IterationRecord {
+                loop_index: budget.loops() - 1,
+                fn_evals: budget.fn_evals(),
+                best_f: best.get_eval(),
+                diagonal_len: step_size * (self.dimension as f64).sqrt(),
+            });
+
+            // <----- image convergence ----->
+
+            if recent_best.len() == window {
+                recent_best.pop_front();
+            }
+            recent_best.push_back(best.get_eval());
+
+            if recent_best.len() == window
+                && recent_best.back().unwrap() - recent_best.front().unwrap() <= self.criteria.tol_f
+            {
+                log::info!("optimization process terminated due to image convergence");
+                break 0;
+            }
+        };
+
+        HypercubeOptimizerResult::new(
+            exit_code,
+            budget.loops(),
+            budget.fn_evals(),
+            Some(&best),
+            budget.elapsed(),
+        )
+        .with_history(history)
+    }
+}
+
+impl Optimizer for SimulatedAnnealing {
+    fn maximize(&mut self, obj_function: &Objective) -> HypercubeOptimizerResult {
+        self.maximize_with(&LocalBackend::new(obj_function))
+    }
+}
+
+/// Evaluates a single point, returning `None` if the evaluation failed or returned NaN
+fn evaluate<B>(backend: &B, budget: &mut Budget, point: Point) -> Option<PointEval>
+where
+    B: EvaluationBackend + ?Sized,
+{
+    let image = backend
+        .evaluate_batch(std::slice::from_ref(&point))
+        .remove(0);
+    budget.record_evals(1);
+
+    match image {
+        Ok(image) if !image.is_nan() => Some(PointEval::from_image(point, image)),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("evaluation failed at {:?}: {:?}", point, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::{neg_rastrigin, neg_sphere};
+    use crate::point;
+
+    fn criteria(max_eval: u32) -> StoppingCriteria {
+        StoppingCriteria {
+            tol_x: 1e-6,
+            tol_f: 1e-9,
+            max_loop: 100_000,
+            max_eval,
+            max_timeout: 60,
+        }
+    }
+
+    #[test]
+    fn cooling_schedules() {
+        let exponential = ExponentialCooling::new(10.0, 0.5);
+        assert_eq!(exponential.temperature(0), 10.0);
+        assert_eq!(exponential.temperature(2), 2.5);
+
+        let linear = LinearCooling::new(10.0, 3.0);
+        assert_eq!(linear.temperature(1), 7.0);
+        assert_eq!(linear.temperature(4), 0.0);
+
+        let logarithmic = LogarithmicCooling::new(10.0);
+        assert_eq!(logarithmic.temperature(0), 10.0);
+        assert!(logarithmic.temperature(100) < logarithmic.temperature(10));
+    }
+
+    #[test]
+    #[should_panic]
+    fn exponential_cooling_invalid_rate() {
+        ExponentialCooling::new(1.0, 1.5);
+    }
+
+    #[test]
+    fn improves_on_sphere() {
+        let mut annealing = SimulatedAnnealing::new(point![3.0; 2], -5.0, 5.0, criteria(5_000));
+        annealing.set_seed(3);
+
+        let result = Optimizer::maximize(&mut annealing, &neg_sphere);
+
+        assert!(result.get_best_f().unwrap() > -0.01);
+        assert!(result.get_fn_evals() <= 5_000);
+    }
+
+    #[test]
+    fn custom_cooling_schedule() {
+        struct Constant;
+
+        impl CoolingSchedule for Constant {
+            fn temperature(&self, _iteration: u32) -> f64 {
+                0.5
+            }
+        }
+
+        let mut annealing = SimulatedAnnealing::new(point![3.0; 2], -5.12, 5.12, criteria(500));
+        annealing.set_cooling_schedule(Constant);
+        annealing.set_seed(4);
+
+        let result = Optimizer::maximize(&mut annealing, &neg_rastrigin);
+
+        assert!(result.get_fn_evals() <= 500);
+        assert_eq!(result.get_history().len() as u32, result.get_loops());
+        assert!(result.get_best_f().unwrap() >= neg_rastrigin(&point![3.0; 2]));
+    }
+
+    #[test]
+    fn seeded_runs_match() {
+        let run = || {
+            let mut annealing =
+                SimulatedAnnealing::new(point![3.0; 3], -5.12, 5.12, criteria(1_000));
+            annealing.set_seed(5);
+            Optimizer::maximize(&mut annealing, &neg_rastrigin).get_best_f()
+        };
+
+        assert_eq!(run(), run());
+    }
+}
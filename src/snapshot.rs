@@ -0,0 +1,161 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::eval_log::EvalLogWriter;
+use crate::point::Point;
+
+/// File format of population snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnapshotFormat {
+    /// the binary evaluation log format, readable with `EvalLogReader`. Points that failed to
+    /// evaluate are left out.
+    #[default]
+    Binary,
+
+    /// CSV with a header row, one column per coordinate (`x0`, `x1`, ...) and a final `f` column
+    /// that is empty for failed evaluations
+    Csv,
+}
+
+/// Writes the population of every optimization loop to its own file in a directory, numbered in
+/// the order the loops ran, so the movement of the hypercube can be animated afterwards
+#[derive(Debug, Clone)]
+pub(crate) struct PopulationSnapshots {
+    dir: PathBuf,
+    format: SnapshotFormat,
+
+    /// number of the next snapshot file
+    next_index: u32,
+}
+
+impl PopulationSnapshots {
+    pub(crate) fn new(dir: PathBuf, format: SnapshotFormat) -> Self {
+        Self {
+            dir,
+            format,
+            next_index: 0,
+        }
+    }
+
+    /// Starts numbering the snapshot files from zero again, e.g. when a new optimization starts
+    pub(crate) fn rewind(&mut self) {
+        self.next_index = 0;
+    }
+
+    /// Writes `population` and the `images` of its points to the next snapshot file
+    pub(crate) fn write(&mut self, population: &[Point], images: &[Option<f64>]) -> io::Result<()> {
+        assert_eq!(
+            population.len(),
+            images.len(),
+            "population and image counts do not match"
+        );
+
+        fs::create_dir_all(&self.dir)?;
+
+        let extension = match self.format {
+            SnapshotFormat::Binary => "bin",
+            SnapshotFormat::Csv => "csv",
+        };
+        let path = self
+            .dir
+            .join(format!("population_{:05}.{}", self.next_index, extension));
+        self.next_index += 1;
+
+        match self.format {
+            SnapshotFormat::Binary => write_binary(&path, population, images),
+            SnapshotFormat::Csv => write_csv(&path, population, images),
+        }
+    }
+}
+
+fn write_binary(path: &Path, population: &[Point], images: &[Option<f64>]) -> io::Result<()> {
+    let dimension = population.first().map_or(1, |p| p.dim());
+    let mut writer = EvalLogWriter::create(path, dimension)?;
+
+    for (point, image) in population.iter().zip(images) {
+        if let Some(image) = image {
+            writer.append(point, *image)?;
+        }
+    }
+
+    writer.flush()
+}
+
+fn write_csv(path: &Path, population: &[Point], images: &[Option<f64>]) -> io::Result<()> {
+    let dimension = population.first().map_or(0, |p| p.dim());
+
+    let mut csv = String::new();
+
+    for i in 0..dimension {
+        write!(csv, "x{},", i).unwrap();
+    }
+    csv.push_str("f\n");
+
+    for (point, image) in population.iter().zip(images) {
+        for coord in point.iter() {
+            write!(csv, "{},", coord).unwrap();
+        }
+
+        if let Some(image) = image {
+            write!(csv, "{}", image).unwrap();
+        }
+        csv.push('\n');
+    }
+
+    fs::write(path, csv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval_log::EvalLogReader;
+    use crate::evaluation::PointEval;
+    use crate::point;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("hypercube-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn csv_snapshots() {
+        let dir = temp_dir("csv-snapshots");
+        let mut snapshots = PopulationSnapshots::new(dir.clone(), SnapshotFormat::Csv);
+
+        let population = vec![point![1.0, -2.5], point![0.5, 3.0]];
+
+        snapshots.write(&population, &[Some(-4.0), None]).unwrap();
+        snapshots
+            .write(&population, &[Some(1.5), Some(2.0)])
+            .unwrap();
+
+        let first = fs::read_to_string(dir.join("population_00000.csv")).unwrap();
+        let second = fs::read_to_string(dir.join("population_00001.csv")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first, "x0,x1,f\n1,-2.5,-4\n0.5,3,\n");
+        assert_eq!(second, "x0,x1,f\n1,-2.5,1.5\n0.5,3,2\n");
+    }
+
+    #[test]
+    fn binary_snapshots() {
+        let dir = temp_dir("binary-snapshots");
+        let mut snapshots = PopulationSnapshots::new(dir.clone(), SnapshotFormat::Binary);
+
+        snapshots
+            .write(&[point![1.0, -2.5], point![0.5, 3.0]], &[Some(-4.0), None])
+            .unwrap();
+
+        let records: Vec<_> = EvalLogReader::open(dir.join("population_00000.bin"))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            records,
+            vec![PointEval::from_image(point![1.0, -2.5], -4.0)]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::time::{Duration, Instant};
+
+/// Stopping conditions shared by all optimizers in this crate
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoppingCriteria {
+    /// desired tolerance for the difference between consecutive function inputs
+    pub tol_x: f64,
+
+    /// desired tolerance for the difference between consective function evaluations
+    pub tol_f: f64,
+
+    /// maximum number of optimization loops allowed
+    pub max_loop: u32,
+
+    /// maximum number of function evaluations allowed
+    pub max_eval: u32,
+
+    /// maximum amount of time (in seconds) to optimize objective function
+    pub max_timeout: u32,
+}
+
+impl Default for StoppingCriteria {
+    fn default() -> Self {
+        Self {
+            tol_x: 0.01,
+            tol_f: 0.1,
+            max_loop: 2000,
+            max_eval: 5000,
+            max_timeout: 120,
+        }
+    }
+}
+
+/// Keeps track of the loops, function evaluations, and time used by an optimization run and
+/// decides when the run has exhausted its budget
+#[derive(Debug, Clone)]
+pub(crate) struct Budget {
+    criteria: StoppingCriteria,
+    start_time: Instant,
+    fn_evals: u32,
+    loops: u32,
+}
+
+impl Budget {
+    /// Starts tracking a new optimization run
+    pub(crate) fn start(criteria: &StoppingCriteria) -> Self {
+        Self {
+            criteria: criteria.clone(),
+            start_time: Instant::now(),
+            fn_evals: 0,
+            loops: 0,
+        }
+    }
+
+    pub(crate) fn record_evals(&mut self, fn_evals: u32) {
+        self.fn_evals += fn_evals;
+    }
+
+    pub(crate) fn record_loop(&mut self) {
+        self.loops += 1;
+    }
+
+    pub(crate) fn fn_evals(&self) -> u32 {
+        self.fn_evals
+    }
+
+    pub(crate) fn loops(&self) -> u32 {
+        self.loops
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.start_time.elapsed()
+    }
+
+    /// Number of function evaluations left before `max_eval` is reached
+    pub(crate) fn remaining_evals(&self) -> u32 {
+        self.criteria.max_eval.saturating_sub(self.fn_evals)
+    }
+
+    /// Checks whether another loop needing `next_evals` function evaluations fits in the budget.
+    /// Returns the exit code to stop with if it doesn't: 2 when the loop or evaluation budget is
+    /// exhausted and 3 when the optimization timed out.
+    pub(crate) fn check(&self, next_evals: u32) -> Option<u32> {
+        if self.elapsed() >= Duration::from_secs(self.criteria.max_timeout as u64) {
+            Some(3)
+        } else if self.loops >= self.criteria.max_loop || next_evals > self.remaining_evals() {
+            Some(2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Mann-Kendall trend statistic of `values` in the order they were observed, as a z-score. Large
+/// positive values indicate an increasing trend, large negative values a decreasing one, and
+/// values near zero no trend at all.
+pub(crate) fn mann_kendall_z(values: &[f64]) -> f64 {
+    let n = values.len();
+
+    if n < 3 {
+        return 0.0;
+    }
+
+    let s: f64 = (0..n)
+        .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+        .map(|(i, j)| match values[j].partial_cmp(&values[i]) {
+            Some(std::cmp::Ordering::Greater) => 1.0,
+            Some(std::cmp::Ordering::Less) => -1.0,
+            _ => 0.0,
+        })
+        .sum();
+
+    let n = n as f64;
+    let variance = n * (n - 1.0) * (2.0 * n + 5.0) / 18.0;
+
+    // continuity correction
+    let corrected = s - s.signum();
+
+    corrected / variance.sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_exhausted_by_evals() {
+        let criteria = StoppingCriteria {
+            max_eval: 100,
+            ..StoppingCriteria::default()
+        };
+        let mut budget = Budget::start(&criteria);
+
+        budget.record_evals(90);
+
+        assert_eq!(budget.check(10), None);
+        assert_eq!(budget.check(11), Some(2));
+    }
+
+    #[test]
+    fn budget_exhausted_by_loops() {
+        let criteria = StoppingCriteria {
+            max_loop: 2,
+            ..StoppingCriteria::default()
+        };
+        let mut budget = Budget::start(&criteria);
+
+        budget.record_loop();
+        assert_eq!(budget.check(0), None);
+
+        budget.record_loop();
+        assert_eq!(budget.check(0), Some(2));
+    }
+
+    #[test]
+    fn mann_kendall_trends() {
+        let increasing: Vec<f64> = (0..20).map(|i| i as f64).collect();
+        let decreasing: Vec<f64> = increasing.iter().rev().copied().collect();
+        let alternating: Vec<f64> = (0..20).map(|i| (i % 2) as f64).collect();
+
+        assert!(mann_kendall_z(&increasing) > 5.0);
+        assert!(mann_kendall_z(&decreasing) < -5.0);
+        assert!(mann_kendall_z(&alternating).abs() < 1.0);
+        assert_eq!(mann_kendall_z(&[1.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn budget_exhausted_by_timeout() {
+        let criteria = StoppingCriteria {
+            max_timeout: 0,
+            ..StoppingCriteria::default()
+        };
+        let budget = Budget::start(&criteria);
+
+        assert_eq!(budget.check(0), Some(3));
+    }
+}
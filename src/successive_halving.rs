@@ -0,0 +1,303 @@
+use std::time::Instant;
+
+use crate::backend::{EvaluationBackend, LocalBackend, MaybeSync, Objective};
+use crate::evaluation::PointEval;
+use crate::optimizer::{HypercubeOptimizer, HypercubeOptimizerConfig, Optimizer};
+use crate::point::Point;
+use crate::result::{HypercubeOptimizerResult, IterationRecord};
+
+/// Successive halving over several candidate regions of the search space. Every initial point
+/// starts its own hypercube optimization, called an arm. The arms take turns in rounds: every round
+/// splits an equal share of the remaining `max_eval` budget between the arms still running, then
+/// drops the worst half of the arms, so the budget of the culled arms goes to the survivors. The
+/// last arm left gets whatever budget remains.
+///
+/// This avoids committing the whole budget to a single basin of attraction, which matters for
+/// expensive objectives. Arms start from hypercubes spanning the search space unless the
+/// configuration sets a smaller `initial_scale`, which centers them on their initial points.
+pub struct SuccessiveHalving {
+    /// initial point of every arm
+    init_points: Vec<Point>,
+
+    /// lower bound of the search space
+    lower_bound: f64,
+
+    /// upper bound of the search space
+    upper_bound: f64,
+
+    /// tuning options shared by every arm. `max_eval` is the budget of all arms together.
+    config: HypercubeOptimizerConfig,
+
+    /// number of points every arm samples per loop
+    population_size: Option<u64>,
+}
+
+/// One hypercube optimization of a successive halving run
+struct Arm {
+    optimizer: HypercubeOptimizer,
+
+    /// result of the latest round of the arm, covering all of its rounds so far
+    result: Option<HypercubeOptimizerResult>,
+
+    /// per-loop history of all rounds of the arm
+    history: Vec<IterationRecord>,
+}
+
+impl Arm {
+    fn loops(&self) -> u32 {
+        self.result.as_ref().map_or(0, |r| r.get_loops())
+    }
+
+    fn fn_evals(&self) -> u32 {
+        self.result.as_ref().map_or(0, |r| r.get_fn_evals())
+    }
+
+    fn best_f(&self) -> Option<f64> {
+        self.result.as_ref().and_then(|r| r.get_best_f())
+    }
+
+    /// Checks whether the arm stopped only because it ran out of loops, so that another round can
+    /// continue it
+    fn can_continue(&self) -> bool {
+        match &self.result {
+            Some(result) => {
+                matches!(result.get_exit_code(), 0 | 2)
+                    && result.get_loops() == self.optimizer.get_config().stopping.max_loop
+                    && result.get_best_f().is_some()
+            }
+            None => true,
+        }
+    }
+
+    /// Runs the arm for `max_loop` more loops, continuing from where its last round stopped
+    fn run<B>(&mut self, backend: &B, max_loop: u32)
+    where
+        B: EvaluationBackend + MaybeSync + ?Sized,
+        B::Error: Send,
+    {
+        if let Some(checkpoint) = self
+            .result
+            .as_ref()
+            .and_then(|r| self.optimizer.checkpoint_after(r))
+        {
+            self.optimizer.set_resume_from(checkpoint);
+        }
+        self.optimizer.set_max_loop(self.loops() + max_loop);
+
+        let result = self.optimizer.maximize_with(backend);
+        self.history.extend_from_slice(result.get_history());
+        self.result = Some(result);
+    }
+}
+
+impl SuccessiveHalving {
+    /// Returns a new `SuccessiveHalving` optimizer
+    ///
+    /// # Arguments
+    ///
+    /// * `init_points` - the initial point of every arm
+    /// * `lower_bound` - the lower bound of the search space
+    /// * `upper_bound` - the upper bound of the search space
+    /// * `config` - tuning options shared by every arm. `max_eval` is the budget of all arms
+    ///   together and `max_loop` the limit of every arm. Seeds count up from the seed of `config`
+    ///   for every arm, if it has one.
+    ///
+    pub fn new(
+        init_points: Vec<Point>,
+        lower_bound: f64,
+        upper_bound: f64,
+        config: HypercubeOptimizerConfig,
+    ) -> Self {
+        assert!(!init_points.is_empty(), "successive halving needs an arm");
+        assert!(
+            init_points.iter().all(|p| p.dim() == init_points[0].dim()),
+            "initial point dimensions do not match"
+        );
+
+        Self {
+            init_points,
+            lower_bound,
+            upper_bound,
+            config,
+            population_size: None,
+        }
+    }
+
+    /// Sets the number of points every arm samples per loop. Small populations let more loops
+    /// run before arms are culled. Defaults to the population size of `HypercubeOptimizer`.
+    pub fn set_population_size(&mut self, population_size: u64) {
+        assert_ne!(population_size, 0, "population size cannot be zero");
+        self.population_size = Some(population_size);
+    }
+
+    /// Maximizes the objective function evaluated by `backend`. The result reports the best arm,
+    /// with the loops and function evaluations of all arms.
+    pub fn maximize_with<B>(&mut self, backend: &B) -> HypercubeOptimizerResult
+    where
+        B: EvaluationBackend + MaybeSync + ?Sized,
+        B::Error: Send,
+    {
+        let start_time = Instant::now();
+
+        let mut arms: Vec<Arm> = self
+            .init_points
+            .iter()
+            .enumerate()
+            .map(|(i, init_point)| {
+                let mut config = self.config.clone();
+                config.seed = config.seed.map(|seed| seed.wrapping_add(i as u64));
+
+                let mut optimizer = HypercubeOptimizer::with_config(
+                    init_point.clone(),
+                    self.lower_bound,
+                    self.upper_bound,
+                    config,
+                );
+
+                if let Some(population_size) = self.population_size {
+                    optimizer.set_population_size(population_size);
+                }
+
+                Arm {
+                    optimizer,
+                    result: None,
+                    history: Vec::new(),
+                }
+            })
+            .collect();
+
+        // loops and evaluations of the culled arms
+        let mut culled_loops = 0;
+        let mut culled_evals = 0;
+
+        loop {
+            let spent_evals = culled_evals + arms.iter().map(Arm::fn_evals).sum::<u32>();
+            let remaining_evals = self.config.stopping.max_eval.saturating_sub(spent_evals);
+
+            // the remaining budget is split evenly between the rounds left, one per halving
+            let rounds_left = arms.len().next_power_of_two().trailing_zeros() + 1;
+            let running = arms.iter().filter(|a| a.can_continue()).count() as u32;
+
+            if running == 0 {
+                break;
+            }
+
+            let arm_evals = remaining_evals / rounds_left / running;
+
+            for arm in arms.iter_mut().filter(|a| a.can_continue()) {
+                // every round evaluates the starting point of the arm once
+                let population_size = arm.optimizer.get_population_size();
+                let max_loop = (arm_evals.saturating_sub(1) as u64 / population_size) as u32;
+
+                let max_loop =
+                    max_loop.min(self.config.stopping.max_loop.saturating_sub(arm.loops()));
+
+                if max_loop > 0 {
+                    arm.run(backend, max_loop);
+                }
+            }
+
+            if arms.len() == 1 {
+                break;
+            }
+
+            // keep the better half of the arms, culling arms that never evaluated successfully first
+            arms.sort_by(|a, b| b.best_f().partial_cmp(&a.best_f()).unwrap());
+
+            for arm in arms.drain(arms.len().div_ceil(2)..) {
+                culled_loops += arm.loops();
+                culled_evals += arm.fn_evals();
+            }
+        }
+
+        let loops = culled_loops + arms.iter().map(Arm::loops).sum::<u32>();
+        let fn_evals = culled_evals + arms.iter().map(Arm::fn_evals).sum::<u32>();
+
+        let winner = arms.swap_remove(0);
+
+        let (exit_code, best_value) = match &winner.result {
+            Some(result) => (
+                result.get_exit_code(),
+                result
+                    .get_best_x()
+                    .zip(result.get_best_f())
+                    .map(|(x, f)| PointEval::from_image(x.clone(), f)),
+            ),
+            None => {
+                log::error!("evaluation budget too small to run any arm");
+                (1, None)
+            }
+        };
+
+        HypercubeOptimizerResult::new(
+            exit_code,
+            loops,
+            fn_evals,
+            best_value.as_ref(),
+            start_time.elapsed(),
+        )
+        .with_history(winner.history)
+    }
+}
+
+impl Optimizer for SuccessiveHalving {
+    fn maximize(&mut self, obj_function: &Objective) -> HypercubeOptimizerResult {
+        self.maximize_with(&LocalBackend::new(obj_function))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective_functions::neg_sphere;
+    use crate::point;
+    use crate::stopping::StoppingCriteria;
+
+    fn config(max_eval: u32) -> HypercubeOptimizerConfig {
+        HypercubeOptimizerConfig {
+            stopping: StoppingCriteria {
+                tol_x: 1e-12,
+                tol_f: 1e-12,
+                max_eval,
+                ..StoppingCriteria::default()
+            },
+            seed: Some(3),
+            initial_scale: 0.2,
+            ..HypercubeOptimizerConfig::default()
+        }
+    }
+
+    #[test]
+    fn keeps_best_region() {
+        // a unimodal objective centered away from the origin, only one arm starts near it
+        let objective = |p: &Point| -(&(p - &point![4.0, 4.0]) * &(p - &point![4.0, 4.0])).sum();
+
+        let init_points = vec![
+            point![-4.0, -4.0],
+            point![-4.0, 4.0],
+            point![4.0, -4.0],
+            point![3.5, 3.5],
+        ];
+
+        let mut halving = SuccessiveHalving::new(init_points, -5.0, 5.0, config(20_000));
+        halving.set_population_size(20);
+
+        let result = Optimizer::maximize(&mut halving, &objective);
+        let best_x = result.get_best_x().unwrap();
+
+        assert!(result.get_fn_evals() <= 20_000);
+        assert!((best_x - &point![4.0, 4.0]).len() < 0.1);
+    }
+
+    #[test]
+    fn single_arm_spends_budget() {
+        let mut halving = SuccessiveHalving::new(vec![point![2.0; 3]], -5.0, 5.0, config(1_001));
+        halving.set_population_size(10);
+
+        let result = Optimizer::maximize(&mut halving, &neg_sphere);
+
+        // one evaluation of the initial point and 100 loops
+        assert_eq!(result.get_fn_evals(), 1_001);
+        assert_eq!(result.get_loops(), 100);
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt::Write as _;
+
+/// Formats a field of a CSV row following RFC 4180. Fields containing a separator, quote, line
+/// break, or leading or trailing whitespace are quoted, with quotes inside doubled.
+pub(crate) fn csv_field(field: &str) -> String {
+    let needs_quotes = field.contains([',', '"', '\n', '\r'])
+        || field.starts_with(char::is_whitespace)
+        || field.ends_with(char::is_whitespace);
+
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Formats a string as a JSON string literal following RFC 8259. Quotes, backslashes, and control
+/// characters are escaped.
+pub(crate) fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{8}' => escaped.push_str("\\b"),
+            '\u{c}' => escaped.push_str("\\f"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+/// Formats an optional number as JSON, using `null` for missing or non-finite values, which JSON
+/// can't represent
+pub(crate) fn json_number(value: Option<f64>) -> String {
+    match value {
+        Some(v) if v.is_finite() => v.to_string(),
+        _ => "null".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("sphere"), "sphere");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\r\nlines"), "\"two\r\nlines\"");
+        assert_eq!(csv_field(" padded"), "\" padded\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn json_escaping() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(json_string("tab\there\r\n"), "\"tab\\there\\r\\n\"");
+        assert_eq!(
+            json_string("\u{0}\u{1f}\u{7f}"),
+            "\"\\u0000\\u001f\\u007f\""
+        );
+        assert_eq!(json_string("ünïcode ✓"), "\"ünïcode ✓\"");
+    }
+
+    #[test]
+    fn json_numbers() {
+        assert_eq!(json_number(Some(-0.5)), "-0.5");
+        assert_eq!(json_number(Some(1e300)).parse::<f64>().unwrap(), 1e300);
+        assert_eq!(json_number(Some(f64::INFINITY)), "null");
+        assert_eq!(json_number(Some(f64::NAN)), "null");
+        assert_eq!(json_number(None), "null");
+    }
+}
@@ -0,0 +1,200 @@
+use crate::backend::EvaluationBackend;
+use crate::lbfgs::DifferentiableObjective;
+use crate::linalg::{invert, mat_vec};
+use crate::point::Point;
+
+/// Affine map `native = matrix * x + offset` from the search space of the optimizer to the native
+/// parameter space of the objective function. Lets the optimizer work in a well-scaled hypercube
+/// (e.g. `[-1, 1]` along every dimension) while the objective receives its own parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputTransform {
+    dimension: u32,
+
+    /// row-major `dimension` x `dimension` matrix
+    matrix: Vec<f64>,
+
+    /// inverse of `matrix`
+    inverse: Vec<f64>,
+
+    offset: Point,
+}
+
+impl InputTransform {
+    /// Scales every dimension by `scale` and then shifts it by `offset`
+    pub fn scale_offset(scale: Point, offset: Point) -> Self {
+        assert_eq!(
+            scale.dim(),
+            offset.dim(),
+            "scale and offset dimensions do not match"
+        );
+        assert!(
+            scale.iter().all(|s| *s != 0.0),
+            "scale cannot be zero along any dimension"
+        );
+
+        let dimension = scale.dim() as usize;
+        let mut matrix = vec![0.0; dimension * dimension];
+
+        for (i, s) in scale.iter().enumerate() {
+            matrix[i * dimension + i] = *s;
+        }
+
+        Self::affine(matrix, offset)
+    }
+
+    /// Applies the row-major square `matrix` and then shifts by `offset`. The matrix must be
+    /// invertible so that points can be mapped back into the search space.
+    pub fn affine(matrix: Vec<f64>, offset: Point) -> Self {
+        let dimension = offset.dim();
+
+        assert_ne!(dimension, 0, "dimension cannot be zero");
+        assert_eq!(
+            matrix.len(),
+            (dimension * dimension) as usize,
+            "matrix is not {} x {}",
+            dimension,
+            dimension
+        );
+
+        let inverse = invert(&matrix, dimension as usize).expect("matrix is not invertible");
+
+        Self {
+            dimension,
+            matrix,
+            inverse,
+            offset,
+        }
+    }
+
+    /// Maps a point of the search space to the native parameter space
+    pub fn to_native(&self, point: &Point) -> Point {
+        assert_eq!(
+            point.dim(),
+            self.dimension,
+            "point dimension does not match transform dimension"
+        );
+
+        let coords: Vec<f64> = point.iter().copied().collect();
+
+        &Point::from_vec(mat_vec(&self.matrix, &coords)) + &self.offset
+    }
+
+    /// Maps a point of the native parameter space back to the search space
+    pub fn to_internal(&self, native: &Point) -> Point {
+        assert_eq!(
+            native.dim(),
+            self.dimension,
+            "point dimension does not match transform dimension"
+        );
+
+        let shifted: Vec<f64> = (native - &self.offset).iter().copied().collect();
+
+        Point::from_vec(mat_vec(&self.inverse, &shifted))
+    }
+
+    pub fn get_dimension(&self) -> u32 {
+        self.dimension
+    }
+}
+
+/// Backend that maps points to the native parameter space before handing them to another backend
+pub(crate) struct TransformedBackend<'a, B: ?Sized> {
+    backend: &'a B,
+    transform: &'a InputTransform,
+}
+
+impl<'a, B: ?Sized> TransformedBackend<'a, B> {
+    pub(crate) fn new(backend: &'a B, transform: &'a InputTransform) -> Self {
+        Self { backend, transform }
+    }
+}
+
+impl<B> EvaluationBackend for TransformedBackend<'_, B>
+where
+    B: EvaluationBackend + ?Sized,
+{
+    type Error = B::Error;
+
+    fn evaluate_batch(&self, points: &[Point]) -> Vec<Result<f64, Self::Error>> {
+        let native: Vec<Point> = points.iter().map(|p| self.transform.to_native(p)).collect();
+
+        self.backend.evaluate_batch(&native)
+    }
+}
+
+/// Differentiable objective seen from the search space of the optimizer. Gradients are mapped
+/// back with the chain rule.
+pub(crate) struct TransformedObjective<'a, D: ?Sized> {
+    objective: &'a D,
+    transform: &'a InputTransform,
+}
+
+impl<'a, D: ?Sized> TransformedObjective<'a, D> {
+    pub(crate) fn new(objective: &'a D, transform: &'a InputTransform) -> Self {
+        Self {
+            objective,
+            transform,
+        }
+    }
+}
+
+impl<D> DifferentiableObjective for TransformedObjective<'_, D>
+where
+    D: DifferentiableObjective + ?Sized,
+{
+    fn value(&self, x: &Point) -> f64 {
+        self.objective.value(&self.transform.to_native(x))
+    }
+
+    fn gradient(&self, x: &Point) -> Point {
+        let native_gradient = self.objective.gradient(&self.transform.to_native(x));
+        let n = self.transform.dimension as usize;
+
+        // d f(A x + b) / dx = A^T grad f
+        Point::from_vec(
+            (0..n)
+                .map(|j| {
+                    native_gradient
+                        .iter()
+                        .enumerate()
+                        .map(|(i, g)| self.transform.matrix[i * n + j] * g)
+                        .sum()
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point;
+
+    #[test]
+    fn scale_offset_round_trip() {
+        let transform = InputTransform::scale_offset(point![2.0, 0.5], point![1.0, -3.0]);
+
+        let native = transform.to_native(&point![1.0, 4.0]);
+
+        assert_eq!(native, point![3.0, -1.0]);
+        assert_eq!(transform.to_internal(&native), point![1.0, 4.0]);
+    }
+
+    #[test]
+    fn affine_round_trip() {
+        let transform = InputTransform::affine(vec![0.0, 1.0, -2.0, 1.0], point![0.5, 0.5]);
+
+        let point = point![1.5, -2.0];
+        let round_trip = transform.to_internal(&transform.to_native(&point));
+
+        for (a, b) in round_trip.iter().zip(point.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn singular_matrix() {
+        InputTransform::affine(vec![1.0, 2.0, 2.0, 4.0], point![0.0, 0.0]);
+    }
+}
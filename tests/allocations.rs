@@ -0,0 +1,54 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use hypercube_optimizer::point;
+use hypercube_optimizer::point::{Point, INLINE_DIMENSION};
+
+/// Allocator that counts heap allocations
+struct CountingAllocator {
+    allocations: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocations.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator {
+    allocations: AtomicUsize::new(0),
+};
+
+/// Returns the number of heap allocations made by `operation`
+fn count_allocations<F: FnOnce() -> Point>(operation: F) -> usize {
+    let before = ALLOCATOR.allocations.load(Ordering::SeqCst);
+    let point = operation();
+    let allocations = ALLOCATOR.allocations.load(Ordering::SeqCst) - before;
+
+    drop(point);
+    allocations
+}
+
+// a single test, so no other test thread allocates while counting
+#[test]
+fn low_dimensional_points_stay_inline() {
+    let n = INLINE_DIMENSION as u32;
+    let a = point![1.5; n];
+    let b = point![0.5; n];
+
+    assert_eq!(count_allocations(|| Point::fill(1.0, n)), 0);
+    assert_eq!(count_allocations(|| Point::from_slice(&[1.0, 2.0, 3.0])), 0);
+    assert_eq!(count_allocations(|| &a + &b), 0);
+    assert_eq!(count_allocations(|| &a * &b), 0);
+    assert_eq!(count_allocations(|| a.scale(2.0)), 0);
+    assert_eq!(count_allocations(|| a.iter().map(|x| x * 2.0).collect()), 0);
+
+    // larger points spill to the heap
+    assert_eq!(count_allocations(|| Point::fill(1.0, n + 1)), 1);
+}
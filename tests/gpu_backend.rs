@@ -0,0 +1,96 @@
+#![cfg(feature = "gpu-backend")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use hypercube_optimizer::backend::EvaluationBackend;
+use hypercube_optimizer::gpu_backend::{GpuBackend, GpuBackendError};
+use hypercube_optimizer::optimizer::HypercubeOptimizer;
+use hypercube_optimizer::point;
+use hypercube_optimizer::point::Point;
+
+/// Stand-in for a GPU kernel that computes the negated sphere function of every matrix row
+fn neg_sphere_kernel(points: &[f64], dimension: usize) -> Result<Vec<f64>, String> {
+    Ok(points
+        .chunks(dimension)
+        .map(|row| -row.iter().map(|x| x * x).sum::<f64>())
+        .collect())
+}
+
+#[test]
+fn evaluates_flat_matrix() {
+    let backend = GpuBackend::new(neg_sphere_kernel);
+    let points = vec![point![1.0, 2.0], point![0.0, 3.0], point![-1.0, 0.0]];
+
+    let images: Vec<f64> = backend
+        .evaluate_batch(&points)
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(images, vec![-5.0, -9.0, -1.0]);
+}
+
+#[test]
+fn splits_batch_into_launches() {
+    let launches = AtomicUsize::new(0);
+    let kernel = |points: &[f64], dimension: usize| {
+        launches.fetch_add(1, Ordering::SeqCst);
+        neg_sphere_kernel(points, dimension)
+    };
+
+    let backend = GpuBackend::new(kernel).with_max_launch_size(4);
+    let points: Vec<Point> = (0..10).map(|i| point![i as f64; 3]).collect();
+
+    let images = backend.evaluate_batch(&points);
+
+    assert_eq!(images.len(), 10);
+    assert_eq!(images[9], Ok(-243.0));
+    assert_eq!(launches.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn failed_launch_fails_its_points() {
+    let kernel = |points: &[f64], dimension: usize| {
+        if points[0] >= 5.0 {
+            Err("device lost")
+        } else {
+            Ok(vec![0.0; points.len() / dimension])
+        }
+    };
+
+    let backend = GpuBackend::new(kernel).with_max_launch_size(5);
+    let points: Vec<Point> = (0..10).map(|i| point![i as f64; 2]).collect();
+
+    let images = backend.evaluate_batch(&points);
+
+    assert!(images[..5].iter().all(|image| image.is_ok()));
+    assert!(images[5..]
+        .iter()
+        .all(|image| *image == Err(GpuBackendError::Kernel("\"device lost\"".to_string()))));
+}
+
+#[test]
+fn wrong_image_count() {
+    let backend = GpuBackend::new(|_: &[f64], _: usize| Ok::<_, String>(vec![1.0]));
+
+    let images = backend.evaluate_batch(&[point![1.0], point![2.0]]);
+
+    assert_eq!(
+        images[0],
+        Err(GpuBackendError::ImageCount {
+            expected: 2,
+            actual: 1
+        })
+    );
+}
+
+#[test]
+fn maximize_with_gpu_backend() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.1, 50, 5000, 120);
+    optimizer.set_seed(1);
+
+    let result = optimizer.maximize_with(&GpuBackend::new(neg_sphere_kernel));
+
+    assert!(result.get_best_f().unwrap() > -0.1);
+}
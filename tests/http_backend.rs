@@ -0,0 +1,128 @@
+#![cfg(feature = "http-backend")]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use hypercube_optimizer::backend::EvaluationBackend;
+use hypercube_optimizer::http_backend::{HttpBackend, HttpBackendError};
+use hypercube_optimizer::point;
+use hypercube_optimizer::point::Point;
+use serde_json::{json, Value};
+
+/// Starts a minimal HTTP server that replies to every request with the summation of each point.
+/// The first `failures` requests are answered with `failure_status` instead.
+fn spawn_summation_server(failures: usize, failure_status: u16) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}/evaluate", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let server_requests = requests.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            // read headers to find the body length
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if line == "\r\n" {
+                    break;
+                }
+
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let request_number = server_requests.fetch_add(1, Ordering::SeqCst);
+
+            let (status, reply) = if request_number < failures {
+                (failure_status, json!({ "error": "try again" }))
+            } else {
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                let values: Vec<f64> = request["points"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|p| {
+                        p.as_array()
+                            .unwrap()
+                            .iter()
+                            .map(|x| x.as_f64().unwrap())
+                            .sum()
+                    })
+                    .collect();
+
+                (200, json!({ "values": values }))
+            };
+
+            let reply = reply.to_string();
+            write!(
+                stream,
+                "HTTP/1.1 {} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+                Connection: close\r\n\r\n{}",
+                status,
+                reply.len(),
+                reply
+            )
+            .unwrap();
+        }
+    });
+
+    (address, requests)
+}
+
+#[test]
+fn evaluates_points_in_batches() {
+    let (address, requests) = spawn_summation_server(0, 500);
+    let backend = HttpBackend::new(address)
+        .with_batch_size(4)
+        .with_max_concurrency(2);
+
+    let points: Vec<Point> = (0..10).map(|i| point![i as f64; 3]).collect();
+    let images = backend.evaluate_batch(&points);
+
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    for (i, image) in images.into_iter().enumerate() {
+        assert_eq!(image.unwrap(), 3.0 * i as f64);
+    }
+}
+
+#[test]
+fn retries_server_errors() {
+    let (address, requests) = spawn_summation_server(2, 503);
+    let backend = HttpBackend::new(address).with_retries(2, Duration::from_millis(1));
+
+    let images = backend.evaluate_batch(&[point![1.0, 2.0]]);
+
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+    assert_eq!(images, vec![Ok(3.0)]);
+}
+
+#[test]
+fn client_errors_are_not_retried() {
+    let (address, requests) = spawn_summation_server(1, 400);
+    let backend = HttpBackend::new(address).with_retries(2, Duration::from_millis(1));
+
+    let images = backend.evaluate_batch(&[point![1.0, 2.0], point![3.0, 4.0]]);
+
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        images,
+        vec![
+            Err(HttpBackendError::Status(400)),
+            Err(HttpBackendError::Status(400))
+        ]
+    );
+}
@@ -9,16 +9,16 @@ fn eight_corners() {
     // shrink HC to half its size
     test_hypercube.shrink(0.50);
 
-    test_hypercube
+    assert!(test_hypercube
         .try_displace_to(&point![30.0, 30.0, 30.0])
-        .is_ok();
-    test_hypercube
+        .is_ok());
+    assert!(test_hypercube
         .try_displace_to(&point![30.0, 30.0, 90.0])
-        .is_ok();
+        .is_ok());
 
-    test_hypercube
+    assert!(test_hypercube
         .try_displace_to(&point![30.0, 90.0, 30.0])
-        .is_ok();
+        .is_ok());
     test_hypercube
         .try_displace_to(&point![30.0, 90.0, 90.0])
         .unwrap();
@@ -81,7 +81,7 @@ fn shrink_and_try_displace_by_1() {
     let mut test_hypercube = Hypercube::new(5, 0.0, 120.0);
     let small_vector = point![1.0; 5];
 
-    test_hypercube.shrink((59.0 / 60.0) as f64);
+    test_hypercube.shrink(59.0 / 60.0);
     assert!(test_hypercube.try_displace_by(&small_vector).is_ok());
 
     // displacing again should fail
@@ -0,0 +1,1266 @@
+use hypercube_optimizer::backend::EvaluationBackend;
+use hypercube_optimizer::bounds::HypercubeBounds;
+use hypercube_optimizer::checkpoint::Checkpoint;
+use hypercube_optimizer::cmaes::CmaEs;
+use hypercube_optimizer::eval_log::{EvalLogReader, EvalLogWriter};
+use hypercube_optimizer::events::OptimizerEvent;
+use hypercube_optimizer::experiment::run_experiment;
+use hypercube_optimizer::grid_search::grid_search;
+use hypercube_optimizer::nelder_mead::NelderMead;
+use hypercube_optimizer::normalization::OutputNormalization;
+use hypercube_optimizer::objective_functions::{
+    neg_rastrigin, neg_rosenbrock, neg_rosenbrock_gradient, neg_sphere, neg_sphere_gradient,
+};
+use hypercube_optimizer::optimizer::{
+    HypercubeOptimizer, HypercubeOptimizerConfig, Optimizer, PlateauDetection, Resampling,
+    RestartPolicy, ShrinkStrategy,
+};
+use hypercube_optimizer::particle_swarm::ParticleSwarm;
+use hypercube_optimizer::point;
+use hypercube_optimizer::point::Point;
+use hypercube_optimizer::random_search::RandomSearch;
+use hypercube_optimizer::sampler::Sampler;
+use hypercube_optimizer::snapshot::SnapshotFormat;
+use hypercube_optimizer::stopping::StoppingCriteria;
+use hypercube_optimizer::successive_halving::SuccessiveHalving;
+use hypercube_optimizer::transform::InputTransform;
+
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+#[cfg(feature = "parallel")]
+fn maximize_with_max_threads() {
+    let mut optimizer = HypercubeOptimizer::new(point![1.0; 2], -5.0, 5.0, 0.01, 0.0, 5, 5000, 120);
+    optimizer.set_max_threads(2).unwrap();
+
+    let max_seen_threads = AtomicUsize::new(0);
+
+    let result = optimizer.maximize(|p: &Point| {
+        max_seen_threads.fetch_max(rayon::current_num_threads(), Ordering::Relaxed);
+        neg_sphere(p)
+    });
+
+    assert_eq!(max_seen_threads.load(Ordering::Relaxed), 2);
+    assert!(result.get_best_f().is_some());
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn maximize_with_shared_thread_pool() {
+    let pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(3)
+            .build()
+            .unwrap(),
+    );
+
+    let mut optimizer = HypercubeOptimizer::new(point![1.0; 2], -5.0, 5.0, 0.01, 0.0, 5, 5000, 120);
+    optimizer.set_thread_pool(pool.clone());
+
+    let pool_evals = AtomicUsize::new(0);
+
+    let result = optimizer.maximize(|p: &Point| {
+        if pool.current_thread_index().is_some() {
+            pool_evals.fetch_add(1, Ordering::Relaxed);
+        }
+        neg_sphere(p)
+    });
+
+    // everything except the initial point evaluation happens inside the pool
+    assert_eq!(
+        pool_evals.load(Ordering::Relaxed) as u32,
+        result.get_fn_evals() - 1
+    );
+}
+
+#[test]
+#[cfg(not(feature = "parallel"))]
+fn maximize_non_sync_objective() {
+    let mut optimizer = HypercubeOptimizer::new(point![1.0; 2], -5.0, 5.0, 0.01, 0.0, 5, 5000, 120);
+
+    // a `Cell` is not `Sync`, which is only required when populations are evaluated in parallel
+    let evaluations = std::cell::Cell::new(0);
+
+    let result = Optimizer::maximize(&mut optimizer, &|p: &Point| {
+        evaluations.set(evaluations.get() + 1);
+        neg_sphere(p)
+    });
+
+    assert_eq!(evaluations.get(), result.get_fn_evals());
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+#[should_panic]
+fn set_max_threads_zero() {
+    let mut optimizer = HypercubeOptimizer::new(point![1.0; 2], -5.0, 5.0, 0.01, 0.0, 5, 5000, 120);
+    let _ = optimizer.set_max_threads(0);
+}
+
+/// Backend that can only evaluate points inside the unit ball
+struct UnitBallBackend;
+
+impl EvaluationBackend for UnitBallBackend {
+    type Error = String;
+
+    fn evaluate_batch(&self, points: &[Point]) -> Vec<Result<f64, Self::Error>> {
+        points
+            .iter()
+            .map(|p| {
+                if p.len() <= 1.0 {
+                    Ok(neg_sphere(p))
+                } else {
+                    Err(format!("{:?} is outside the unit ball", p))
+                }
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn maximize_with_custom_backend() {
+    let mut optimizer = HypercubeOptimizer::new(point![0.5; 2], -5.0, 5.0, 0.01, 0.0, 5, 5000, 120);
+    optimizer.set_seed(1);
+
+    let result = optimizer.maximize_with(&UnitBallBackend);
+
+    // five loops are not enough to converge
+    assert_eq!(result.get_exit_code(), 2);
+    assert!(result.get_best_x().unwrap().len() <= 1.0);
+}
+
+#[test]
+fn maximize_with_nan_images() {
+    let mut optimizer = HypercubeOptimizer::new(point![0.5; 2], -5.0, 5.0, 0.01, 0.0, 5, 5000, 120);
+    optimizer.set_seed(1);
+
+    // NaN outside the unit ball, treated like a failed evaluation
+    let result = optimizer.maximize(|p: &Point| if p.len() <= 1.0 { -p.len() } else { f64::NAN });
+
+    assert_eq!(result.get_exit_code(), 2);
+    assert!(result.get_best_x().unwrap().len() <= 1.0);
+}
+
+#[test]
+fn maximize_with_failing_initial_point() {
+    let mut optimizer = HypercubeOptimizer::new(point![4.0; 2], -5.0, 5.0, 0.01, 0.0, 5, 5000, 120);
+
+    let result = optimizer.maximize_with(&UnitBallBackend);
+
+    assert_eq!(result.get_exit_code(), 1);
+    assert!(result.get_best_x().is_none());
+}
+
+#[test]
+fn optimizers_are_interchangeable() {
+    let criteria = StoppingCriteria {
+        max_eval: 3000,
+        ..StoppingCriteria::default()
+    };
+
+    let mut hypercube_optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 50, 3000, 120);
+    hypercube_optimizer.set_seed(1);
+
+    let mut random_search = RandomSearch::new(point![3.0; 2], -5.0, 5.0, criteria.clone());
+    random_search.set_seed(1);
+
+    let mut optimizers: Vec<Box<dyn Optimizer>> = vec![
+        Box::new(hypercube_optimizer),
+        Box::new(CmaEs::new(point![3.0; 2], -5.0, 5.0, criteria.clone())),
+        Box::new(NelderMead::new(point![3.0; 2], -5.0, 5.0, criteria.clone())),
+        Box::new(ParticleSwarm::new(point![3.0; 2], -5.0, 5.0, criteria)),
+        Box::new(random_search),
+    ];
+
+    for optimizer in optimizers.iter_mut() {
+        let result = optimizer.maximize(&neg_sphere);
+
+        assert!(result.get_best_f().unwrap() > -0.1);
+        assert!(result.get_fn_evals() <= 3000);
+    }
+}
+
+#[test]
+fn maximize_from_grid_search_point() {
+    let bounds = HypercubeBounds::new(2, -5.0, 5.0);
+    let init = grid_search(&bounds, 5, neg_sphere);
+
+    let mut optimizer =
+        HypercubeOptimizer::new(init.get_point().clone(), -5.0, 5.0, 0.01, 0.0, 5, 5000, 120);
+    let result = optimizer.maximize(neg_sphere);
+
+    assert!(result.get_best_f().unwrap() >= init.get_eval());
+}
+
+#[test]
+fn maximize_with_gradient_polishes_best_point() {
+    let run = |polish: bool| {
+        let mut optimizer =
+            HypercubeOptimizer::new(point![-1.0; 3], -2.0, 2.0, 0.0, 0.0, 20, 100_000, 120);
+        optimizer.set_seed(4);
+
+        if polish {
+            optimizer.maximize_with_gradient(&(neg_rosenbrock, neg_rosenbrock_gradient))
+        } else {
+            optimizer.maximize(neg_rosenbrock)
+        }
+    };
+
+    let unpolished = run(false);
+    let polished = run(true);
+
+    assert!(polished.get_best_f().unwrap() >= unpolished.get_best_f().unwrap());
+    assert!(polished.get_best_f().unwrap() > -1e-8);
+    assert!(polished.get_fn_evals() > unpolished.get_fn_evals());
+    assert!(polished.get_best_x().unwrap().max_val().unwrap() <= 2.0);
+}
+
+#[test]
+fn maximize_with_gradient_drops_stale_interval() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![-1.0; 3], -2.0, 2.0, 0.0, 0.0, 20, 100_000, 120);
+    optimizer.set_seed(4);
+    optimizer.set_resampling(Resampling::default());
+
+    let result = optimizer.maximize_with_gradient(&(neg_rosenbrock, neg_rosenbrock_gradient));
+
+    // the interval belonged to the best point before polishing
+    assert!(result.get_best_f().unwrap() > -1e-8);
+    assert!(result.get_best_f_interval().is_none());
+}
+
+#[test]
+fn maximize_with_trust_region_strategy() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 3], -5.0, 5.0, 0.01, 1e-6, 200, 100_000, 120);
+    optimizer.set_seed(2);
+    optimizer.set_shrink_strategy(ShrinkStrategy::TrustRegion {
+        expansion: 2.0,
+        contraction: 0.5,
+        success_threshold: 0.0,
+    });
+
+    let result = optimizer.maximize(neg_sphere);
+
+    assert!(result.get_best_f().unwrap() > -1e-3);
+    assert!(result.get_best_x().unwrap().max_val().unwrap() <= 5.0);
+}
+
+#[test]
+#[should_panic]
+fn trust_region_contraction_above_one() {
+    let mut optimizer = HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.1, 10, 500, 120);
+    optimizer.set_shrink_strategy(ShrinkStrategy::TrustRegion {
+        expansion: 2.0,
+        contraction: 1.5,
+        success_threshold: 0.0,
+    });
+}
+
+#[test]
+fn maximize_with_importance_sampling() {
+    for importance_sampling in [false, true] {
+        let mut optimizer =
+            HypercubeOptimizer::new(point![-3.0; 3], -5.0, 5.0, 0.01, 1e-9, 100, 100_000, 120);
+        optimizer.set_seed(6);
+        optimizer.set_importance_sampling(importance_sampling);
+
+        let result = optimizer.maximize(neg_sphere);
+        let weights = result.get_dimension_importance().unwrap();
+
+        assert_eq!(weights.len(), 3);
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(result.get_best_f().unwrap() > -0.1);
+    }
+}
+
+#[test]
+fn maximize_without_init_point() {
+    let config = HypercubeOptimizerConfig {
+        stopping: StoppingCriteria {
+            max_loop: 50,
+            ..StoppingCriteria::default()
+        },
+        seed: Some(3),
+        ..HypercubeOptimizerConfig::default()
+    };
+
+    let mut optimizer = HypercubeOptimizer::from_bounds(3, 0.0, 8.0, config);
+    let result = optimizer.maximize(|p: &Point| -(p - &point![6.0; 3]).len());
+
+    assert!(result.get_best_f().unwrap() > -0.5);
+}
+
+#[test]
+fn maximize_with_initial_candidates() {
+    let mut optimizer = HypercubeOptimizer::new(point![4.0; 2], -5.0, 5.0, 0.01, 0.0, 1, 5000, 120);
+    optimizer.set_seed(5);
+    optimizer.set_initial_candidates(vec![point![-3.0, 2.0], point![0.001, -0.001]]);
+
+    let result = optimizer.maximize(neg_sphere);
+    let population_size = 2u32.pow(2) * 10;
+
+    assert!(result.get_best_f().unwrap() >= neg_sphere(&point![0.001, -0.001]));
+    assert_eq!(result.get_fn_evals(), 3 + population_size);
+}
+
+#[test]
+#[should_panic]
+fn initial_candidate_out_of_bounds() {
+    let mut optimizer = HypercubeOptimizer::new(point![4.0; 2], -5.0, 5.0, 0.01, 0.0, 1, 5000, 120);
+    optimizer.set_initial_candidates(vec![point![6.0, 0.0]]);
+}
+
+#[test]
+fn maximize_with_restarts() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.12, 5.12, 0.01, 5.0, 500, 20_000, 120);
+    optimizer.set_seed(8);
+    optimizer.set_restart_policy(RestartPolicy {
+        max_restarts: 3,
+        population_growth: 2.0,
+    });
+
+    let result = optimizer.maximize(neg_rastrigin);
+    let restart_bests = result.get_restart_bests();
+
+    assert!(restart_bests.len() > 1);
+    assert!(restart_bests.len() <= 4);
+    assert_eq!(
+        result.get_best_f(),
+        restart_bests.iter().map(|b| b.get_eval()).reduce(f64::max)
+    );
+    assert!(result.get_fn_evals() <= 20_000);
+    assert_eq!(
+        result.get_history().last().unwrap().best_f,
+        result.get_best_f().unwrap()
+    );
+}
+
+#[test]
+fn best_handle_tracks_best_value() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.1, 20, 5000, 120);
+    optimizer.set_seed(2);
+
+    let handle = optimizer.best_handle();
+    let done = AtomicBool::new(false);
+
+    let result = std::thread::scope(|scope| {
+        // poll the best value from another thread while the optimizer runs
+        let poller = scope.spawn(|| {
+            let mut polled = Vec::new();
+
+            while !done.load(Ordering::SeqCst) {
+                if let Some(best) = handle.get() {
+                    polled.push(best.get_eval());
+                }
+            }
+
+            polled
+        });
+
+        let result = optimizer.maximize(neg_sphere);
+        done.store(true, Ordering::SeqCst);
+
+        let polled = poller.join().unwrap();
+        assert!(polled.windows(2).all(|w| w[0] <= w[1]));
+
+        result
+    });
+
+    assert_eq!(
+        handle.get().map(|best| best.get_eval()),
+        result.get_best_f()
+    );
+}
+
+#[test]
+fn maximize_with_eval_log() {
+    let path = std::env::temp_dir().join(format!("hypercube-eval-log-{}.bin", std::process::id()));
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.1, 10, 5000, 120);
+    optimizer.set_seed(3);
+    optimizer.set_eval_log(EvalLogWriter::create(&path, 2).unwrap());
+
+    let result = optimizer.maximize(neg_sphere);
+
+    let records: Vec<_> = EvalLogReader::open(&path)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(records.len() as u32, result.get_fn_evals());
+    assert_eq!(records[0].get_point(), &point![3.0; 2]);
+    assert_eq!(
+        records.iter().map(|r| r.get_eval()).reduce(f64::max),
+        result.get_best_f()
+    );
+}
+
+#[test]
+fn maximize_stops_at_target_value() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 2000, 100_000, 120);
+    optimizer.set_seed(4);
+    optimizer.set_stop_at_value(-0.5);
+
+    let result = optimizer.maximize(neg_sphere);
+
+    assert_eq!(result.get_exit_code(), 5);
+    assert_eq!(result.get_message(), "target value reached");
+    assert!(result.get_best_f().unwrap() >= -0.5);
+    assert!(result.get_loops() < 2000);
+    assert_eq!(result.get_history().len() as u32, result.get_loops());
+}
+
+#[test]
+fn maximize_stops_at_target_value_reached_by_init_point() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![0.0; 2], -5.0, 5.0, 0.01, 0.0, 20, 5000, 120);
+    optimizer.set_stop_at_value(-0.5);
+
+    let result = optimizer.maximize(neg_sphere);
+
+    assert_eq!(result.get_exit_code(), 5);
+    assert_eq!(result.get_fn_evals(), 1);
+    assert_eq!(result.get_best_f(), Some(0.0));
+}
+
+#[test]
+fn maximize_with_relative_tolerance() {
+    // large offset makes any absolute tolerance meaningless
+    let objective = |p: &Point| 1e9 + neg_sphere(p);
+
+    let run = |rel_tol_f: f64| {
+        let mut optimizer =
+            HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 500, 100_000, 120);
+        optimizer.set_seed(7);
+        optimizer.set_rel_tol_f(rel_tol_f);
+        optimizer.maximize(objective)
+    };
+
+    assert_eq!(run(0.0).get_loops(), 500);
+    assert!(run(1e-9).get_loops() < 500);
+}
+
+#[test]
+fn maximize_with_short_stagnation_window() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 1.0, 2000, 100_000, 120);
+    optimizer.set_seed(1);
+    optimizer.set_stagnation_window(5);
+
+    let result = optimizer.maximize(neg_sphere);
+    let stagnation = result.get_stagnation().unwrap();
+
+    assert_eq!(result.get_exit_code(), 0);
+    assert!(result.get_loops() < 2000);
+    assert_eq!(stagnation.window, 5);
+    assert_eq!(stagnation.stagnant_loops, 5);
+}
+
+#[test]
+fn observer_receives_events() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 10, 5000, 120);
+    optimizer.set_seed(2);
+    optimizer.set_observer(move |event: &OptimizerEvent| {
+        recorded.lock().unwrap().push(event.clone());
+    });
+
+    let result = optimizer.maximize(neg_sphere);
+    let events = events.lock().unwrap();
+
+    assert_eq!(events[0], OptimizerEvent::LoopStarted { loop_index: 0 });
+
+    let evaluated = events
+        .iter()
+        .filter(|e| matches!(e, OptimizerEvent::Evaluated { .. }))
+        .count();
+    assert_eq!(evaluated as u32, result.get_loops());
+
+    for event in events.iter() {
+        if let OptimizerEvent::Shrunk { from, to } = event {
+            assert!(to < from);
+        }
+    }
+
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, OptimizerEvent::Displaced { .. })));
+}
+
+#[test]
+fn maximize_reports_phase_timings() {
+    // a slow objective function should dominate the optimization time
+    let slow_sphere = |x: &Point| {
+        std::thread::sleep(std::time::Duration::from_micros(200));
+        neg_sphere(x)
+    };
+
+    let mut optimizer = HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 5, 500, 120);
+    optimizer.set_seed(3);
+
+    let result = optimizer.maximize(slow_sphere);
+
+    let timings = result.get_phase_timings().unwrap();
+    assert!(timings.evaluation > timings.sampling);
+    assert!(timings.evaluation > timings.geometry);
+
+    let percentages = result.get_phase_percentages().unwrap();
+    let total = percentages.sampling + percentages.evaluation + percentages.geometry;
+
+    assert!(percentages.evaluation > 50.0);
+    assert!(total <= 100.0 + 1e-9);
+    assert!((total + percentages.other - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn maximize_stops_before_time_budget() {
+    // every population evaluation takes well over 20ms
+    let slow_sphere = |x: &Point| {
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        neg_sphere(x)
+    };
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 2000, 1_000_000, 120);
+    optimizer.set_seed(4);
+    #[cfg(feature = "parallel")]
+    optimizer.set_max_threads(1).unwrap();
+    optimizer.set_time_budget(std::time::Duration::from_millis(300));
+
+    let result = optimizer.maximize(slow_sphere);
+
+    assert_eq!(result.get_exit_code(), 3);
+    assert!(result.get_best_f().is_some());
+    // the last loop is predicted to fit, leaving slack only for scheduling noise
+    assert!(result.get_time_elapsed() < std::time::Duration::from_millis(350));
+}
+
+#[test]
+fn maximize_with_past_deadline() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 100, 5000, 120);
+    optimizer.set_deadline(std::time::Instant::now());
+
+    let result = optimizer.maximize(neg_sphere);
+
+    // only the initial point is evaluated
+    assert_eq!(result.get_exit_code(), 3);
+    assert_eq!(result.get_loops(), 0);
+    assert_eq!(result.get_fn_evals(), 1);
+    assert_eq!(result.get_best_x(), Some(&point![3.0; 2]));
+}
+
+#[test]
+fn best_x_within_bounds_when_optimum_on_boundary() {
+    // the maximum lies in the upper corner of the search space
+    let corner_seeker = |x: &Point| x.sum();
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![0.0; 3], -5.0, 5.0, 0.01, 0.0, 300, 100_000, 120);
+    optimizer.set_seed(5);
+
+    let result = optimizer.maximize(corner_seeker);
+    let bounds = HypercubeBounds::new(3, -5.0, 5.0);
+
+    assert!(bounds.contains(result.get_best_x().unwrap()));
+    assert!(result.get_best_f().unwrap() > 14.0);
+}
+
+#[test]
+fn maximize_with_small_initial_hypercube() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![4.5, -4.5], -5.0, 5.0, 0.01, 0.0, 50, 100_000, 120);
+    optimizer.set_seed(6);
+    optimizer.set_initial_scale(0.2);
+
+    let result = optimizer.maximize(neg_sphere);
+    let history = result.get_history();
+
+    // the first hypercube has a side of 2 and stays inside the search space
+    assert!((history[0].diagonal_len - 2.0 * 2f64.sqrt()).abs() < 1e-9);
+    assert!(HypercubeBounds::new(2, -5.0, 5.0).contains(result.get_best_x().unwrap()));
+}
+
+#[test]
+fn maximize_with_log_normalization() {
+    // images range from -1 at the optimum to -1e50 in the corners of the search space
+    let steep_sphere = |x: &Point| -(10f64.powf(-neg_sphere(x)));
+
+    for normalization in [
+        OutputNormalization::Log,
+        OutputNormalization::Rank,
+        OutputNormalization::ZScore,
+    ] {
+        let mut optimizer =
+            HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 1e-4, 500, 100_000, 120);
+        optimizer.set_seed(7);
+        optimizer.set_output_normalization(normalization);
+
+        let result = optimizer.maximize(steep_sphere);
+
+        // reported values are raw
+        let best_x = result.get_best_x().unwrap();
+        assert_eq!(result.get_best_f().unwrap(), steep_sphere(best_x));
+        assert!(result.get_best_f().unwrap() > -1.5, "{:?}", normalization);
+    }
+}
+
+#[test]
+fn maximize_with_input_transform() {
+    // the parameters live on very different scales
+    let badly_scaled = |p: &Point| {
+        let a = (p.get(0).unwrap() - 1000.0) / 100.0;
+        let b = (p.get(1).unwrap() - 0.001) / 0.0001;
+        -(a * a + b * b)
+    };
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![2.0, -2.0], -5.0, 5.0, 0.01, 1e-6, 300, 100_000, 120);
+    optimizer.set_seed(8);
+    optimizer.set_input_transform(InputTransform::scale_offset(
+        point![100.0, 0.0001],
+        point![1000.0, 0.001],
+    ));
+    let handle = optimizer.best_handle();
+
+    let result = optimizer.maximize(badly_scaled);
+    let best_x = result.get_best_x().unwrap();
+
+    // the best point is reported as native parameters
+    assert!((best_x.get(0).unwrap() - 1000.0).abs() < 10.0);
+    assert!((best_x.get(1).unwrap() - 0.001).abs() < 1e-5);
+    assert_eq!(result.get_best_f().unwrap(), badly_scaled(best_x));
+    assert_eq!(handle.get().unwrap().get_point(), best_x);
+}
+
+#[test]
+fn maximize_with_gradient_and_input_transform() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 20, 5000, 120);
+    optimizer.set_seed(9);
+    optimizer.set_input_transform(InputTransform::scale_offset(
+        point![2.0, 0.5],
+        point![1.0, 1.0],
+    ));
+
+    let result = optimizer.maximize_with_gradient(&(neg_sphere, neg_sphere_gradient));
+
+    // the maximum of the sphere function lies at the native origin
+    for coord in result.get_best_x().unwrap().iter() {
+        assert!(coord.abs() < 1e-6);
+    }
+}
+
+#[test]
+fn maximize_with_constraint_repair() {
+    // maximum of the objective lies outside the feasible half-plane x + y <= 1
+    let objective = |x: &Point| {
+        let dx = x.get(0).unwrap() - 2.0;
+        let dy = x.get(1).unwrap() - 2.0;
+        -(dx * dx + dy * dy)
+    };
+
+    let infeasible_evals = Arc::new(AtomicUsize::new(0));
+    let counter = infeasible_evals.clone();
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![0.0; 2], -5.0, 5.0, 0.01, 1e-6, 300, 100_000, 120);
+    optimizer.set_seed(10);
+
+    // project onto the half-plane
+    optimizer.set_repair(|x: &Point| {
+        let excess = (x.sum() - 1.0).max(0.0) / 2.0;
+        Point::from_vec(x.iter().map(|c| c - excess).collect())
+    });
+
+    let result = optimizer.maximize(|x: &Point| {
+        if x.sum() > 1.0 + 1e-9 {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+        objective(x)
+    });
+
+    assert_eq!(infeasible_evals.load(Ordering::SeqCst), 0);
+
+    for coord in result.get_best_x().unwrap().iter() {
+        assert!((coord - 0.5).abs() < 0.05);
+    }
+}
+
+#[test]
+fn maximize_with_repair_bounds() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![-1.0; 2], -5.0, 5.0, 0.01, 1e-6, 100, 100_000, 120);
+    optimizer.set_seed(11);
+    optimizer.set_repair_bounds(point![-5.0, -5.0], point![5.0, -1.0]);
+
+    let result = optimizer.maximize(neg_sphere);
+    let best_x = result.get_best_x().unwrap();
+
+    // the second coordinate is stuck on the face of the feasible box
+    assert_eq!(*best_x.get(1).unwrap(), -1.0);
+    assert!(best_x.get(0).unwrap().abs() < 0.1);
+}
+
+#[test]
+fn maximize_on_grid() {
+    let off_grid_evals = Arc::new(AtomicUsize::new(0));
+    let counter = off_grid_evals.clone();
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.1, 2.9], -5.0, 5.0, 0.01, 1e-6, 200, 100_000, 120);
+    optimizer.set_seed(12);
+    optimizer.set_grid_steps(point![0.25, 0.0]);
+
+    // the continuous maximum lies between grid points
+    let result = optimizer.maximize(|x: &Point| {
+        let x0 = *x.get(0).unwrap();
+        let steps = (x0 + 5.0) / 0.25;
+
+        if (steps - steps.round()).abs() > 1e-9 {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+
+        -((x0 - 0.1).powi(2) + x.get(1).unwrap().powi(2))
+    });
+
+    assert_eq!(off_grid_evals.load(Ordering::SeqCst), 0);
+
+    let best_x = result.get_best_x().unwrap();
+    assert_eq!(*best_x.get(0).unwrap(), 0.0);
+    assert!(best_x.get(1).unwrap().abs() < 0.05);
+}
+
+#[test]
+fn maximize_with_per_dimension_tol_x() {
+    let run = |tol_x: Point| {
+        let mut optimizer =
+            HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 200, 1_000_000, 120);
+        optimizer.set_seed(13);
+        optimizer.set_tol_x(tol_x);
+        optimizer.maximize(neg_sphere)
+    };
+
+    // loose along both dimensions: the best point settles long before the loop budget runs out
+    let loose = run(point![1.0, 1.0]);
+    assert_eq!(loose.get_exit_code(), 0);
+    assert!(loose.get_loops() < 200);
+
+    // a tight tolerance along a single dimension keeps the optimization going
+    let tight = run(point![1.0, 1e-300]);
+    assert!(tight.get_loops() > loose.get_loops());
+}
+
+#[test]
+fn maximize_with_dimension_weights() {
+    let run = |weights: Option<Point>| {
+        let mut optimizer =
+            HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 1e-6, 200, 1_000_000, 120);
+        optimizer.set_seed(14);
+        if let Some(weights) = weights {
+            optimizer.set_dimension_weights(weights);
+        }
+        optimizer.maximize(neg_rosenbrock)
+    };
+
+    // unit weights are the same as no weights
+    let unweighted = run(None);
+    let unit = run(Some(point![1.0; 2]));
+
+    assert_eq!(unweighted.get_loops(), unit.get_loops());
+    assert_eq!(unweighted.get_best_f(), unit.get_best_f());
+
+    // a heavily weighted dimension changes how fast the hypercube shrinks
+    let weighted = run(Some(point![1.0, 100.0]));
+
+    assert_ne!(weighted.get_history(), unweighted.get_history());
+    assert!(weighted.get_best_f().unwrap() > -1.0);
+}
+
+#[test]
+fn maximize_noisy_with_plateau_detection() {
+    // deterministic noise that keeps consecutive best evaluations from ever matching
+    let noisy_sphere = |x: &Point| {
+        let noise = (1e4 * x.get(0).unwrap() * x.get(1).unwrap()).sin() * 0.5;
+        neg_sphere(x) + noise
+    };
+
+    let run = |plateau_detection: Option<PlateauDetection>| {
+        let mut optimizer =
+            HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 1e-12, 300, 1_000_000, 120);
+        optimizer.set_seed(15);
+        if let Some(plateau_detection) = plateau_detection {
+            optimizer.set_plateau_detection(plateau_detection);
+        }
+        optimizer.maximize(noisy_sphere)
+    };
+
+    let tolerance = run(None);
+    let plateau = run(Some(PlateauDetection::default()));
+
+    assert_eq!(plateau.get_exit_code(), 0);
+    assert!(plateau.get_loops() < tolerance.get_loops());
+    assert!(plateau.get_best_f().unwrap() > -1.0);
+}
+
+#[test]
+fn maximize_noisy_with_resampling() {
+    // noise that differs between repeated evaluations of the same point
+    let calls = AtomicU64::new(0);
+    let noisy_sphere = |x: &Point| {
+        let call = calls.fetch_add(1, Ordering::Relaxed);
+        neg_sphere(x) + (call as f64 * 12.9898).sin() * 0.1
+    };
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 1e-12, 50, 1_000_000, 120);
+    optimizer.set_seed(4);
+    optimizer.set_resampling(Resampling::default());
+
+    let result = optimizer.maximize(noisy_sphere);
+
+    let best_f = result.get_best_f().unwrap();
+    let interval = result.get_best_f_interval().unwrap();
+
+    assert_eq!(interval.mean, best_f);
+    assert!(interval.lower < best_f && best_f < interval.upper);
+    assert_eq!(interval.confidence, 0.95);
+    assert!(best_f > -1.0);
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 1e-12, 50, 1_000_000, 120);
+    optimizer.set_seed(4);
+
+    assert!(optimizer
+        .maximize(neg_sphere)
+        .get_best_f_interval()
+        .is_none());
+}
+
+#[test]
+fn resampling_within_evaluation_budget() {
+    // budgets that run out right before resampling the starting point or a loop best
+    for max_eval in [4, 138] {
+        let calls = AtomicU64::new(0);
+        let counted_sphere = |x: &Point| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            neg_sphere(x)
+        };
+
+        let mut optimizer =
+            HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 1e-12, 50, max_eval, 120);
+        optimizer.set_seed(4);
+        optimizer.set_population_size(40);
+        optimizer.set_resampling(Resampling::default());
+
+        let result = optimizer.maximize(counted_sphere);
+
+        assert_eq!(result.get_exit_code(), 2);
+        assert!(result.get_fn_evals() <= max_eval);
+        assert_eq!(result.get_fn_evals() as u64, calls.load(Ordering::Relaxed));
+    }
+}
+
+#[test]
+fn experiment_over_seeds() {
+    let config = HypercubeOptimizerConfig {
+        stopping: StoppingCriteria {
+            max_loop: 50,
+            ..StoppingCriteria::default()
+        },
+        seed: Some(100),
+        ..HypercubeOptimizerConfig::default()
+    };
+
+    let experiment = run_experiment(neg_sphere, &point![3.0; 2], -5.0, 5.0, &config, 8);
+
+    let seeds: Vec<u64> = experiment.get_runs().iter().map(|r| r.seed).collect();
+    assert_eq!(seeds, (100..108).collect::<Vec<u64>>());
+
+    assert!(experiment.get_median_best_f().unwrap() > -1e-2);
+    assert!(experiment.get_best_f_iqr().unwrap() >= 0.0);
+    assert_eq!(experiment.get_success_rate(-0.1), 1.0);
+    assert_eq!(experiment.get_success_rate(1.0), 0.0);
+
+    // the same configuration reproduces every run
+    let repeated = run_experiment(neg_sphere, &point![3.0; 2], -5.0, 5.0, &config, 8);
+    assert_eq!(experiment.get_median_best_f(), repeated.get_median_best_f());
+}
+
+#[test]
+fn resume_from_periodic_checkpoint() {
+    let path =
+        std::env::temp_dir().join(format!("hypercube-checkpoint-{}.bin", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let optimizer = |seed: u64| {
+        let mut optimizer =
+            HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 1e-12, 1e-12, 20, 1_000_000, 120);
+        optimizer.set_seed(seed);
+        optimizer.set_checkpoint_every(5, &path);
+        optimizer.set_auto_resume(true);
+        optimizer
+    };
+
+    let first = optimizer(8).maximize(neg_sphere);
+
+    let checkpoint = Checkpoint::read(&path).unwrap();
+    assert_eq!(checkpoint.get_loops() % 5, 0);
+    assert!(checkpoint.get_loops() > 0 && checkpoint.get_loops() < first.get_loops());
+    assert!(checkpoint.get_best().get_eval() <= first.get_best_f().unwrap());
+
+    // the same optimization continues after the checkpoint
+    let resumed = optimizer(8).maximize(neg_sphere);
+    assert_eq!(resumed.get_history()[0].loop_index, checkpoint.get_loops());
+    assert!(resumed.get_fn_evals() > checkpoint.get_fn_evals());
+    assert!(resumed.get_best_f().unwrap() >= checkpoint.get_best().get_eval());
+
+    // another configuration ignores the checkpoint
+    let other = optimizer(9).maximize(neg_sphere);
+    assert_eq!(other.get_history()[0].loop_index, 0);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn cancel_stops_optimization() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 1e-12, 1e-12, 200, 1_000_000, 120);
+    optimizer.set_seed(2);
+
+    // cancel from within the objective function once a few loops ran
+    let cancel = optimizer.cancel_handle();
+    let calls = AtomicUsize::new(0);
+    let result = optimizer.maximize(|x: &Point| {
+        if calls.fetch_add(1, Ordering::Relaxed) == 1000 {
+            cancel.cancel();
+        }
+        neg_sphere(x)
+    });
+
+    assert_eq!(result.get_exit_code(), 6);
+    assert_eq!(result.get_message(), "optimization cancelled");
+    assert!(result.get_loops() > 0 && result.get_loops() < 200);
+    assert!(result.get_best_f().is_some());
+
+    // a new optimization withdraws the cancellation
+    assert_ne!(optimizer.maximize(neg_sphere).get_exit_code(), 6);
+}
+
+#[test]
+fn reset_reproduces_first_run() {
+    let config = HypercubeOptimizerConfig {
+        stopping: StoppingCriteria {
+            tol_f: 1e-12,
+            max_loop: 100,
+            max_eval: 1_000_000,
+            ..StoppingCriteria::default()
+        },
+        seed: Some(21),
+        initial_scale: 0.5,
+        restart_policy: Some(RestartPolicy::default()),
+        ..HypercubeOptimizerConfig::default()
+    };
+    let mut optimizer = HypercubeOptimizer::with_config(point![3.0; 2], -5.0, 5.0, config);
+
+    let first = optimizer.maximize(neg_rastrigin);
+
+    optimizer.reset();
+    let second = optimizer.maximize(neg_rastrigin);
+
+    assert_eq!(first.get_best_x(), second.get_best_x());
+    assert_eq!(first.get_fn_evals(), second.get_fn_evals());
+
+    // a new seed explores differently
+    optimizer.reset_with_seed(22);
+    let third = optimizer.maximize(neg_rastrigin);
+
+    assert_ne!(first.get_best_x(), third.get_best_x());
+    assert_eq!(optimizer.get_config().seed, Some(22));
+}
+
+#[test]
+fn clones_optimize_on_other_threads() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 1e-12, 100, 1_000_000, 120);
+    optimizer.set_seed(6);
+
+    let events = Arc::new(AtomicUsize::new(0));
+    let counter = events.clone();
+    optimizer.set_observer(move |_: &OptimizerEvent| {
+        counter.fetch_add(1, Ordering::Relaxed);
+    });
+
+    // the clone has its own cancellation handle
+    let clone = optimizer.clone();
+    optimizer.cancel_handle().cancel();
+    assert!(!clone.cancel_handle().is_cancelled());
+
+    // and runs exactly like the original would
+    let handle = std::thread::spawn(move || {
+        let mut clone = clone;
+        clone.maximize(neg_sphere)
+    });
+    let from_clone = handle.join().unwrap();
+    let from_original = optimizer.maximize(neg_sphere);
+
+    assert_eq!(from_clone.get_best_x(), from_original.get_best_x());
+    assert!(optimizer.best_handle().get().is_some());
+
+    // both report to the same observer
+    assert!(events.load(Ordering::Relaxed) > 2 * from_original.get_loops() as usize);
+}
+
+#[test]
+fn population_snapshots_every_loop() {
+    let dir = std::env::temp_dir().join(format!("hypercube-snapshots-{}", std::process::id()));
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 1e-12, 1e-12, 10, 1_000_000, 120);
+    optimizer.set_seed(12);
+    optimizer.set_population_snapshots(&dir, SnapshotFormat::Csv);
+
+    let result = optimizer.maximize(neg_sphere);
+
+    let mut files: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+
+    assert_eq!(files.len(), result.get_loops() as usize);
+    assert_eq!(files[0], "population_00000.csv");
+
+    let first = std::fs::read_to_string(dir.join(&files[0])).unwrap();
+    let mut lines = first.lines();
+
+    assert_eq!(lines.next(), Some("x0,x1,f"));
+    assert!(lines.all(|line| line.split(',').count() == 3));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn manifest_reproduces_run() {
+    // no seed is configured, so the manifest records the one that was drawn
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 1e-12, 1e-12, 50, 1_000_000, 120);
+    optimizer.set_initial_candidates(vec![point![0.5, -1.0]]);
+    optimizer.set_objective_name("neg_rastrigin");
+    optimizer.set_population_size(25);
+
+    let original = optimizer.maximize(neg_rastrigin);
+    let manifest = original.get_manifest().unwrap();
+
+    assert_eq!(manifest.crate_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(manifest.init_population_size, 25);
+    assert_eq!(manifest.objective_name.as_deref(), Some("neg_rastrigin"));
+    assert!(manifest
+        .to_json()
+        .contains("\"objective_name\": \"neg_rastrigin\""));
+
+    let reproduced = HypercubeOptimizer::from_manifest(manifest).maximize(neg_rastrigin);
+
+    assert_eq!(reproduced.get_best_x(), original.get_best_x());
+    assert_eq!(reproduced.get_fn_evals(), original.get_fn_evals());
+    assert_eq!(reproduced.get_manifest(), Some(manifest));
+
+    // another objective function gives itself away through the hash
+    let other = HypercubeOptimizer::from_manifest(manifest).maximize(neg_sphere);
+
+    assert_ne!(
+        other.get_manifest().unwrap().objective_hash,
+        manifest.objective_hash
+    );
+}
+
+#[test]
+fn maximize_with_mirrored_sampling() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 4], -5.0, 5.0, 1e-6, 1e-9, 200, 1_000_000, 120);
+    optimizer.set_seed(5);
+    optimizer.set_sampler(Sampler::Mirrored);
+
+    let result = optimizer.maximize(neg_sphere);
+
+    assert_eq!(optimizer.get_config().sampler, Sampler::Mirrored);
+    assert!(result.get_best_f().unwrap() > -1e-2);
+}
+
+#[test]
+fn gaussian_sampling_converges_faster() {
+    let run = |sampler: Sampler| {
+        let mut optimizer =
+            HypercubeOptimizer::new(point![3.0; 4], -5.0, 5.0, 1e-12, 1e-12, 30, 1_000_000, 120);
+        optimizer.set_seed(5);
+        optimizer.set_sampler(sampler);
+        optimizer.maximize(neg_sphere).get_best_f().unwrap()
+    };
+
+    let uniform = run(Sampler::Uniform);
+    let gaussian = run(Sampler::Gaussian {
+        fraction: 0.5,
+        std_dev: 0.05,
+    });
+
+    assert!(gaussian > uniform);
+}
+
+#[test]
+fn first_population_from_file() {
+    let path = std::env::temp_dir().join(format!("hypercube-doe-{}.csv", std::process::id()));
+    std::fs::write(&path, "x0,x1\n4,4\n-3,2\n0,0\n1,-1\n").unwrap();
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 1e-12, 1e-12, 5, 1_000_000, 120);
+    optimizer.set_seed(3);
+    optimizer.set_initial_population_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let result = optimizer.maximize(neg_sphere);
+
+    // the optimum is part of the first population, and every loop evaluates four points
+    assert_eq!(result.get_best_x(), Some(&point![0.0, 0.0]));
+    assert_eq!(result.get_fn_evals(), 1 + 4 * result.get_loops());
+
+    // points outside the search space are rejected
+    let path_json = path.with_extension("json");
+    std::fs::write(&path_json, "[[0.5, 6.0]]").unwrap();
+
+    let error = optimizer
+        .set_initial_population_file(&path_json)
+        .unwrap_err();
+    std::fs::remove_file(&path_json).unwrap();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn successive_halving_finds_best_basin() {
+    let init_points = vec![
+        point![-4.0, -4.0],
+        point![4.0, 4.0],
+        point![-4.0, 4.0],
+        point![4.0, -4.0],
+        point![2.0, -3.0],
+        point![-3.0, 2.0],
+        point![0.2, -0.3],
+        point![3.0, 1.0],
+    ];
+    let config = HypercubeOptimizerConfig {
+        stopping: StoppingCriteria {
+            tol_x: 1e-9,
+            tol_f: 1e-9,
+            max_eval: 40_000,
+            ..StoppingCriteria::default()
+        },
+        seed: Some(11),
+        initial_scale: 0.2,
+        ..HypercubeOptimizerConfig::default()
+    };
+
+    let mut halving = SuccessiveHalving::new(init_points, -5.12, 5.12, config);
+    halving.set_population_size(25);
+
+    let result = Optimizer::maximize(&mut halving, &neg_rastrigin);
+
+    // every basin other than the global one is worse than -0.99
+    assert!(result.get_fn_evals() <= 40_000);
+    assert!(result.get_best_f().unwrap() > -0.1);
+}
+
+#[test]
+fn best_point_metadata() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 3], -5.0, 5.0, 1e-6, 1e-9, 200, 1_000_000, 120);
+    optimizer.set_seed(4);
+
+    // per-dimension breakdown of the negated sphere function
+    let result = optimizer.maximize_with_metadata(|p: &Point| {
+        let terms: Vec<f64> = p.iter().map(|x| -x * x).collect();
+        (terms.iter().sum(), terms)
+    });
+
+    let terms = result.get_best_metadata::<Vec<f64>>().unwrap();
+    let best_x = result.get_best_x().unwrap();
+
+    assert_eq!(terms.len(), 3);
+    assert_eq!(terms.iter().sum::<f64>(), result.get_best_f().unwrap());
+    assert_eq!(terms[0], -best_x.get(0).unwrap().powi(2));
+    assert!(result.get_best_metadata::<String>().is_none());
+}
+
+#[test]
+fn exit_codes_for_exhausted_budgets() {
+    // evaluation budget: the initial point and two populations of 40 points
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 1e-9, 1e-9, 100, 81, 120);
+    let result = optimizer.maximize(neg_sphere);
+
+    assert_eq!(result.get_exit_code(), 2);
+    assert_eq!(result.get_fn_evals(), 81);
+
+    // loop budget
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 1e-9, 1e-9, 3, 1_000_000, 120);
+    let result = optimizer.maximize(neg_sphere);
+
+    assert_eq!(result.get_exit_code(), 2);
+    assert_eq!(result.get_loops(), 3);
+
+    // timeout
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 1e-9, 1e-9, 100, 1_000_000, 0);
+    let result = optimizer.maximize(neg_sphere);
+
+    assert_eq!(result.get_exit_code(), 3);
+    assert_eq!(result.get_loops(), 0);
+}
+
+#[test]
+fn exit_code_for_too_large_bounds() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![0.0; 4], -1e6, 1e6, 1e-9, 1e-9, 100, 5000, 120);
+    let result = optimizer.maximize(neg_sphere);
+
+    assert_eq!(result.get_exit_code(), 4);
+    assert_eq!(result.get_fn_evals(), 0);
+
+    // an explicit population size makes the search space usable
+    optimizer.set_population_size(50);
+    let result = optimizer.maximize(neg_sphere);
+
+    assert_eq!(result.get_exit_code(), 2);
+    assert!(result.get_fn_evals() <= 5000);
+}
+
+#[test]
+fn input_convergence_waits_for_image_improvement() {
+    let evaluations = AtomicUsize::new(0);
+
+    // the image keeps creeping up with every evaluation wherever the best point is
+    let objective = |p: &Point| {
+        let n = evaluations.fetch_add(1, Ordering::Relaxed);
+        n as f64 - 1e-6 * p.len()
+    };
+
+    // every move of the best point is within the input tolerance
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 100.0, 0.1, 100, 1_000_000, 120);
+    optimizer.set_seed(5);
+    let result = optimizer.maximize(objective);
+
+    assert_eq!(result.get_exit_code(), 2);
+    assert_eq!(result.get_loops(), 100);
+}
@@ -0,0 +1,41 @@
+use hypercube_optimizer::objective_functions::neg_sphere;
+use hypercube_optimizer::optimizer::HypercubeOptimizer;
+use hypercube_optimizer::point;
+use hypercube_optimizer::point::Point;
+
+#[test]
+fn history_recorded_every_loop() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![1.0; 2], -5.0, 5.0, 0.01, 0.0, 10, 5000, 120);
+
+    let result = optimizer.maximize(neg_sphere);
+    let history = result.get_history();
+
+    assert_eq!(history.len(), 10);
+
+    // function evaluations and best values should never go backwards
+    for pair in history.windows(2) {
+        assert!(pair[1].fn_evals > pair[0].fn_evals);
+        assert!(pair[1].best_f >= pair[0].best_f);
+    }
+
+    assert_eq!(history.last().unwrap().fn_evals, result.get_fn_evals());
+}
+
+#[cfg(feature = "plotting")]
+#[test]
+fn plot_convergence_png_and_svg() {
+    let mut optimizer =
+        HypercubeOptimizer::new(point![1.0; 2], -5.0, 5.0, 0.01, 0.0, 10, 5000, 120);
+
+    let result = optimizer.maximize(neg_sphere);
+
+    for file_name in ["hypercube_convergence.png", "hypercube_convergence.svg"] {
+        let path = std::env::temp_dir().join(file_name);
+
+        result.plot_convergence(&path).unwrap();
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
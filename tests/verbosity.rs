@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+
+use hypercube_optimizer::hypercube::{Hypercube, MAX_POPULATION_SIZE};
+use hypercube_optimizer::objective_functions::neg_sphere;
+use hypercube_optimizer::optimizer::{HypercubeOptimizer, Verbosity};
+use hypercube_optimizer::point;
+use hypercube_optimizer::point::Point;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Logger that counts info records, and warning records of the hypercube module
+struct CountingLogger {
+    info_records: AtomicUsize,
+    hypercube_warn_records: AtomicUsize,
+}
+
+impl Log for CountingLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() == Level::Info {
+            self.info_records.fetch_add(1, Ordering::SeqCst);
+        }
+
+        if record.level() == Level::Warn && record.target() == "hypercube_optimizer::hypercube" {
+            self.hypercube_warn_records.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CountingLogger = CountingLogger {
+    info_records: AtomicUsize::new(0),
+    hypercube_warn_records: AtomicUsize::new(0),
+};
+
+static INIT_LOGGER: Once = Once::new();
+
+fn init_logger() {
+    INIT_LOGGER.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+    });
+}
+
+/// Runs an optimization with the given verbosity and returns the number of info records logged
+fn count_info_records(verbosity: Verbosity) -> usize {
+    let before = LOGGER.info_records.load(Ordering::SeqCst);
+
+    let mut optimizer =
+        HypercubeOptimizer::new(point![3.0; 2], -5.0, 5.0, 0.01, 0.0, 20, 5000, 120);
+    optimizer.set_seed(2);
+    optimizer.set_verbosity(verbosity);
+    optimizer.maximize(neg_sphere);
+
+    LOGGER.info_records.load(Ordering::SeqCst) - before
+}
+
+#[test]
+fn verbosity_controls_logged_messages() {
+    init_logger();
+
+    let quiet = count_info_records(Verbosity::Quiet);
+    let summary = count_info_records(Verbosity::Summary);
+    let loops = count_info_records(Verbosity::Loops);
+
+    assert_eq!(quiet, 0);
+    assert_eq!(summary, 3);
+    assert!(loops > summary);
+}
+
+#[test]
+fn clamped_population_is_logged() {
+    init_logger();
+
+    let before = LOGGER.hypercube_warn_records.load(Ordering::SeqCst);
+    let hypercube = Hypercube::new(1, 0.0, 2e6);
+
+    assert_eq!(hypercube.get_population_size(), MAX_POPULATION_SIZE);
+    assert_eq!(
+        LOGGER.hypercube_warn_records.load(Ordering::SeqCst) - before,
+        1
+    );
+}